@@ -0,0 +1,208 @@
+use std::{
+    any::Any,
+    error::Error,
+    thread,
+    panic::{self, AssertUnwindSafe},
+    collections::{VecDeque, HashMap},
+};
+
+use rand::prelude::*;
+use rand_chacha::{ChaChaRng};
+
+use crate::base::{self, GameState, StepFailure, DecisionChoice};
+use crate::snapshot::{Snapshot};
+use crate::solve;
+
+
+/*
+    The known information assistant: given a position (a snapshot of a real game where the draw
+    piles are hidden, see `snapshot.rs`) rank the choices of the next decision.
+
+    Each sample deals the hidden cards at random (consistent with every card that has been seen)
+    and plays every choice out a number of times with random decisions after it. Choices are ranked
+    by their average score over all samples, the same choice in every sample is the one with the
+    same label (the card drafted rather than its place among the cards drawn, say).
+*/
+
+
+pub struct AdviceRow {
+    pub choice: String,
+
+    pub playouts: usize,
+    pub victories: usize,
+    pub total_score: i64,
+    pub min_score: i16,
+    pub max_score: i16,
+}
+
+impl AdviceRow {
+    fn new(choice: String) -> AdviceRow {
+        AdviceRow {
+            choice,
+
+            playouts: 0,
+            victories: 0,
+            total_score: 0,
+            min_score: i16::MAX,
+            max_score: i16::MIN,
+        }
+    }
+
+    pub fn mean_score(&self) -> f64 {
+        self.total_score as f64 / self.playouts as f64
+    }
+}
+
+
+struct AdviceJob {
+    decision_point: GameState,
+    choice: DecisionChoice,
+    label: String,
+    seed: String,
+}
+
+// The choices of a decision with their labels.
+type LabelledChoices = Vec<(DecisionChoice, String)>;
+
+// Runs the game (from a step boundary) to the next decision, returning the state the decision's
+// step starts from and the choices it offers.
+fn find_decision(state: &GameState) -> Result<(GameState, LabelledChoices), Box<dyn Error>> {
+    let mut current_state = state.clone();
+
+    loop {
+        let mut working_state = current_state.clone();
+
+        match working_state.step() {
+            Ok(_) => {
+                current_state = working_state;
+                current_state.advance()?;
+            },
+            Err(StepFailure::DecisionRequired) => {
+                let decision = working_state.effect_stack.last()
+                    .and_then(|e| e.as_decision())
+                    .ok_or_else(|| Box::<dyn Error>::from("Decision required without a decision."))?;
+
+                let choices = decision.valid_choices(&working_state).into_iter()
+                    .map(|choice| {
+                        let label = decision.choice_label(&working_state, &choice);
+                        (choice, label)
+                    })
+                    .collect();
+                return Ok((current_state, choices));
+            },
+            Err(StepFailure::GameOverVictory) | Err(StepFailure::GameOverDefeat) => {
+                bail!("The game ends before the next decision ({}).", working_state.game_over_reason.as_ref().unwrap());
+            },
+            Err(fail) => {
+                return Err(Box::<dyn Error>::from(fail));
+            },
+        }
+    }
+}
+
+// The message a thread panicked with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "no message".to_string()
+    }
+}
+
+pub fn advise(position: &Snapshot, samples: usize, playouts: usize, threads: usize) -> Result<Vec<AdviceRow>, Box<dyn Error>> {
+    if samples == 0 || playouts == 0 {
+        bail!("Advice needs at least one sample and one playout.");
+    }
+
+    let mut jobs = Vec::new();
+    let mut decision_step = None;
+    for sample in 0..samples {
+        let seed = format!("{}-sample-{}", position.config.seed, sample);
+        let state = position.make_sampled_state(&seed)?;
+
+        let (decision_point, choices) = find_decision(&state)?;
+        decision_step = Some(decision_point.step);
+
+        for (choice, label) in choices.into_iter() {
+            jobs.push(AdviceJob {
+                decision_point: decision_point.clone(),
+                choice,
+                label,
+                seed: seed.clone(),
+            });
+        }
+    }
+
+    println!("Advising on the decision at {} ({} samples, {} playouts per choice).",
+        decision_step.unwrap(), samples, playouts);
+
+    // Deal the jobs out to the threads round robin.
+    let threads = threads.max(1);
+    let mut thread_jobs: Vec<Vec<AdviceJob>> = (0..threads).map(|_| Vec::new()).collect();
+    for (i, job) in jobs.into_iter().enumerate() {
+        thread_jobs[i % threads].push(job);
+    }
+
+    let mut handles = Vec::new();
+    for jobs in thread_jobs.into_iter() {
+        handles.push(thread::spawn(move || -> Result<Vec<(String, GameState)>, String> {
+            let mut results = Vec::new();
+            for job in jobs.iter() {
+                let choice_key = job.choice.to_string();
+                let mut rng = ChaChaRng::from_seed(base::make_seed(&format!("{}-{}", job.seed, choice_key)));
+
+                for _ in 0..playouts {
+                    let mut choices = VecDeque::new();
+                    choices.push_back(job.choice.clone());
+
+                    // a panic deep in the rules is reported as the failure of this playout
+                    let end = panic::catch_unwind(AssertUnwindSafe(|| {
+                            solve::playout(&job.decision_point, choices, &mut rng).map_err(|e| e.to_string())
+                        }))
+                        .map_err(|payload| format!("panicked ({})", panic_message(payload.as_ref())))
+                        .and_then(|result| result)
+                        .map_err(|e| format!("Playout of `{}` ({}) failed: {}", job.label, choice_key, e))?;
+                    results.push((job.label.clone(), end));
+                }
+            }
+            Ok(results)
+        }));
+    }
+
+    let mut rows: HashMap<String, AdviceRow> = HashMap::new();
+    let mut order = Vec::new();
+    for handle in handles {
+        let results = handle.join()
+            .map_err(|payload| Box::<dyn Error>::from(format!("An advice thread panicked: {}", panic_message(payload.as_ref()))))??;
+
+        for (choice, end) in results.into_iter() {
+            if !rows.contains_key(&choice) {
+                order.push(choice.clone());
+            }
+            let row = rows.entry(choice.clone()).or_insert_with(|| AdviceRow::new(choice));
+
+            let score = end.score_game();
+            row.playouts += 1;
+            row.total_score += score as i64;
+            row.min_score = row.min_score.min(score);
+            row.max_score = row.max_score.max(score);
+            if end.step == base::GameStep::Victory {
+                row.victories += 1;
+            }
+        }
+    }
+
+    let mut rows: Vec<AdviceRow> = order.into_iter().map(|c| rows.remove(&c).unwrap()).collect();
+    rows.sort_by(|a, b| b.mean_score().partial_cmp(&a.mean_score()).unwrap());
+
+    println!("  rank  choice                          mean   win%    min   max  playouts");
+    for (rank, row) in rows.iter().enumerate() {
+        println!("  {:>4}  {:<30} {:>6.2} {:>6.1} {:>6} {:>5} {:>9}",
+            rank + 1, row.choice, row.mean_score(), 100.0 * row.victories as f64 / row.playouts as f64,
+            row.min_score, row.max_score, row.playouts);
+    }
+
+    Ok(rows)
+}
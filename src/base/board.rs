@@ -3,6 +3,7 @@
 use std::{
     sync::{Arc},
     iter::*,
+    cmp::{min, max},
 };
 
 use super::{
@@ -18,7 +19,9 @@ pub struct LandDescription {
     pub adjacent: Vec<u8>,
     pub kind: LandKind,
     pub is_coastal: bool,
-    pub edge_range: Option<(u8, u8)>, // ranges from 0 to 27... need to figure out the tiling, is it 7 gaps a side?
+    // The part of the edge of the board the land runs along (see `EDGE_SIDE_LENGTH`), none for
+    // lands inside the board.
+    pub edge_range: Option<(u8, u8)>,
 
     pub starting_tokens: TokenMap<u8>,
     pub starting_invaders: InvaderMap<u8>,
@@ -56,6 +59,17 @@ pub struct LandState {
     // more specific effects
     pub defense: u16,
     pub fear_generated_here_this_round: u8,
+    // invader actions that don't happen here this round, builds by the kind of building
+    pub skip_explore: bool,
+    pub skip_build: InvaderMap<bool>,
+    pub skip_ravage: bool,
+    // damage to the invaders after each town, city or dahan destroyed here this round, which may
+    // also go to adjacent lands when it spreads
+    pub vengeance: u8,
+    pub vengeance_spreads: bool,
+    // destroyed here by the effect being resolved, until its vengeance is done (always 0 between
+    // effects)
+    pub vengeance_owed: u8,
 }
 
 #[derive(Clone)]
@@ -65,9 +79,65 @@ pub struct TableState {
     pub lands: Vec<LandState>,
 }
 
+/*
+    Positions along the edge of a board go around it from 0 to 27, 7 to each of its 4 sides, the
+    ocean being on side 0. A range may wrap past 27 back to 0 (`(25, 1)`).
+
+    Boards of a table are joined side to side, the lands along the two sides are adjacent where
+    their ranges meet. Sides meet in opposite directions: the start of one is the end of the other.
+*/
+pub const EDGE_SIDE_LENGTH: u8 = 7;
+pub const EDGE_SIDE_COUNT: u8 = 4;
+
+// Two boards joined along a side of each, as (board, side).
+#[derive(Copy, Clone)]
+pub struct BoardJoin {
+    pub a: (u8, u8),
+    pub b: (u8, u8),
+}
+
+// The standard layouts: two boards back to back, three in a ring and four as two back to back
+// pairs joined in a ring.
+pub fn standard_layout(board_count: usize) -> Vec<BoardJoin> {
+    let join = |a, b| BoardJoin { a, b };
+    match board_count {
+        2 => vec![join((0, 2), (1, 2))],
+        3 => vec![join((0, 1), (1, 3)), join((1, 1), (2, 3)), join((2, 1), (0, 3))],
+        4 => vec![join((0, 2), (1, 2)), join((1, 1), (2, 3)), join((2, 2), (3, 2)), join((3, 1), (0, 3))],
+        _ => Vec::new(),
+    }
+}
+
+impl LandDescription {
+    // The part of the edge of the land on a side of its board, from the start of the side.
+    pub fn edge_on_side(&self, side: u8) -> Option<(i16, i16)> {
+        let (start, end) = self.edge_range?;
+        let edge_length = (EDGE_SIDE_LENGTH * EDGE_SIDE_COUNT) as i16;
+        let (start, mut end) = (start as i16, end as i16);
+        if end < start {
+            end += edge_length;
+        }
+
+        let side_start = (side * EDGE_SIDE_LENGTH) as i16;
+        let side_end = side_start + EDGE_SIDE_LENGTH as i16;
+        [0, edge_length].iter()
+            .map(|shift| (max(start - shift, side_start), min(end - shift, side_end)))
+            .find(|(lo, hi)| lo < hi)
+            .map(|(lo, hi)| (lo - side_start, hi - side_start))
+    }
+}
+
+// Whether two lands on joined sides meet.
+fn lands_meet(a: &LandDescription, a_side: u8, b: &LandDescription, b_side: u8) -> bool {
+    let side = EDGE_SIDE_LENGTH as i16;
+    match (a.edge_on_side(a_side), b.edge_on_side(b_side)) {
+        (Some((a_lo, a_hi)), Some((b_lo, b_hi))) => max(a_lo, side - b_hi) < min(a_hi, side - b_lo),
+        _ => false,
+    }
+}
+
 pub fn make_map(content: &Vec<Box<dyn ContentPack>>, board_names: Vec<&str>) -> TableDescription {
     let mut boards = Vec::new();
-    let mut lands = Vec::new();
     let mut land_count = 0;
     let mut board_count = 0;
 
@@ -77,8 +147,10 @@ pub fn make_map(content: &Vec<Box<dyn ContentPack>>, board_names: Vec<&str>) ->
             let land_mut = Arc::get_mut(land).unwrap();
             land_mut.index_on_table += land_count;
             land_mut.parent_board_index = board_count;
-            
-            lands.push(land.clone());
+            // boards list adjacency by index on the board
+            for adjacent in land_mut.adjacent.iter_mut() {
+                *adjacent += land_count;
+            }
         }
 
         land_count += board.lands.len() as u8;
@@ -87,6 +159,28 @@ pub fn make_map(content: &Vec<Box<dyn ContentPack>>, board_names: Vec<&str>) ->
         board_count += 1;
     }
 
+    // the lands along joined sides are adjacent (oceans don't reach past their board)
+    for join in standard_layout(boards.len()) {
+        let ((a_board, a_side), (b_board, b_side)) = (join.a, join.b);
+        let mut meeting = Vec::new();
+        for a in boards[a_board as usize].lands.iter().filter(|l| l.kind != LandKind::Ocean) {
+            for b in boards[b_board as usize].lands.iter().filter(|l| l.kind != LandKind::Ocean) {
+                if lands_meet(a, a_side, b, b_side) {
+                    meeting.push((a.index_on_board, b.index_on_board, a.index_on_table, b.index_on_table));
+                }
+            }
+        }
+
+        for (a_index, b_index, a_table_index, b_table_index) in meeting {
+            Arc::get_mut(&mut boards[a_board as usize].lands[a_index as usize]).unwrap().adjacent.push(b_table_index);
+            Arc::get_mut(&mut boards[b_board as usize].lands[b_index as usize]).unwrap().adjacent.push(a_table_index);
+        }
+    }
+
+    let lands = boards.iter()
+        .flat_map(|b| b.lands.iter().cloned())
+        .collect();
+
     TableDescription {
         boards,
         lands,
@@ -105,6 +199,33 @@ impl TableDescription {
             .filter(|l| l.adjacent.contains(&adjacent_to_index))
             .collect()
     }
+
+    // The lands within `range` of any of `sources` (range 0 being the sources themselves), by
+    // index on the table in ascending order.
+    pub fn get_lands_within(&self, sources: &[u8], range: u8) -> Vec<u8> {
+        let mut distance: Vec<Option<u8>> = vec![None; self.lands.len()];
+        let mut frontier: Vec<u8> = sources.to_vec();
+        for source in sources.iter() {
+            distance[*source as usize] = Some(0);
+        }
+
+        for step in 1..=range {
+            let mut next = Vec::new();
+            for index in frontier.iter() {
+                for adjacent in self.lands[*index as usize].adjacent.iter() {
+                    if distance[*adjacent as usize].is_none() {
+                        distance[*adjacent as usize] = Some(step);
+                        next.push(*adjacent);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        (0..self.lands.len() as u8)
+            .filter(|i| distance[*i as usize].is_some())
+            .collect()
+    }
 }
 
 impl LandState {
@@ -118,6 +239,11 @@ impl LandState {
 
         self.defense = 0;
         self.fear_generated_here_this_round = 0;
+        self.skip_explore = false;
+        self.skip_build = InvaderMap::new(false);
+        self.skip_ravage = false;
+        self.vengeance = 0;
+        self.vengeance_spreads = false;
     }
 
     pub fn get_count(&self, pk: &PieceKind) -> usize {
@@ -151,6 +277,12 @@ impl TableState {
 
                     defense: 0,
                     fear_generated_here_this_round: 0,
+                    skip_explore: false,
+                    skip_build: InvaderMap::new(false),
+                    skip_ravage: false,
+                    vengeance: 0,
+                    vengeance_spreads: false,
+                    vengeance_owed: 0,
                 });
             }
         }
@@ -160,4 +292,65 @@ impl TableState {
             lands,
         }
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CoreContent;
+
+    fn make_table(board_count: usize) -> TableDescription {
+        let content: Vec<Box<dyn ContentPack>> = vec![Box::new(CoreContent::new())];
+        make_map(&content, ["A", "B", "C", "D"][..board_count].to_vec())
+    }
+
+    #[test]
+    fn layouts_join_every_board_symmetrically() {
+        for board_count in 1..=4 {
+            let table = make_table(board_count);
+            assert_eq!(table.land_count as usize, 9 * board_count);
+            assert_eq!(table.lands.len(), 9 * board_count);
+
+            for land in table.lands.iter() {
+                assert_eq!(table.lands[land.index_on_table as usize].index_on_table, land.index_on_table);
+                for adjacent in land.adjacent.iter() {
+                    let other = &table.lands[*adjacent as usize];
+                    assert!(other.adjacent.contains(&land.index_on_table),
+                        "{} boards, lands {} and {}", board_count, land.index_on_table, adjacent);
+                    // oceans don't reach past their board
+                    if land.kind == LandKind::Ocean {
+                        assert_eq!(other.parent_board_index, land.parent_board_index);
+                    }
+                }
+            }
+
+            // every join links its boards
+            for join in standard_layout(board_count) {
+                let (a, b) = (join.a.0, join.b.0);
+                assert!(table.lands.iter().any(|l| l.parent_board_index == a
+                    && l.adjacent.iter().any(|i| table.lands[*i as usize].parent_board_index == b)),
+                    "{} boards, boards {} and {}", board_count, a, b);
+            }
+
+            // and the island is in one piece
+            let inland: Vec<u8> = table.lands.iter()
+                .filter(|l| l.kind != LandKind::Ocean)
+                .map(|l| l.index_on_table)
+                .collect();
+            assert_eq!(table.get_lands_within(&inland[..1], 255).iter().filter(|i| inland.contains(i)).count(), inland.len());
+        }
+    }
+
+    #[test]
+    fn lands_within_range_grow_from_the_sources() {
+        let table = make_table(1);
+        assert_eq!(table.get_lands_within(&[1], 0), vec![1]);
+
+        let mut adjacent = table.lands[1].adjacent.clone();
+        adjacent.push(1);
+        adjacent.sort();
+        assert_eq!(table.get_lands_within(&[1], 1), adjacent);
+        assert_eq!(table.get_lands_within(&[1], 8).len(), 9);
+    }
+}
@@ -2,17 +2,20 @@
 use std::{
     fmt,
     iter::*,
+    str::FromStr,
 };
 
 use super::{
     board::{BoardDescription},
-    deck::{FearCardDescription, PowerCardDescription},
+    deck::{FearCardDescription, PowerCardDescription, BlightCardDescription, EventCardDescription, InvaderCard, STANDARD_INVADER_STAGES},
     spirit::{SpiritDescription},
+    piece::{Invader},
+    step::{StepFailure},
     game::{GameState},
 };
 
 
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
 pub enum InvaderActionKind {
     Ravage,
     Build,
@@ -29,6 +32,20 @@ impl fmt::Display for InvaderActionKind {
     }
 }
 
+// The inverse of the Display impl.
+impl FromStr for InvaderActionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Ravage" => Ok(InvaderActionKind::Ravage),
+            "Build" => Ok(InvaderActionKind::Build),
+            "Explore" => Ok(InvaderActionKind::Explore),
+            _ => Err(format!("unknown invader action `{}`", s)),
+        }
+    }
+}
+
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
 pub enum LandKind {
@@ -51,6 +68,22 @@ impl fmt::Display for LandKind {
     }
 }
 
+// The inverse of the Display impl.
+impl FromStr for LandKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Ocean" => Ok(LandKind::Ocean),
+            "Jungle" => Ok(LandKind::Jungle),
+            "Mountain" => Ok(LandKind::Mountain),
+            "Sands" => Ok(LandKind::Sands),
+            "Wetlands" => Ok(LandKind::Wetlands),
+            _ => Err(format!("unknown land kind `{}`", s)),
+        }
+    }
+}
+
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
 pub enum TerrorLevel {
@@ -77,15 +110,74 @@ pub enum PowerSpeed {
 }
 
 
-pub trait EventCardDescription {
+/*
+    An adversary at one of its levels (0 to 6), each level adds its rules to the ones below it:
 
-}
+    * Setup: changes to the island, run at init (before the spirits set up).
+    * The invader deck: which stage each card of the deck is, and the invader steps.
+    * Fear cards: how many of each terror level.
+    * Escalation: what happens when a stage II card explores.
+    * Loss condition: an extra way for the invaders to win, checked after every step.
 
+    The rest of its rules hook into the game where they apply, each hook does nothing by default:
+
+    * After the setup explore.
+    * After each invader card resolves (escalation, by default) and after the cards advance.
+    * The invader actions themselves: lands without invaders that build anyway, and what follows
+      the damage the invaders do to a land when they ravage.
+    * The invader pieces: their health and damage (as they are added, and again when time passes).
+*/
 pub trait AdversaryDescription : Send + Sync {
+    fn name(&self) -> &'static str;
+    fn level(&self) -> u8;
+    // The difficulty of the level, which scales the score of a game.
+    fn difficulty(&self) -> u8;
+
     fn fear_cards(&self) -> (u8, u8, u8);
+    // The stage of each invader card, from the top of the deck.
+    fn invader_deck_stages(&self) -> Vec<u8> {
+        STANDARD_INVADER_STAGES.to_vec()
+    }
     fn invader_steps(&self) -> Vec<InvaderActionKind>;
 
-    fn setup(&self, game: &mut GameState);
+    fn setup(&self, game: &mut GameState) -> Result<(), StepFailure>;
+    fn after_setup_explore(&self, _game: &mut GameState) -> Result<(), StepFailure> {
+        Ok(())
+    }
+
+    // After the invaders explore with a stage II card.
+    fn escalation(&self, _game: &mut GameState, _card: InvaderCard) -> Result<(), StepFailure> {
+        Ok(())
+    }
+    // After the invaders act with a card, in every land it targets.
+    fn after_invader_card(&self, game: &mut GameState, action: InvaderActionKind, card: InvaderCard) -> Result<(), StepFailure> {
+        if action == InvaderActionKind::Explore && card.phase() == 2 {
+            self.escalation(game, card)?;
+        }
+
+        Ok(())
+    }
+    // After the invader cards advance, at the end of the invader phase.
+    fn after_invader_advance(&self, _game: &mut GameState) -> Result<(), StepFailure> {
+        Ok(())
+    }
+
+    // Whether a land without invaders builds anyway.
+    fn builds_without_invaders(&self, _game: &GameState, _land_index: u8) -> bool {
+        false
+    }
+    // After the invaders damage a land when they ravage (before the dahan fight back), `blighted`
+    // when the damage added blight to it.
+    fn after_ravage_damage(&self, _game: &mut GameState, _land_index: u8, _damage: u16, _blighted: bool) -> Result<(), StepFailure> {
+        Ok(())
+    }
+    // Changes to the health and damage of invaders.
+    fn modify_invader(&self, _invader: &mut Invader) {}
+
+    // The reason the invaders have won, if they have.
+    fn check_loss(&self, _game: &GameState) -> Option<&'static str> {
+        None
+    }
 }
 
 pub trait ContentPack : Send + Sync {
@@ -95,6 +187,10 @@ pub trait ContentPack : Send + Sync {
     fn get_fear_cards(&self) -> Vec<FearCardDescription>;
 
     fn get_power_cards(&self) -> Vec<PowerCardDescription>;
+
+    fn get_blight_cards(&self) -> Vec<BlightCardDescription>;
+
+    fn get_event_cards(&self) -> Vec<EventCardDescription>;
 }
 
 
@@ -136,6 +232,26 @@ pub fn join_fear_cards(content: &Vec<Box<dyn ContentPack>>) -> Vec<FearCardDescr
     result
 }
 
+pub fn join_blight_cards(content: &Vec<Box<dyn ContentPack>>) -> Vec<BlightCardDescription>
+{
+    let mut result = Vec::new();
+    for c in content.iter() {
+        result.extend(c.get_blight_cards());
+    }
+    
+    result
+}
+
+pub fn join_event_cards(content: &Vec<Box<dyn ContentPack>>) -> Vec<EventCardDescription>
+{
+    let mut result = Vec::new();
+    for c in content.iter() {
+        result.extend(c.get_event_cards());
+    }
+    
+    result
+}
+
 pub fn join_power_cards(content: &Vec<Box<dyn ContentPack>>) -> Vec<PowerCardDescription>
 {
     let mut result = Vec::new();
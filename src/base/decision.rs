@@ -1,8 +1,20 @@
 
+use std::{
+    fmt,
+    str::FromStr,
+};
+
 use super::*;
 
 pub trait Decision : Effect {
     fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice>;
+
+    // Chance decisions stand in for a random outcome (see `fate.rs`) rather than a player's choice.
+    fn is_chance(&self) -> bool { false }
+
+    // What a choice means outside of this game, where the same choice should be recognized across
+    // games that only share what is known of the position (see `advise.rs`).
+    fn choice_label(&self, _game: &GameState, choice: &DecisionChoice) -> String { choice.to_string() }
 }
 
 #[derive(Clone)]
@@ -27,17 +39,154 @@ pub enum DecisionChoice {
     AreaPieceSequence(Vec<(u8, PieceKind, usize)>),
 }
 
+/*
+    The text form of a choice is what game records store, so it must stay stable:
+
+    * `choice 2`
+    * `sequence 1 0 3`
+    * `land 4 from 2` (target land, source land)
+    * `spirit 0`
+    * `presence 0 4 from 1` (spirit, target land, source presence)
+    * `damage 1 0 2`
+    * `pieces Explorer:0 Town:1`
+    * `area-pieces 4:Explorer:0 5:Dahan:1`
+*/
+impl fmt::Display for DecisionChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecisionChoice::Sequence(seq) => {
+                write!(f, "sequence")?;
+                for i in seq.iter() {
+                    write!(f, " {}", i)?;
+                }
+                Ok(())
+            },
+            DecisionChoice::Choice(i) => write!(f, "choice {}", i),
+            DecisionChoice::TargetLand{target_land, source_land} => write!(f, "land {} from {}", target_land, source_land),
+            DecisionChoice::TargetSpirit{target_spirit} => write!(f, "spirit {}", target_spirit),
+            DecisionChoice::PlacePresence{spirit, target_land, source_presence} => write!(f, "presence {} {} from {}", spirit, target_land, source_presence),
+            DecisionChoice::Damage(damage) => {
+                write!(f, "damage")?;
+                for d in damage.iter() {
+                    write!(f, " {}", d)?;
+                }
+                Ok(())
+            },
+            DecisionChoice::PieceSequence(pieces) => {
+                write!(f, "pieces")?;
+                for (kind, index) in pieces.iter() {
+                    write!(f, " {}:{}", kind, index)?;
+                }
+                Ok(())
+            },
+            DecisionChoice::AreaPieceSequence(pieces) => {
+                write!(f, "area-pieces")?;
+                for (land, kind, index) in pieces.iter() {
+                    write!(f, " {}:{}:{}", land, kind, index)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+fn parse_number<T: FromStr>(s: &str, what: &str) -> Result<T, String> {
+    s.parse::<T>().map_err(|_| format!("expected a {} but found `{}`", what, s))
+}
+
+impl FromStr for DecisionChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let (kind, args) = match words.split_first() {
+            Some((kind, args)) => (*kind, args),
+            None => return Err("empty choice".to_string()),
+        };
+
+        match (kind, args) {
+            ("sequence", args) => Ok(DecisionChoice::Sequence(
+                args.iter().map(|a| parse_number(a, "index")).collect::<Result<_, _>>()?)),
+            ("choice", [i]) => Ok(DecisionChoice::Choice(parse_number(i, "index")?)),
+            ("land", [target, "from", source]) => Ok(DecisionChoice::TargetLand{
+                target_land: parse_number(target, "land")?,
+                source_land: parse_number(source, "land")?,
+            }),
+            ("spirit", [target]) => Ok(DecisionChoice::TargetSpirit{target_spirit: parse_number(target, "spirit")?}),
+            ("presence", [spirit, target, "from", source]) => Ok(DecisionChoice::PlacePresence{
+                spirit: parse_number(spirit, "spirit")?,
+                target_land: parse_number(target, "land")?,
+                source_presence: parse_number(source, "presence")?,
+            }),
+            ("damage", args) => Ok(DecisionChoice::Damage(
+                args.iter().map(|a| parse_number(a, "damage")).collect::<Result<_, _>>()?)),
+            ("pieces", args) => Ok(DecisionChoice::PieceSequence(
+                args.iter().map(|a| {
+                    match a.split(':').collect::<Vec<_>>().as_slice() {
+                        [kind, index] => Ok((kind.parse::<PieceKind>()?, parse_number(index, "index")?)),
+                        _ => Err(format!("expected `Kind:index` but found `{}`", a)),
+                    }
+                }).collect::<Result<_, _>>()?)),
+            ("area-pieces", args) => Ok(DecisionChoice::AreaPieceSequence(
+                args.iter().map(|a| {
+                    match a.split(':').collect::<Vec<_>>().as_slice() {
+                        [land, kind, index] => Ok((parse_number(land, "land")?, kind.parse::<PieceKind>()?, parse_number(index, "index")?)),
+                        _ => Err(format!("expected `land:Kind:index` but found `{}`", a)),
+                    }
+                }).collect::<Result<_, _>>()?)),
+            _ => Err(format!("could not read choice `{}`", s)),
+        }
+    }
+}
+
 mod card_play;
 mod cascade_blight;
 mod do_damage;
+mod fate;
 mod growth;
+mod innate;
 mod meta;
 mod move_piece;
+mod remove_piece;
+mod spirit;
 
 pub use self::card_play::{DoCardPlayDecision, DoCardPlaysDecision, CardPlaysDecision};
 pub use self::cascade_blight::{CascadeBlightDecision};
-pub use self::do_damage::{DoDamageToDahanDecision, DoDamageToInvadersDecision, DestroyInvadersDecision};
-pub use self::growth::{AddPresenceDecision, ChooseGrowthDecision, GainMinorPowerCardDecision, GainMajorPowerCardDecision, GainPowerCardDecision};
-pub use self::meta::{ChooseEffectDecision};
+pub use self::do_damage::{DoDamageToDahanDecision, DoDamageToInvadersDecision, DoDamageToDifferentInvadersDecision, DestroyInvadersDecision};
+pub use self::fate::{FateDeck, FateOptions, FateDrawDecision};
+pub use self::growth::{AddPresenceDecision, AddPresenceToLandDecision, ChooseGrowthDecision, GainMinorPowerCardDecision, GainPowerCardDecision, ForgetPowerCardDecision, ReclaimOneDecision, ProgressionUnderflow};
+pub use self::innate::{DoInnatePowersEffect};
+pub use self::meta::{ChooseEffectDecision, ChooseLandDecision, ChooseLandEffectDecision, ChooseDifferentLandsDecision, ChooseSpiritEffectDecision, EachSpiritChoosesLandDecision};
 pub use self::move_piece::{PushDecision, GatherDecision};
+pub use self::remove_piece::{RemoveInvadersDecision};
+pub use self::spirit::{GainElementsDecision, DestroyPresenceDecision, GiftPowerCardDecision};
+
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choices_round_trip_through_text() {
+        let choices = [
+            "choice 2",
+            "sequence 1 0 3",
+            "sequence",
+            "land 4 from 2",
+            "spirit 0",
+            "presence 0 4 from 1",
+            "damage 1 0 2",
+            "pieces Explorer:0 Town:1 Blight:0 Dahan:2",
+            "area-pieces 4:Explorer:0 5:Dahan:1 6:City:0",
+        ];
+        for text in choices.iter() {
+            assert_eq!(text.parse::<DecisionChoice>().unwrap().to_string(), *text);
+        }
+        assert!(matches!("presence 0 4 from 1".parse(), Ok(DecisionChoice::PlacePresence{spirit: 0, target_land: 4, source_presence: 1})));
+
+        for text in ["", "choice", "choice x", "choice 1 2", "land 4 to 2", "pieces Explorer", "pieces Giant:0", "area-pieces 4:Town", "dance 1"].iter() {
+            assert!(text.parse::<DecisionChoice>().is_err(), "`{}`", text);
+        }
+    }
+}
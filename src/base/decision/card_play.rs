@@ -56,7 +56,10 @@ impl Effect for CardPlaysDecision {
         spirit_mut.energy -= cost;
 
         // 2b. Gain elements
-        // TODO: gain elements
+        for schoice in choice.iter() {
+            let elements = spirit_mut.deck.hand[*schoice].desc.elements;
+            spirit_mut.elements.gain(&elements);
+        }
 
         // 2c. Place the cards into pending
         choice.sort();
@@ -82,12 +85,12 @@ impl Decision for CardPlaysDecision {
         let hand_size = spirit.deck.hand.len();
         let plays = spirit.plays as usize;
 
-        // for each possible play length
-        (0..(min(hand_size, plays)+1))
+        // for each possible play length (playing nothing is always possible, even with an empty hand)
+        (1..(min(hand_size, plays)+1))
             // generate the possible combinations
             .map(|cards_to_play| (0..hand_size).combinations(cards_to_play))
             // into one list
-            .fold(Vec::new(), |mut acc, v| { acc.extend(v); acc })
+            .fold(vec![Vec::new()], |mut acc, v| { acc.extend(v); acc })
             .into_iter()
             // filter for those that can actually be played in combination energy wise
             .filter(|cards| {cards.iter().map(|card| spirit.deck.hand[*card].desc.cost).sum::<u8>() <= spirit.energy})
@@ -98,69 +101,128 @@ impl Decision for CardPlaysDecision {
 }
 
 
-#[derive(Clone)]
-pub struct DoCardPlayDecision {
-    pub spirit_index: u8,
-    pub pending_index: usize,
+// The lands with the spirit's presence a land power may be targeted from.
+fn get_valid_sources(game: &GameState, spirit_index: u8, src: fn(&LandState, u8) -> bool) -> Vec<u8> {
+    // the spirit's own presence, and that of any spirit it is entwined with
+    let spirits: Vec<u8> = (0..game.spirits.len() as u8)
+        .filter(|s| *s == spirit_index || game.spirits[spirit_index as usize].entwined_with[*s])
+        .collect();
+
+    game.table.lands.iter().enumerate()
+        .filter(|(_, state)| spirits.iter().any(|s| state.presence[*s] > 0 && src(state, *s)))
+        .map(|(index, _)| index as u8)
+        .collect()
 }
 
-impl DoCardPlayDecision {
-    pub fn get_valid_targets(&self, game: &GameState, card_desc: &PowerCardDescription) -> Result<Vec<PowerTarget>, StepFailure> {
+// The lands a land power of the spirit may target from `source_land`.
+fn get_lands_in_range(game: &GameState, spirit_index: u8, source_land: u8, range: u8, dst: fn(&LandState) -> bool) -> Vec<u8> {
+    let range = range + game.get_spirit(spirit_index).map_or(0, |spirit| spirit.extra_range);
+    game.desc.table.get_lands_within(&[source_land], range).into_iter()
+        .filter(|index| {
+            let state = &game.table.lands[*index as usize];
+            state.is_in_play && dst(state)
+        })
+        .collect()
+}
 
-        match card_desc.target_filter {
-            PowerTargetFilter::Land{range, src, dst} => {
-                // TODO src filter and ranges
-                Ok(game.table.lands.iter().enumerate()
-                    .filter(|(_, state)| dst(state))
-                    .map(|(index, _)| PowerTarget::Land(index as u8))
-                    .collect())
-            },
-            PowerTargetFilter::Spirit(target) => {
-                Ok(game.spirits.iter().enumerate()
-                    .filter(|(_, state)| target(state))
-                    .map(|(index, _)| PowerTarget::Spirit(index as u8))
-                    .collect())
+// The valid targets of a power (card or innate), with the land they are targeted from for land
+// powers. A land in range of several sources is offered once from each of them.
+pub fn get_valid_targets(game: &GameState, spirit_index: u8, target_filter: PowerTargetFilter) -> Vec<(PowerTarget, Option<u8>)> {
+    match target_filter {
+        PowerTargetFilter::Land{range, src, dst} => {
+            let in_range: Vec<(u8, Vec<u8>)> = get_valid_sources(game, spirit_index, src).into_iter()
+                .map(|source_land| (source_land, get_lands_in_range(game, spirit_index, source_land, range, dst)))
+                .collect();
+
+            (0..game.table.lands.len() as u8)
+                .flat_map(|target_land| in_range.iter()
+                    .filter(move |(_, lands)| lands.contains(&target_land))
+                    .map(move |(source_land, _)| (PowerTarget::Land(target_land), Some(*source_land))))
+                .collect()
+        },
+        PowerTargetFilter::Spirit(target) => {
+            game.spirits.iter().enumerate()
+                .filter(|(_, state)| target(state))
+                .map(|(index, _)| (PowerTarget::Spirit(index as u8), None))
+                .collect()
+        }
+    }
+}
+
+// Consumes and validates the targeting choice of a power.
+pub fn consume_target(game: &mut GameState, spirit_index: u8, target_filter: PowerTargetFilter) -> Result<(PowerTarget, Option<u8>), StepFailure> {
+    match target_filter {
+        PowerTargetFilter::Land{range, src, dst} => {
+            let (target_land, source_land) = match game.consume_choice()?
+            {
+                DecisionChoice::TargetLand{target_land, source_land} => Ok((target_land, source_land)),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?;
+
+            if !get_valid_sources(game, spirit_index, src).contains(&source_land)
+                || !get_lands_in_range(game, spirit_index, source_land, range, dst).contains(&target_land) {
+                return Err(StepFailure::RulesViolation("not given a valid target".to_string()));
             }
+
+            Ok((PowerTarget::Land(target_land), Some(source_land)))
+        },
+        PowerTargetFilter::Spirit(_) => {
+            let target = PowerTarget::Spirit(
+                match game.consume_choice()?
+                {
+                    DecisionChoice::TargetSpirit{target_spirit} => Ok(target_spirit),
+                    _ => Err(StepFailure::DecisionMismatch),
+                }?);
+
+            // Find it in the valid possible decisions
+            if !get_valid_targets(game, spirit_index, target_filter).contains(&(target, None)) {
+                return Err(StepFailure::RulesViolation("not given a valid target".to_string()));
+            }
+
+            Ok((target, None))
         }
     }
 }
 
+// The targeting choices of a power.
+pub fn target_choices(game: &GameState, spirit_index: u8, target_filter: PowerTargetFilter) -> Vec<DecisionChoice> {
+    get_valid_targets(game, spirit_index, target_filter).into_iter()
+        .map(|(power_target, source_land)| match power_target {
+            PowerTarget::Spirit(index) => DecisionChoice::TargetSpirit{target_spirit: index},
+            PowerTarget::Land(index) => DecisionChoice::TargetLand{target_land: index, source_land: source_land.unwrap()},
+        }).collect()
+}
+
+
+#[derive(Clone)]
+pub struct DoCardPlayDecision {
+    pub spirit_index: u8,
+    pub pending_index: usize,
+}
+
 impl Effect for DoCardPlayDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
         let card_desc = Arc::clone(&game.get_spirit(self.spirit_index)?.deck.pending[self.pending_index].desc);
 
-        // 1. Figure out the kind of card we are dealing with to get the decision
-        let target = match card_desc.target_filter {
-            PowerTargetFilter::Land{..} => {
-                PowerTarget::Land(
-                    match game.consume_choice()?
-                    {
-                        DecisionChoice::TargetLand{target_land, ..} => Ok(target_land),
-                        _ => Err(StepFailure::DecisionMismatch),
-                    }?)
-            },
-            PowerTargetFilter::Spirit(_) => {
-                PowerTarget::Spirit(
-                    match game.consume_choice()?
-                    {
-                        DecisionChoice::TargetSpirit{target_spirit} => Ok(target_spirit),
-                        _ => Err(StepFailure::DecisionMismatch),
-                    }?)
-            }
-        };
-
-        // 1a. Find it in the valid possible decisions
-        if !self.get_valid_targets(game, &card_desc)?.contains(&target) {
-            return Err(StepFailure::RulesViolation("not given a valid target".to_string()));
+        // 0. A power without a valid target does nothing
+        if get_valid_targets(game, self.spirit_index, card_desc.target_filter).is_empty() {
+            game.log_effect(format_args!("playing card |{}| (but it has no valid target)", card_desc));
+            return Ok(());
         }
 
-        game.log_decision(format_args!("playing card (targeting {})...", target));
+        // 1. Get the targeting decision
+        let (target, src_land_index) = consume_target(game, self.spirit_index, card_desc.target_filter)?;
+
+        match src_land_index {
+            Some(source_land) => game.log_decision(format_args!("playing card (targeting {} from land {})...", target, source_land)),
+            None => game.log_decision(format_args!("playing card (targeting {})...", target)),
+        }
 
         // 2. Invoke it! Finally!
         game.power_usages.push(PowerUsage {
             target,
             using_spirit_index: self.spirit_index,
-            src_land_index: None,
+            src_land_index,
         });
 
         game.do_effect_box(card_desc.box_clone())?;
@@ -180,68 +242,138 @@ impl Decision for DoCardPlayDecision {
     fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
         let card_desc = Arc::clone(&game.get_spirit(self.spirit_index).ok().unwrap().deck.pending[self.pending_index].desc);
 
-        self.get_valid_targets(game, &card_desc).ok().unwrap().into_iter()
-            .map(|power_target| match power_target {
-                PowerTarget::Spirit(index) => DecisionChoice::TargetSpirit{target_spirit: index},
-                PowerTarget::Land(index) => DecisionChoice::TargetLand{target_land: index, source_land: 0},
-            }).collect()
+        target_choices(game, self.spirit_index, card_desc.target_filter)
+    }
+}
+
+
+// Right after a land-targeting card resolves, the spirit may repeat it by paying its cost again
+// when an effect lets it (see `SpiritState::may_repeat_powers`).
+#[derive(Clone)]
+pub struct RepeatCardPlayDecision {
+    pub spirit_index: u8,
+    pub pending_index: usize,
+}
+
+impl Effect for RepeatCardPlayDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Sanity check
+        let spirit = game.get_spirit(self.spirit_index)?;
+        let card_desc = Arc::clone(&spirit.deck.pending[self.pending_index].desc);
+        if spirit.may_repeat_powers == 0 || spirit.energy < card_desc.cost
+            || !matches!(card_desc.target_filter, PowerTargetFilter::Land{..}) {
+            return Ok(());
+        }
+
+        // 2. Get the decision
+        let choice = match game.consume_choice()?
+        {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        match choice {
+            0 => {
+                game.log_decision(format_args!("not repeating card |{}|.", card_desc));
+                Ok(())
+            },
+            1 => {
+                game.log_decision(format_args!("repeating card |{}|...", card_desc));
+
+                let spirit_mut = game.get_spirit_mut(self.spirit_index)?;
+                spirit_mut.energy -= card_desc.cost;
+                spirit_mut.may_repeat_powers -= 1;
+
+                game.do_effect(DoCardPlayDecision{spirit_index: self.spirit_index, pending_index: self.pending_index})?;
+                game.do_effect(self.clone())
+            },
+            _ => Err(StepFailure::InternalError("choice out of range".to_string())),
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for RepeatCardPlayDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        vec![DecisionChoice::Choice(0), DecisionChoice::Choice(1)]
     }
 }
 
 
+/*
+    The cards played resolve one at a time in an order of the players' choosing, interleaving the
+    spirits. Each resolution is a decision of which card goes next, the decision carries the cards
+    already resolved this phase (by spirit, kind and index, as pending indices could move).
+*/
 #[derive(Clone)]
 pub struct DoCardPlaysDecision {
     pub power_speed: PowerSpeed,
+    pub resolved: Vec<(u8, PowerCardKind, usize)>,
 }
 
 impl DoCardPlaysDecision {
-    fn pending_size(&self, game: &GameState) -> usize {
-        game.spirits.iter()
-            .map(|s| s.deck.pending.iter().filter(|c| c.desc.speed == self.power_speed).count())
-            .sum()
+    pub fn new(power_speed: PowerSpeed) -> DoCardPlaysDecision {
+        DoCardPlaysDecision {
+            power_speed,
+            resolved: Vec::new(),
+        }
+    }
+
+    // The cards left to resolve as (spirit index, pending index).
+    fn remaining(&self, game: &GameState) -> Vec<(u8, usize)> {
+        game.spirits.iter().enumerate()
+            .flat_map(|(spirit_index, spirit)| spirit.deck.pending.iter().enumerate()
+                .filter(move |(_, card)| card.desc.speed == self.power_speed
+                    && !self.resolved.contains(&(spirit_index as u8, card.desc.kind, card.index)))
+                .map(move |(pending_index, _)| (spirit_index as u8, pending_index)))
+            .collect()
     }
 }
 
 impl Effect for DoCardPlaysDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
         // 1. Sanity check
-        let pend_size = self.pending_size(game);
-        if pend_size == 0 {
-            game.log_effect(format_args!("playing cards... (but no cards to play)"));
+        let remaining = self.remaining(game);
+        if remaining.is_empty() {
+            if self.resolved.is_empty() {
+                game.log_effect(format_args!("playing cards... (but no cards to play)"));
+            }
             return Ok(());
         }
 
-        // 2. Get the decision
-        let choice = match game.consume_choice()?
-        {
-            DecisionChoice::Sequence(res) => Ok(res),
-            _ => Err(StepFailure::DecisionMismatch),
-        }?;
+        // 2. Get the decision, unless there is only one card left
+        let choice = if remaining.len() == 1 {
+            0
+        } else {
+            match game.consume_choice()?
+            {
+                DecisionChoice::Choice(res) => Ok(res),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?
+        };
 
         // 2a. Verify it's contents
-        for schoice in choice.iter() {
-            if !(*schoice < pend_size) {
-                return Err(StepFailure::InternalError("choice out of range".to_string()));
-            }
+        if choice >= remaining.len() {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
         }
 
-        // 2b. Verify uniqueness
-        let mut uniq = HashSet::new();
-        choice.iter().all(|x| uniq.insert(x));
+        let (spirit_index, pending_index) = remaining[choice];
+        let card = game.get_spirit(spirit_index)?.deck.pending[pending_index].clone();
 
-        if uniq.len() != choice.len() {
-            return Err(StepFailure::InternalError("duplicate play choices!".to_string()));
-        }
+        game.log_decision(format_args!("playing card |{}| of spirit {}...", card.desc, spirit_index));
 
-        game.log_decision(format_args!("playing cards..."));
+        // 3. Run the card, then the rest
+        game.do_effect(DoCardPlayDecision{spirit_index, pending_index})?;
+        game.do_effect(RepeatCardPlayDecision{spirit_index, pending_index})?;
 
-        // 3. run the cards
-        // TODO: hack, only one spirit
-        for schoice in choice.iter() {
-            game.do_effect(DoCardPlayDecision{spirit_index: 0, pending_index: *schoice})?;
-        }
+        let mut resolved = self.resolved.clone();
+        resolved.push((spirit_index, card.desc.kind, card.index));
 
-        Ok(())
+        game.do_effect(DoCardPlaysDecision{power_speed: self.power_speed, resolved})
     }
 
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
@@ -252,9 +384,100 @@ impl Effect for DoCardPlaysDecision {
 
 impl Decision for DoCardPlaysDecision {
     fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
-        let pend_size = self.pending_size(game);
-        (0..pend_size)
-            .map(|index| DecisionChoice::Sequence(vec![index]))
+        (0..self.remaining(game).len())
+            .map(DecisionChoice::Choice)
             .collect()
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::set_up_game;
+
+    #[test]
+    fn sacred_site_powers_need_two_presence() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let filter = PowerTargetFilter::Land{range: 0, src: sacred_site, dst: |_| true};
+        assert!(get_valid_targets(&game, 0, filter).is_empty());
+
+        let land = game.table.lands.iter().position(|l| l.presence[0] > 0).unwrap() as u8;
+        game.table.lands[land as usize].presence[0] = 2;
+        let targets = get_valid_targets(&game, 0, filter);
+        assert!(targets == vec![(PowerTarget::Land(land), Some(land))]);
+    }
+
+    #[test]
+    fn targets_are_offered_from_every_source() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        for land in game.table.lands.iter_mut() {
+            land.presence[0] = 0;
+        }
+        // two adjacent lands, both in range 1 of each other and of themselves
+        let a = game.table.lands.iter().position(|l| l.is_in_play && l.desc.kind != LandKind::Ocean).unwrap() as u8;
+        let b = *game.desc.table.lands[a as usize].adjacent.iter()
+            .find(|l| game.table.lands[**l as usize].is_in_play && game.desc.table.lands[**l as usize].kind != LandKind::Ocean)
+            .unwrap();
+        game.table.lands[a as usize].presence[0] = 1;
+        game.table.lands[b as usize].presence[0] = 1;
+
+        let filter = PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true};
+        let targets = get_valid_targets(&game, 0, filter);
+        for (target, source) in [(a, a), (a, b), (b, a), (b, b)].iter() {
+            assert!(targets.contains(&(PowerTarget::Land(*target), Some(*source))));
+        }
+        assert_eq!(targets.iter().filter(|(t, _)| *t == PowerTarget::Land(a)).count(), 2);
+    }
+
+    #[test]
+    fn extra_range_extends_targets() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let filter = PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true};
+        let before = get_valid_targets(&game, 0, filter).len();
+
+        game.spirits[0].extra_range = 1;
+        assert!(get_valid_targets(&game, 0, filter).len() > before);
+    }
+
+    #[test]
+    fn entwined_spirits_target_from_each_others_presence() {
+        let mut game = set_up_game(&["river", "lightning"], &["A", "B"]);
+        let land = game.table.lands.iter().position(|l| l.presence[1] > 0 && l.presence[0] == 0).unwrap() as u8;
+        let filter = PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true};
+        assert!(!get_valid_targets(&game, 0, filter).contains(&(PowerTarget::Land(land), Some(land))));
+
+        game.spirits[0].entwined_with[1] = true;
+        assert!(get_valid_targets(&game, 0, filter).contains(&(PowerTarget::Land(land), Some(land))));
+    }
+
+    #[test]
+    fn repeating_a_card_pays_its_cost_again() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let land = game.table.lands.iter().position(|l| l.presence[0] > 0).unwrap() as u8;
+        game.spirits[0].deck.pending.push(PowerCard{index: 0, desc: Arc::new(PowerCardDescription {
+            name: "Test Power",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::new(false),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true},
+
+            effect: |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(land_index)})
+            },
+        })});
+        game.spirits[0].energy = 1;
+        game.spirits[0].may_repeat_powers = 1;
+        let fear = game.fear_generated_total;
+
+        let target = DecisionChoice::TargetLand{target_land: land, source_land: land};
+        game.choices.extend([target.clone(), DecisionChoice::Choice(1), target]);
+        game.do_effect(DoCardPlaysDecision::new(PowerSpeed::Fast)).unwrap();
+
+        assert!(game.choices.is_empty());
+        assert_eq!(game.fear_generated_total, fear + 2);
+        assert_eq!(game.spirits[0].energy, 0);
+        assert_eq!(game.spirits[0].may_repeat_powers, 0);
+    }
+}
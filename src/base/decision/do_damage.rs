@@ -3,8 +3,11 @@ use std::{
     any::Any,
     iter::*,
     cmp::*,
+    collections::HashSet,
 };
 
+use itertools::Itertools;
+
 use super::*;
 
 
@@ -38,6 +41,22 @@ pub struct DoDamageToDahanDecision {
 
 impl Effect for DoDamageToDahanDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 0. Protected dahan ignore the damage, or have their extra health
+        let (health_bonus, ignore_damage) = game.get_dahan_protection(self.land_index)?;
+        if ignore_damage {
+            game.log_effect(format_args!("{} damage to dahan in {} (but they ignore it!).", self.damage, self.land_index));
+            return Ok(());
+        }
+        if health_bonus > 0 {
+            let health_max = Dahan::new().health_max + health_bonus;
+            for dahan in game.get_land_mut(self.land_index)?.dahan.iter_mut() {
+                if dahan.health_max < health_max {
+                    dahan.health_cur += health_max - dahan.health_max;
+                    dahan.health_max = health_max;
+                }
+            }
+        }
+
         let dahan = game.get_land(self.land_index)?.dahan.clone();
 
         // 1. Sanity check
@@ -95,6 +114,7 @@ impl Effect for DoDamageToDahanDecision {
         for dahan_index in destroyed_dahan {
             game.do_effect(RemoveDahanEffect{land_index: self.land_index, dahan_index, destroyed: true})?;
         }
+        game.do_effect(AvengeDestroyedEffect{land_index: self.land_index})?;
 
         if damage_remaining != 0 {
             game.log_subeffect(format_args!("{} damage to dahan in {} spilled over.", damage_remaining, self.land_index));
@@ -185,6 +205,7 @@ impl Effect for DoDamageToInvadersDecision {
         for invader_index in destroyed_invaders {
             game.do_effect(RemoveInvaderEffect{land_index: self.land_index, invader_index, destroyed: true})?;
         }
+        game.do_effect(AvengeDestroyedEffect{land_index: self.land_index})?;
 
         if damage_remaining != 0 {
             game.log_effect(format_args!("{} damage to dahan in {} spilled over.", damage_remaining, self.land_index));
@@ -217,6 +238,78 @@ impl Decision for DoDamageToInvadersDecision {
 }
 
 
+// 1 damage to each of `count` different invaders (or all of them, if there are fewer).
+#[derive(Clone)]
+pub struct DoDamageToDifferentInvadersDecision {
+    pub land_index: u8,
+    pub count: u8,
+}
+
+impl Effect for DoDamageToDifferentInvadersDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let invaders = game.get_land(self.land_index)?.invaders.clone();
+
+        // 1. Sanity check
+        if invaders.is_empty() || self.count == 0 {
+            game.log_effect(format_args!("1 damage to {} different invaders in {} (but no invaders!).", self.count, self.land_index));
+            return Ok(());
+        }
+
+        // 2. Get the damage decision
+        let damage_layout: Vec<u16>
+            = match game.consume_choice()?
+            {
+                DecisionChoice::Damage(res) => Ok(res),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?;
+
+        if damage_layout.len() != invaders.len() || damage_layout.iter().any(|d| *d > 1)
+            || damage_layout.iter().sum::<u16>() != min(self.count as usize, invaders.len()) as u16 {
+            return Err(StepFailure::RulesViolation("Must do 1 damage to each of the different invaders.".to_string()));
+        }
+
+        game.log_decision(format_args!("1 damage to {} different invaders in {}.", self.count, self.land_index));
+
+        // 3. Actually perform the damage, higher indexes first so destroying doesn't move the rest
+        for (invader_index, _) in damage_layout.iter().enumerate().filter(|(_, d)| **d == 1).rev() {
+            let invader = &mut game.get_land_mut(self.land_index)?.invaders[invader_index];
+            invader.health_cur -= 1;
+            if invader.health_cur == 0 {
+                game.do_effect(RemoveInvaderEffect{land_index: self.land_index, invader_index, destroyed: true})?;
+            }
+        }
+
+        game.do_effect(AvengeDestroyedEffect{land_index: self.land_index})
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for DoDamageToDifferentInvadersDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        let invaders = match game.get_land(self.land_index) {
+            Ok(land) => &land.invaders,
+            Err(_) => return Vec::new(),
+        };
+
+        // invaders that look the same make the same choice
+        let mut seen = HashSet::new();
+        (0..invaders.len())
+            .combinations(min(self.count as usize, invaders.len()))
+            .filter(|chosen| seen.insert(chosen.iter()
+                .map(|i| (invaders[*i].kind as u8, invaders[*i].health_cur, invaders[*i].health_max))
+                .sorted()
+                .collect::<Vec<_>>()))
+            .map(|chosen| DecisionChoice::Damage(
+                (0..invaders.len()).map(|i| if chosen.contains(&i) { 1 } else { 0 }).collect()))
+            .collect()
+    }
+}
+
+
 #[derive(Clone)]
 pub struct DestroyInvadersDecision {
     pub land_index: u8,
@@ -275,6 +368,7 @@ impl Effect for DestroyInvadersDecision {
         for invader_index in destroyed_invaders {
             game.do_effect(RemoveInvaderEffect{land_index: self.land_index, invader_index, destroyed: true})?;
         }
+        game.do_effect(AvengeDestroyedEffect{land_index: self.land_index})?;
 
         Ok(())
     }
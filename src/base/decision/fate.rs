@@ -0,0 +1,230 @@
+use std::{
+    any::Any,
+};
+
+use super::*;
+
+
+/*
+    Fate choosing: instead of drawing at random, the card drawn from a fated deck is a (chance)
+    decision for the solver. Fated decks are not shuffled, which cards can be drawn is decided by
+    the rules of each deck:
+
+    * Invader: any card of the phase on top of the deck.
+    * Fear: any card not yet drawn, including the ones set aside (unused) during setup.
+    * Minor/Major: any card in the draw pile.
+    * Blight: any card in the draw pile.
+*/
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+pub enum FateDeck {
+    Invader,
+    Fear,
+    Minor,
+    Major,
+    Blight,
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+pub struct FateOptions {
+    pub invader: bool,
+    pub fear: bool,
+    pub minor: bool,
+    pub major: bool,
+    pub blight: bool,
+}
+
+impl FateOptions {
+    pub fn none() -> FateOptions {
+        FateOptions {
+            invader: false,
+            fear: false,
+            minor: false,
+            major: false,
+            blight: false,
+        }
+    }
+
+    pub fn is_fated(&self, deck: FateDeck) -> bool {
+        match deck {
+            FateDeck::Invader => self.invader,
+            FateDeck::Fear => self.fear,
+            FateDeck::Minor => self.minor,
+            FateDeck::Major => self.major,
+            FateDeck::Blight => self.blight,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.invader || self.fear || self.minor || self.major || self.blight
+    }
+}
+
+
+#[derive(Clone)]
+pub struct FateDrawDecision {
+    pub deck: FateDeck,
+}
+
+impl FateDrawDecision {
+    // The cards that could be drawn next, as indices into the draw pile (top first). For the fear
+    // deck indices past the draw pile are into the unused cards.
+    fn eligible(&self, game: &GameState) -> Vec<usize> {
+        match self.deck {
+            FateDeck::Invader => {
+                let draw = &game.invader.draw;
+                match draw.last() {
+                    Some(top) => (0..draw.len()).rev()
+                        .take_while(|i| draw[*i].phase() == top.phase())
+                        .collect(),
+                    None => Vec::new(),
+                }
+            },
+            FateDeck::Fear => (0..game.fear.draw.len()).rev()
+                .chain(game.fear.draw.len()..(game.fear.draw.len() + game.fear.unused.len()))
+                .collect(),
+            FateDeck::Minor => (0..game.minor_powers.draw.len()).rev().collect(),
+            FateDeck::Major => (0..game.major_powers.draw.len()).rev().collect(),
+            FateDeck::Blight => (0..game.blight.draw.len()).rev().collect(),
+        }
+    }
+}
+
+impl Effect for FateDrawDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Power decks reshuffle when empty, the order doesn't matter as fate picks anyway
+        match self.deck {
+            FateDeck::Minor if game.minor_powers.draw.is_empty() =>
+                game.minor_powers.shuffle_discard_into_draw(game.rng.get_rng()),
+            FateDeck::Major if game.major_powers.draw.is_empty() =>
+                game.major_powers.shuffle_discard_into_draw(game.rng.get_rng()),
+            _ => {},
+        }
+
+        // 2. Nothing to decide
+        let eligible = self.eligible(game);
+        if eligible.len() <= 1 {
+            return Ok(());
+        }
+
+        // 3. Get the decision
+        let choice = match game.consume_choice()?
+        {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if choice >= eligible.len() {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
+        }
+
+        // 4. Put the card on top of the deck
+        let index = eligible[choice];
+        match self.deck {
+            FateDeck::Invader => {
+                let card = game.invader.draw.remove(index);
+                game.log_decision(format_args!("fate draws |{}|.", card));
+                game.invader.draw.push(card);
+            },
+            FateDeck::Fear => {
+                let draw_len = game.fear.draw.len();
+                if index < draw_len {
+                    let card = game.fear.draw.remove(index);
+                    game.log_decision(format_args!("fate draws |{}|.", card.desc.name));
+                    game.fear.draw.push(card);
+                } else {
+                    // swap it with the top card, keeping the size of the deck
+                    let card = game.fear.unused.remove(index - draw_len);
+                    game.log_decision(format_args!("fate draws |{}|.", card.desc.name));
+                    let top = game.fear.draw.pop().unwrap();
+                    game.fear.unused.push(top);
+                    game.fear.draw.push(card);
+                }
+            },
+            FateDeck::Minor | FateDeck::Major => {
+                let deck = if self.deck == FateDeck::Minor { &mut game.minor_powers } else { &mut game.major_powers };
+                let card = deck.draw.remove(index);
+                deck.draw.push(card);
+                let card = deck.draw.last().unwrap().desc.clone();
+                game.log_decision(format_args!("fate draws |{}|.", card));
+            },
+            FateDeck::Blight => {
+                let card = game.blight.draw.remove(index);
+                game.log_decision(format_args!("fate draws |{}|.", card.desc.name));
+                game.blight.draw.push(card);
+            },
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for FateDrawDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        (0..self.eligible(game).len()).map(DecisionChoice::Choice).collect()
+    }
+
+    fn is_chance(&self) -> bool { true }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::new_game;
+
+    #[test]
+    fn fate_chooses_the_blight_card() {
+        let mut game = new_game(&["river"], &["A"]);
+        game.fate.blight = true;
+
+        // unshuffled, the last card of the deck is the bottom one
+        let last = game.desc.blight.len() - 1;
+        game.choices.push_back(DecisionChoice::Choice(last));
+        game.step().unwrap();
+
+        assert_eq!(game.blight.card.as_ref().unwrap().desc.name, game.desc.blight[0].name);
+    }
+
+    #[test]
+    fn fate_draws_invaders_of_the_top_stage() {
+        let mut game = new_game(&["river"], &["A"]);
+        game.fate.invader = true;
+        game.step().unwrap();
+
+        // the three stage I cards on top
+        let decision = FateDrawDecision{deck: FateDeck::Invader};
+        assert_eq!(decision.valid_choices(&game).len(), 3);
+
+        let draw = game.invader.draw.clone();
+        game.choices.push_back(DecisionChoice::Choice(2));
+        game.do_effect(decision).unwrap();
+        assert!(*game.invader.draw.last().unwrap() == draw[draw.len() - 3]);
+        assert_eq!(game.invader.draw.len(), draw.len());
+    }
+
+    #[test]
+    fn fate_swaps_in_unused_fear_cards() {
+        let mut game = new_game(&["river"], &["A"]);
+        game.fate.fear = true;
+        game.step().unwrap();
+
+        let (draw, unused) = (game.fear.draw.len(), game.fear.unused.len());
+        let decision = FateDrawDecision{deck: FateDeck::Fear};
+        assert_eq!(decision.valid_choices(&game).len(), draw + unused);
+
+        let top = game.fear.draw.last().unwrap().desc.name;
+        let swapped = game.fear.unused[0].desc.name;
+        game.choices.push_back(DecisionChoice::Choice(draw));
+        game.do_effect(decision).unwrap();
+
+        assert_eq!(game.fear.draw.last().unwrap().desc.name, swapped);
+        assert_eq!(game.fear.unused.last().unwrap().desc.name, top);
+        assert_eq!((game.fear.draw.len(), game.fear.unused.len()), (draw, unused));
+    }
+}
@@ -76,14 +76,18 @@ impl Decision for ChooseGrowthDecision {
 pub struct GainMinorPowerCardDecision {
     pub spirit_index: u8,
     pub draw_count: usize,
+    // a spirit that then gains one of the drawn cards not kept
+    pub shared_with: Option<u8>,
 }
 
 impl Effect for GainMinorPowerCardDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
-        return game.do_effect(NotImplementedEffect { what: "MINOR POWER DRAFTING, disabled" });
-
         // 1. Setup the draw/pending state
-        game.minor_powers.draw_into_pending(game.rng.get_rng(), self.draw_count);
+        game.draw_powers_into_pending(PowerCardKind::Minor, self.draw_count)?;
+        if game.minor_powers.pending.is_empty() {
+            game.log_effect(format_args!("gain minor power card (but no cards left!)."));
+            return Ok(());
+        }
 
         // 2. Pick the power
         let choice = match game.consume_choice()?
@@ -92,7 +96,7 @@ impl Effect for GainMinorPowerCardDecision {
             _ => Err(StepFailure::DecisionMismatch),
         }?;
 
-        if !(choice < self.draw_count) {
+        if choice >= game.minor_powers.pending.len() {
             return Err(StepFailure::InternalError("choice out of range".to_string()));
         }
 
@@ -103,6 +107,11 @@ impl Effect for GainMinorPowerCardDecision {
         game.log_subeffect(format_args!("drafted |{}|.", card.desc));
         game.get_spirit_mut(self.spirit_index)?.deck.hand.push(card);
 
+        // 4. The other spirit picks from the rest
+        if let Some(spirit_index) = self.shared_with {
+            game.do_effect(GainMinorPowerCardDecision{spirit_index, draw_count: 0, shared_with: None})?;
+        }
+
         game.minor_powers.discard_pending();
 
 
@@ -116,8 +125,18 @@ impl Effect for GainMinorPowerCardDecision {
 }
 
 impl Decision for GainMinorPowerCardDecision {
-    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
-        (0..self.draw_count).map(|index| DecisionChoice::Choice(index)).collect()
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        // fewer than drawn when the deck ran out
+        (0..game.minor_powers.pending.len()).map(DecisionChoice::Choice).collect()
+    }
+
+    // the card drafted, its place among the drawn cards depends on the draw
+    fn choice_label(&self, game: &GameState, choice: &DecisionChoice) -> String {
+        match choice {
+            DecisionChoice::Choice(index) if *index < game.minor_powers.pending.len() =>
+                format!("draft {}", game.minor_powers.pending[*index].desc.name),
+            _ => choice.to_string(),
+        }
     }
 }
 
@@ -126,14 +145,18 @@ impl Decision for GainMinorPowerCardDecision {
 pub struct GainMajorPowerCardDecision {
     pub spirit_index: u8,
     pub draw_count: usize,
+    // a spirit that then gains one of the drawn cards not kept
+    pub shared_with: Option<u8>,
 }
 
 impl Effect for GainMajorPowerCardDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
-        return game.do_effect(NotImplementedEffect { what: "MAJOR POWER DRAFTING, no sacrifice, no major powers" });
-
         // 1. Setup the draw/pending state
-        game.major_powers.draw_into_pending(game.rng.get_rng(), self.draw_count);
+        game.draw_powers_into_pending(PowerCardKind::Major, self.draw_count)?;
+        if game.major_powers.pending.is_empty() {
+            game.log_effect(format_args!("gain major power card (but no cards left!)."));
+            return Ok(());
+        }
 
         // 2. Pick the power
         let choice = match game.consume_choice()?
@@ -142,7 +165,7 @@ impl Effect for GainMajorPowerCardDecision {
             _ => Err(StepFailure::DecisionMismatch),
         }?;
 
-        if !(choice < self.draw_count) {
+        if choice >= game.major_powers.pending.len() {
             return Err(StepFailure::InternalError("choice out of range".to_string()));
         }
 
@@ -150,14 +173,18 @@ impl Effect for GainMajorPowerCardDecision {
         
         // 3. Move card
         let card = game.major_powers.pending.remove(choice);
+        game.log_subeffect(format_args!("drafted |{}|.", card.desc));
         game.get_spirit_mut(self.spirit_index)?.deck.hand.push(card);
 
+        // 4. The other spirit picks from the rest
+        if let Some(spirit_index) = self.shared_with {
+            game.do_effect(GainMajorPowerCardDecision{spirit_index, draw_count: 0, shared_with: None})?;
+        }
+
         game.major_powers.discard_pending();
 
-        // 4. Sacrifice a card
-        // TODO! sacrifice card effect
-        
-        Ok(())
+        // 5. Sacrifice a card
+        game.do_effect(ForgetPowerCardDecision{spirit_index: self.spirit_index})
     }
 
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
@@ -167,20 +194,200 @@ impl Effect for GainMajorPowerCardDecision {
 }
 
 impl Decision for GainMajorPowerCardDecision {
-    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
-        (0..self.draw_count).map(|index| DecisionChoice::Choice(index)).collect()
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        // fewer than drawn when the deck ran out
+        (0..game.major_powers.pending.len()).map(DecisionChoice::Choice).collect()
+    }
+
+    // the card drafted, its place among the drawn cards depends on the draw
+    fn choice_label(&self, game: &GameState, choice: &DecisionChoice) -> String {
+        match choice {
+            DecisionChoice::Choice(index) if *index < game.major_powers.pending.len() =>
+                format!("draft {}", game.major_powers.pending[*index].desc.name),
+            _ => choice.to_string(),
+        }
+    }
+}
+
+
+// Forgets one of the spirit's power cards, chosen by index into its hand, then discard, then
+// pending (the cards played this turn) in that order.
+#[derive(Clone)]
+pub struct ForgetPowerCardDecision {
+    pub spirit_index: u8,
+}
+
+impl Effect for ForgetPowerCardDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let deck = &game.get_spirit(self.spirit_index)?.deck;
+        let (hand, discard, pending) = (deck.hand.len(), deck.discard.len(), deck.pending.len());
+
+        // 1. Sanity check
+        if hand + discard + pending == 0 {
+            game.log_effect(format_args!("forgetting a power card of {} (but no cards!).", self.spirit_index));
+            return Ok(());
+        }
+
+        // 2. Get the decision
+        let choice = match game.consume_choice()?
+        {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if choice >= hand + discard + pending {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
+        }
+
+        // 3. Move card
+        let deck = &mut game.get_spirit_mut(self.spirit_index)?.deck;
+        let card = if choice < hand {
+            deck.hand.remove(choice)
+        } else if choice < hand + discard {
+            deck.discard.remove(choice - hand)
+        } else {
+            deck.pending.remove(choice - hand - discard)
+        };
+        let desc = card.desc.clone();
+        deck.forgotten.push(card);
+
+        game.log_decision(format_args!("forgetting |{}| for {}.", desc, self.spirit_index));
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+    
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for ForgetPowerCardDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        let deck = match game.get_spirit(self.spirit_index) {
+            Ok(spirit) => &spirit.deck,
+            Err(_) => return Vec::new(),
+        };
+        (0..deck.hand.len() + deck.discard.len() + deck.pending.len()).map(DecisionChoice::Choice).collect()
     }
 }
 
 
+// A spirit takes one card of its choice from its discard back into its hand.
+#[derive(Clone)]
+pub struct ReclaimOneDecision {
+    pub spirit_index: u8,
+}
+
+impl Effect for ReclaimOneDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let discard = game.get_spirit(self.spirit_index)?.deck.discard.len();
+
+        // 1. Sanity check
+        if discard == 0 {
+            game.log_effect(format_args!("reclaiming one for {} (but no cards in discard!).", self.spirit_index));
+            return Ok(());
+        }
+
+        // 2. Get the decision
+        let choice = match game.consume_choice()?
+        {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if choice >= discard {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
+        }
+
+        // 3. Move card
+        let deck = &mut game.get_spirit_mut(self.spirit_index)?.deck;
+        let card = deck.discard.remove(choice);
+        let desc = card.desc.clone();
+        deck.hand.push(card);
+
+        game.log_decision(format_args!("reclaiming |{}| for {}.", desc, self.spirit_index));
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+    
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for ReclaimOneDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        let deck = match game.get_spirit(self.spirit_index) {
+            Ok(spirit) => &spirit.deck,
+            Err(_) => return Vec::new(),
+        };
+        (0..deck.discard.len()).map(DecisionChoice::Choice).collect()
+    }
+}
+
+
+// What gaining a power card does once the spirit's power progression is used up (see
+// `GameState::power_progression`).
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+pub enum ProgressionUnderflow {
+    Nothing,
+    Draft,
+}
+
+// Takes the next card of a spirit's power progression out of the power decks, skipping the ones
+// that aren't there anymore (already gained, or in another spirit's deck).
+fn take_progression_card(game: &mut GameState, spirit_index: u8) -> Result<Option<PowerCard>, StepFailure> {
+    for name in game.get_spirit_desc(spirit_index)?.get_power_progression() {
+        for deck in [&mut game.minor_powers, &mut game.major_powers] {
+            for pile in [&mut deck.draw, &mut deck.discard] {
+                if let Some(index) = pile.iter().position(|card| card.desc.name == name) {
+                    return Ok(Some(pile.remove(index)));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
 #[derive(Clone)]
 pub struct GainPowerCardDecision {
     pub spirit_index: u8,
+    // a spirit that then gains one of the drawn cards not kept (nothing is drawn with the power
+    // progression)
+    pub shared_with: Option<u8>,
 }
 
 impl Effect for GainPowerCardDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
-        // 1. Choose minor or major
+        // 0. The power progression gives its next card instead
+        if let Some(underflow) = game.power_progression {
+            if let Some(card) = take_progression_card(game, self.spirit_index)? {
+                game.log_effect(format_args!("gain power card (progression), |{}|.", card.desc));
+                let kind = card.desc.kind;
+                game.get_spirit_mut(self.spirit_index)?.deck.hand.push(card);
+
+                // majors are still paid for
+                if kind == PowerCardKind::Major {
+                    game.do_effect(ForgetPowerCardDecision{spirit_index: self.spirit_index})?;
+                }
+                return Ok(());
+            }
+
+            if underflow == ProgressionUnderflow::Nothing {
+                game.log_effect(format_args!("gain power card (but the progression is used up)."));
+                return Ok(());
+            }
+        }
+
+        // 1. Choose minor or major, of the decks with cards left
+        if self.valid_choices(game).is_empty() {
+            game.log_effect(format_args!("gain power card (but no cards left!)."));
+            return Ok(());
+        }
+
         let choice = match game.consume_choice()?
         {
             DecisionChoice::Choice(res) => Ok(res),
@@ -188,13 +395,13 @@ impl Effect for GainPowerCardDecision {
         }?;
 
         match choice {
-            0 => {
+            0 if game.minor_powers.has_cards() => {
                 game.log_decision(format_args!("gain power card (minor)."));
-                game.do_effect(GainMinorPowerCardDecision{spirit_index: self.spirit_index, draw_count: 4})?;
+                game.do_effect(GainMinorPowerCardDecision{spirit_index: self.spirit_index, draw_count: 4, shared_with: self.shared_with})?;
             }
-            1 => {
+            1 if game.major_powers.has_cards() => {
                 game.log_decision(format_args!("gain power card (major)."));
-                game.do_effect(GainMajorPowerCardDecision{spirit_index: self.spirit_index, draw_count: 4})?;
+                game.do_effect(GainMajorPowerCardDecision{spirit_index: self.spirit_index, draw_count: 4, shared_with: self.shared_with})?;
             }
             _ => {
                 return Err(StepFailure::InternalError("choice out of range".to_string()));
@@ -211,33 +418,90 @@ impl Effect for GainPowerCardDecision {
 }
 
 impl Decision for GainPowerCardDecision {
-    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
-        vec![
-            DecisionChoice::Choice(0), // Minor
-            DecisionChoice::Choice(1), // Major
-        ]
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        let mut choices = Vec::new();
+        if game.minor_powers.has_cards() {
+            choices.push(DecisionChoice::Choice(0)); // Minor
+        }
+        if game.major_powers.has_cards() {
+            choices.push(DecisionChoice::Choice(1)); // Major
+        }
+        choices
     }
 }
 
 
+// The presence on the tracks of a spirit that may be taken next, by presence index.
+fn source_presences(game: &GameState, spirit_index: u8) -> Result<Vec<u8>, StepFailure> {
+    let spirit_desc = game.get_spirit_desc(spirit_index)?;
+    let spirit = game.get_spirit(spirit_index)?;
+
+    let mut res = Vec::new();
+    for (index, presence) in spirit.presence.iter().enumerate() {
+        if let PresenceState::OnTrack(_) = presence {
+            if spirit_desc.may_place_presence(&spirit.presence, index)? {
+                res.push(index as u8);
+            }
+        }
+    }
+
+    Ok(res)
+}
+
 #[derive(Clone)]
 pub struct AddPresenceDecision {
     pub spirit_index: u8,
     pub distance: u8,
 }
 
+impl AddPresenceDecision {
+
+    // The in play lands within distance of the spirit's presence the spirit may add presence to.
+    fn target_lands(&self, game: &GameState) -> Result<Vec<u8>, StepFailure> {
+        let spirit_desc = game.get_spirit_desc(self.spirit_index)?;
+        let spirit = game.get_spirit(self.spirit_index)?;
+
+        let sources: Vec<u8> = spirit.presence.iter()
+            .filter_map(|p| if let PresenceState::OnBoard(land_index) = p { Some(*land_index) } else { None })
+            .collect();
+
+        Ok(game.desc.table.get_lands_within(&sources, self.distance).into_iter()
+            .filter(|i| {
+                let land = &game.table.lands[*i as usize];
+                land.is_in_play && spirit_desc.may_place_presence_in(&land.desc)
+            })
+            .collect())
+    }
+}
+
 impl Effect for AddPresenceDecision {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
-        // 1. Which presence to take
+        // 1. Nothing to add, or nowhere to add it
+        let source_presences = source_presences(game, self.spirit_index)?;
+        let target_lands = self.target_lands(game)?;
+        if source_presences.is_empty() || target_lands.is_empty() {
+            game.log_effect(format_args!("no presence can be added, distance {}.", self.distance));
+            return Ok(());
+        }
+
+        // 2. Which presence to take, and where to
         let (spirit, target_land, source_presence) = match game.consume_choice()?
         {
             DecisionChoice::PlacePresence{spirit, target_land, source_presence} => Ok((spirit, target_land, source_presence)),
             _ => Err(StepFailure::DecisionMismatch),
         }?;
 
+        if spirit != self.spirit_index
+            || !source_presences.contains(&source_presence)
+            || !target_lands.contains(&target_land) {
+            return Err(StepFailure::RulesViolation("Add Presence: presence can't be added there!".to_string()));
+        }
+
         game.log_decision(format_args!("adding presence, distance {}, (target land {}, source presence {})", self.distance, target_land, source_presence));
-        
-        
+
+        // 3. Move it
+        game.do_effect(AddPresenceEffect{ land_index: target_land, spirit_index: spirit, presence_index: source_presence })?;
+
         Ok(())
     }
 
@@ -249,19 +513,188 @@ impl Effect for AddPresenceDecision {
 
 impl Decision for AddPresenceDecision {
     fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
-        let spirit_desc = game.get_spirit_desc(self.spirit_index).ok().unwrap();
-        let spirit = game.get_spirit(self.spirit_index).ok().unwrap();
-
-        spirit.presence.iter()
-            .filter(|p| 
-                if let PresenceState::OnTrack(pot) = p {
-                    spirit_desc.may_place_presence(&spirit.presence, *pot as usize).ok().unwrap()
-                } else { false })
-            .map(|p| 
-                if let PresenceState::OnTrack(pot) = p {
-                    DecisionChoice::PlacePresence{spirit: self.spirit_index, target_land: 1, source_presence: *pot}
-                } else { panic!() })
+        let (source_presences, target_lands) = match (source_presences(game, self.spirit_index), self.target_lands(game)) {
+            (Ok(source_presences), Ok(target_lands)) => (source_presences, target_lands),
+            _ => return Vec::new(),
+        };
+
+        target_lands.iter()
+            .flat_map(|target_land| source_presences.iter()
+                .map(move |source_presence| DecisionChoice::PlacePresence{
+                    spirit: self.spirit_index, target_land: *target_land, source_presence: *source_presence}))
             .collect()
     }
 }
 
+
+// Adds presence to a given land, even one the spirit normally couldn't add presence to.
+#[derive(Clone)]
+pub struct AddPresenceToLandDecision {
+    pub spirit_index: u8,
+    pub land_index: u8,
+}
+
+impl Effect for AddPresenceToLandDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Nothing to add
+        let source_presences = source_presences(game, self.spirit_index)?;
+        if source_presences.is_empty() {
+            game.log_effect(format_args!("no presence can be added to land {}.", self.land_index));
+            return Ok(());
+        }
+
+        // 2. Which presence to take
+        let (spirit, target_land, source_presence) = match game.consume_choice()?
+        {
+            DecisionChoice::PlacePresence{spirit, target_land, source_presence} => Ok((spirit, target_land, source_presence)),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if spirit != self.spirit_index
+            || target_land != self.land_index
+            || !source_presences.contains(&source_presence) {
+            return Err(StepFailure::RulesViolation("Add Presence: presence can't be added there!".to_string()));
+        }
+
+        game.log_decision(format_args!("adding presence to land {} (source presence {})", target_land, source_presence));
+
+        // 3. Move it
+        game.do_effect(AddPresenceEffect{ land_index: target_land, spirit_index: spirit, presence_index: source_presence })
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for AddPresenceToLandDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        match source_presences(game, self.spirit_index) {
+            Ok(source_presences) => source_presences.into_iter()
+                .map(|source_presence| DecisionChoice::PlacePresence{
+                    spirit: self.spirit_index, target_land: self.land_index, source_presence})
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::set_up_game;
+
+    fn names(cards: &[PowerCard]) -> Vec<&'static str> {
+        cards.iter().map(|c| c.desc.name).collect()
+    }
+
+    #[test]
+    fn forgetting_indexes_hand_then_discard_then_pending() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let deck = &mut game.spirits[0].deck;
+        let card = deck.hand.remove(0);
+        deck.discard.push(card);
+        let card = deck.hand.remove(0);
+        deck.pending.push(card);
+        let (hand, discard, pending) = (names(&deck.hand), names(&deck.discard), names(&deck.pending));
+
+        let decision = ForgetPowerCardDecision{spirit_index: 0};
+        assert_eq!(decision.valid_choices(&game).len(), 4);
+        assert!(matches!(game.clone().do_effect(decision.clone()), Err(StepFailure::DecisionRequired)));
+
+        game.choices.push_back(DecisionChoice::Choice(3));
+        game.do_effect(decision.clone()).unwrap();
+        game.choices.push_back(DecisionChoice::Choice(2));
+        game.do_effect(decision.clone()).unwrap();
+        game.choices.push_back(DecisionChoice::Choice(0));
+        game.do_effect(decision.clone()).unwrap();
+
+        let deck = &game.spirits[0].deck;
+        assert_eq!(names(&deck.forgotten), vec![pending[0], discard[0], hand[0]]);
+        assert_eq!(names(&deck.hand), vec![hand[1]]);
+        assert!(deck.discard.is_empty() && deck.pending.is_empty());
+
+        game.choices.push_back(DecisionChoice::Choice(1));
+        assert!(game.do_effect(decision).is_err());
+    }
+
+    #[test]
+    fn reclaiming_one_takes_the_chosen_discard() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let decision = ReclaimOneDecision{spirit_index: 0};
+
+        // nothing to reclaim, nothing to choose
+        assert!(decision.valid_choices(&game).is_empty());
+        game.do_effect(decision.clone()).unwrap();
+        assert_eq!(game.spirits[0].deck.hand.len(), 4);
+
+        let deck = &mut game.spirits[0].deck;
+        let cards: Vec<PowerCard> = deck.hand.drain(..2).collect();
+        deck.discard.extend(cards);
+        let discard = names(&deck.discard);
+
+        assert_eq!(decision.valid_choices(&game).len(), 2);
+        game.choices.push_back(DecisionChoice::Choice(1));
+        game.do_effect(decision).unwrap();
+
+        let deck = &game.spirits[0].deck;
+        assert_eq!(names(&deck.discard), vec![discard[0]]);
+        assert_eq!(deck.hand.len(), 3);
+        assert_eq!(deck.hand.last().unwrap().desc.name, discard[1]);
+    }
+
+    #[test]
+    fn adding_presence_for_a_missing_spirit_offers_nothing() {
+        let game = set_up_game(&["river"], &["A"]);
+        assert!(!AddPresenceDecision{spirit_index: 0, distance: 1}.valid_choices(&game).is_empty());
+        assert!(AddPresenceDecision{spirit_index: 1, distance: 1}.valid_choices(&game).is_empty());
+    }
+
+    #[test]
+    fn drafting_stops_at_an_exhausted_major_deck() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        game.power_progression = None;
+        let mut majors = game.major_powers.draw.split_off(1);
+        game.major_powers.discard.push(majors.pop().unwrap());
+
+        // one card in the draw pile and one in the discard, drawing four gives both
+        let drawn = game.major_powers.draw(game.rng.get_rng(), 4);
+        assert_eq!(drawn.len(), 2);
+        assert!(!game.major_powers.has_cards());
+        assert!(game.major_powers.draw(game.rng.get_rng(), 4).is_empty());
+
+        // only minors are offered
+        let decision = GainPowerCardDecision{spirit_index: 0, shared_with: None};
+        assert!(matches!(decision.valid_choices(&game).as_slice(), [DecisionChoice::Choice(0)]));
+        game.choices.push_back(DecisionChoice::Choice(1));
+        assert!(game.clone().do_effect(decision.clone()).is_err());
+
+        // a major draft draws nothing and forgets nothing
+        let hand = game.spirits[0].deck.hand.len();
+        game.choices.clear();
+        game.do_effect(GainMajorPowerCardDecision{spirit_index: 0, draw_count: 4, shared_with: None}).unwrap();
+        assert_eq!(game.spirits[0].deck.hand.len(), hand);
+        assert!(game.spirits[0].deck.forgotten.is_empty());
+
+        // without minors either there is nothing to gain
+        game.minor_powers.draw.clear();
+        game.minor_powers.discard.clear();
+        assert!(decision.valid_choices(&game).is_empty());
+        game.do_effect(decision).unwrap();
+        assert_eq!(game.spirits[0].deck.hand.len(), hand);
+    }
+
+    #[test]
+    fn drafts_are_labelled_by_card() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        game.draw_powers_into_pending(PowerCardKind::Minor, 4).unwrap();
+        let name = game.minor_powers.pending[2].desc.name;
+
+        let decision = GainMinorPowerCardDecision{spirit_index: 0, draw_count: 4, shared_with: None};
+        assert_eq!(decision.choice_label(&game, &DecisionChoice::Choice(2)), format!("draft {}", name));
+        assert_eq!(decision.choice_label(&game, &DecisionChoice::Choice(4)), "choice 4");
+        assert_eq!(ForgetPowerCardDecision{spirit_index: 0}.choice_label(&game, &DecisionChoice::Choice(1)), "choice 1");
+    }
+}
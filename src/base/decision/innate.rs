@@ -0,0 +1,100 @@
+use std::{
+    any::Any,
+};
+
+use super::*;
+use super::card_play::{get_valid_targets, consume_target, target_choices};
+
+
+/*
+    Innate powers are printed on the spirit panel rather than on cards: they need no play, only the
+    elements. Each level has an element threshold, at the fast (or slow) phase every innate of
+    that speed with at least one level met is used, targeted like a power card.
+*/
+
+#[derive(Clone)]
+pub struct DoInnatePowersEffect {
+    pub power_speed: PowerSpeed,
+}
+
+impl Effect for DoInnatePowersEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        for spirit_index in 0..game.spirits.len() as u8 {
+            let spirit = game.get_spirit(spirit_index)?;
+            let usable: Vec<usize> = spirit.desc.get_innate_powers().iter().enumerate()
+                .filter(|(_, innate)| innate.speed == self.power_speed && !innate.levels_met(&spirit.elements).is_empty())
+                .map(|(index, _)| index)
+                .collect();
+
+            for innate_index in usable {
+                game.do_effect(DoInnatePowerDecision{spirit_index, innate_index})?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+#[derive(Clone)]
+pub struct DoInnatePowerDecision {
+    pub spirit_index: u8,
+    pub innate_index: usize,
+}
+
+impl DoInnatePowerDecision {
+    fn get_innate(&self, game: &GameState) -> Result<InnatePowerDescription, StepFailure> {
+        game.get_spirit(self.spirit_index)?.desc.get_innate_powers()
+            .into_iter().nth(self.innate_index)
+            .ok_or_else(|| StepFailure::InternalError("innate power out of range".to_string()))
+    }
+}
+
+impl Effect for DoInnatePowerDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let innate = self.get_innate(game)?;
+
+        // 0. A power without a valid target does nothing
+        if get_valid_targets(game, self.spirit_index, innate.target_filter).is_empty() {
+            game.log_effect(format_args!("using innate power |{}| (but it has no valid target)", innate));
+            return Ok(());
+        }
+
+        // 1. Get the targeting decision
+        let (target, src_land_index) = consume_target(game, self.spirit_index, innate.target_filter)?;
+
+        match src_land_index {
+            Some(source_land) => game.log_decision(format_args!("using innate power (targeting {} from land {})...", target, source_land)),
+            None => game.log_decision(format_args!("using innate power (targeting {})...", target)),
+        }
+
+        // 2. Use it at the levels met
+        game.power_usages.push(PowerUsage {
+            target,
+            using_spirit_index: self.spirit_index,
+            src_land_index,
+        });
+
+        game.do_effect(innate)?;
+
+        game.power_usages.pop();
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for DoInnatePowerDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        let innate = self.get_innate(game).ok().unwrap();
+
+        target_choices(game, self.spirit_index, innate.target_filter)
+    }
+}
@@ -4,6 +4,8 @@ use std::{
     iter::*,
 };
 
+use itertools::Itertools;
+
 use super::*;
 
 
@@ -38,7 +40,253 @@ impl Effect for ChooseEffectDecision {
 }
 
 impl Decision for ChooseEffectDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        self.choices.iter().enumerate().map(|(index, _)| DecisionChoice::Choice(index)).collect()
+    }
+}
+
+
+// Chooses one of `lands` to apply an effect to, the players make the choices the rules leave to
+// the invaders as well.
+#[derive(Clone)]
+pub struct ChooseLandDecision {
+    pub lands: Vec<u8>,
+    pub effect: LandEffect,
+}
+
+impl Effect for ChooseLandDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision, unless there is nothing to choose from
+        let land_index = match self.lands.as_slice() {
+            [] => return Ok(()),
+            [land_index] => *land_index,
+            _ => match game.consume_choice()? {
+                DecisionChoice::TargetLand{target_land, ..} => Ok(target_land),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?,
+        };
+
+        if !self.lands.contains(&land_index) {
+            return Err(StepFailure::RulesViolation("Land can't be chosen.".to_string()));
+        }
+
+        game.log_decision(format_args!("choosing land {}...", land_index));
+
+        (self.effect)(game, land_index)
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for ChooseLandDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        self.lands.iter()
+            .map(|l| DecisionChoice::TargetLand{target_land: *l, source_land: *l})
+            .collect()
+    }
+}
+
+
+// Chooses one of several effects on the same land.
+#[derive(Clone)]
+pub struct ChooseLandEffectDecision {
+    pub land_index: u8,
+    pub choices: Vec<LandEffect>,
+}
+
+impl Effect for ChooseLandEffectDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision
+        let choice = match game.consume_choice()?
+        {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if choice >= self.choices.len() {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
+        }
+
+        game.log_decision(format_args!("choosing effect in land {}...", self.land_index));
+
+        (self.choices[choice])(game, self.land_index)
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for ChooseLandEffectDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        (0..self.choices.len()).map(DecisionChoice::Choice).collect()
+    }
+}
+
+
+// Chooses `count` different lands of `lands` (all of them when there are no more) to apply an
+// effect to, the choice is a sequence of indexes into `lands`.
+#[derive(Clone)]
+pub struct ChooseDifferentLandsDecision {
+    pub lands: Vec<u8>,
+    pub count: usize,
+    pub effect: LandEffect,
+}
+
+impl Effect for ChooseDifferentLandsDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision, unless there is nothing to choose from
+        let lands = if self.lands.len() <= self.count {
+            self.lands.clone()
+        } else {
+            let sequence = match game.consume_choice()?
+            {
+                DecisionChoice::Sequence(res) => Ok(res),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?;
+
+            if sequence.len() != self.count || sequence.iter().any(|i| *i >= self.lands.len()) || sequence.iter().unique().count() != sequence.len() {
+                return Err(StepFailure::RulesViolation("Must choose that many different lands.".to_string()));
+            }
+            sequence.into_iter().map(|i| self.lands[i]).collect()
+        };
+
+        game.log_decision(format_args!("choosing lands {}...", lands.iter().map(|l| l.to_string()).join(", ")));
+
+        // 2. Apply the effect to each
+        for land_index in lands {
+            (self.effect)(game, land_index)?;
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for ChooseDifferentLandsDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        (0..self.lands.len()).combinations(self.count)
+            .map(DecisionChoice::Sequence)
+            .collect()
+    }
+}
+
+
+// A spirit chooses one of several effects on it.
+#[derive(Clone)]
+pub struct ChooseSpiritEffectDecision {
+    pub spirit_index: u8,
+    pub choices: Vec<SpiritEffect>,
+}
+
+impl Effect for ChooseSpiritEffectDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision
+        let choice = match game.consume_choice()?
+        {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if choice >= self.choices.len() {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
+        }
+
+        game.log_decision(format_args!("spirit {} choosing effect...", self.spirit_index));
+
+        (self.choices[choice])(game, self.spirit_index)
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for ChooseSpiritEffectDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        (0..self.choices.len()).map(DecisionChoice::Choice).collect()
+    }
+}
+
+
+// Each spirit in turn (a spirit per player) chooses an in-play land matching `filter` to apply an
+// effect to, a land no spirit before it chose when `different`. Spirits with no land to choose
+// from skip their choice.
+#[derive(Clone)]
+pub struct EachSpiritChoosesLandDecision {
+    pub filter: fn(game: &GameState, land: &LandState) -> bool,
+    pub different: bool,
+    pub effect: LandEffect,
+
+    // the lands chosen so far, one for each spirit that had its turn
+    pub chosen: Vec<Option<u8>>,
+}
+
+impl EachSpiritChoosesLandDecision {
+    fn lands(&self, game: &GameState) -> Vec<u8> {
+        game.table.lands.iter()
+            .filter(|l| l.is_in_play && (self.filter)(game, l))
+            .map(|l| l.desc.index_on_table)
+            .filter(|l| !(self.different && self.chosen.contains(&Some(*l))))
+            .collect()
+    }
+}
+
+impl Effect for EachSpiritChoosesLandDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let spirit_index = self.chosen.len();
+        if spirit_index >= game.spirits.len() {
+            return Ok(());
+        }
+
+        // 1. Get the decision, unless there is nothing to choose from
+        let lands = self.lands(game);
+        let land_index = match lands.as_slice() {
+            [] => None,
+            [land_index] => Some(*land_index),
+            _ => match game.consume_choice()? {
+                DecisionChoice::TargetLand{target_land, ..} => Ok(Some(target_land)),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?,
+        };
+
+        // 2. Apply the effect
+        if let Some(land_index) = land_index {
+            if !lands.contains(&land_index) {
+                return Err(StepFailure::RulesViolation("Land can't be chosen.".to_string()));
+            }
+
+            game.log_decision(format_args!("spirit {} choosing land {}...", spirit_index, land_index));
+            (self.effect)(game, land_index)?;
+        } else {
+            game.log_effect(format_args!("spirit {} has no land to choose.", spirit_index));
+        }
+
+        // 3. The next spirit
+        let mut next = self.clone();
+        next.chosen.push(land_index);
+        game.do_effect(next)
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for EachSpiritChoosesLandDecision {
     fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
-        game.choices.iter().enumerate().map(|(index, _)| DecisionChoice::Choice(index)).collect()
+        self.lands(game).into_iter()
+            .map(|l| DecisionChoice::TargetLand{target_land: l, source_land: l})
+            .collect()
     }
 }
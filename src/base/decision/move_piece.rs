@@ -20,6 +20,69 @@ pub fn piece_kind_vec_to_string(pkl: &Vec<PieceKind>) -> String {
 }
 
 
+// The pieces of `kinds` in a land in groups that can't be told apart, as the indexes of each
+// group among the pieces of its kind.
+fn piece_groups(land: &LandState, kinds: &[PieceKind]) -> Vec<(PieceKind, Vec<usize>)> {
+    let mut res = Vec::new();
+    for pk in kinds.iter() {
+        let looks: Vec<(u8, u8, u16)> = match pk {
+            PieceKind::Token(_) => vec![(0, 0, 0); land.get_count(pk)],
+            PieceKind::Invader(ik) => land.invaders.iter()
+                .filter(|i| i.kind == *ik)
+                .map(|i| (i.health_cur, i.health_max, i.attack))
+                .collect(),
+            PieceKind::Dahan => land.dahan.iter()
+                .map(|d| (d.health_cur, d.health_max, d.attack))
+                .collect(),
+        };
+
+        let mut groups: Vec<((u8, u8, u16), Vec<usize>)> = Vec::new();
+        for (index, look) in looks.into_iter().enumerate() {
+            match groups.iter_mut().find(|(l, _)| *l == look) {
+                Some((_, indexes)) => indexes.push(index),
+                None => groups.push((look, vec![index])),
+            }
+        }
+
+        res.extend(groups.into_iter().map(|(_, indexes)| (*pk, indexes)));
+    }
+    res
+}
+
+// Every way to take between `least` and `most` pieces out of groups of the given sizes, as the
+// number taken from each.
+pub fn pick_counts(sizes: &[usize], least: usize, most: usize) -> Vec<Vec<usize>> {
+    match sizes.split_first() {
+        None => if least == 0 { vec![Vec::new()] } else { Vec::new() },
+        Some((size, rest)) => (0..=min(*size, most))
+            .flat_map(|taken| {
+                pick_counts(rest, least.saturating_sub(taken), most - taken).into_iter()
+                    .map(move |mut counts| { counts.insert(0, taken); counts })
+            })
+            .collect(),
+    }
+}
+
+// How many pieces a push or gather moves, given how many there are.
+fn move_count_range(count: u8, may: bool, available: usize) -> (usize, usize) {
+    let most = min(count as usize, available);
+    (if may { 0 } else { most }, most)
+}
+
+// Moves pieces as (from, to, kind, index) where the index is among the pieces of that kind in the
+// land they come from, before any of them moved.
+fn move_pieces(game: &mut GameState, mut moves: Vec<(u8, u8, PieceKind, usize)>) -> Result<(), StepFailure> {
+    // higher indexes first, so that moving a piece doesn't change the indexes of the rest
+    moves.sort_by_key(|m| std::cmp::Reverse(m.3));
+
+    for (from_land_index, to_land_index, kind, index) in moves {
+        game.do_effect(MovePieceEffect{from_land_index, to_land_index, kind, index})?;
+    }
+
+    Ok(())
+}
+
+
 #[derive(Clone)]
 pub struct PushDecision {
     pub land_index: u8,
@@ -43,6 +106,10 @@ impl Effect for PushDecision {
                 return Ok(());
             }
         }
+        if !game.get_adjacent_lands(self.land_index)?.iter().any(|l| l.is_in_play) {
+            game.log_effect(format_args!("push {} {} from {} (but nowhere to push to!).", self.count, piece_kind_vec_to_string(&self.kinds), self.land_index));
+            return Ok(());
+        }
 
         if self.may {
             game.log_decision(format_args!("may push {} {} from {}.", self.count, piece_kind_vec_to_string(&self.kinds), self.land_index));
@@ -75,21 +142,22 @@ impl Effect for PushDecision {
         }
 
         // 2c. Verify it's sequence of operations
-        let src_land = game.get_land_mut(self.land_index)?;
+        let src_land = game.get_land(self.land_index)?;
 
         for (l, pk, i) in sequence.iter() {
+            if !self.kinds.contains(pk) {
+                return Err(StepFailure::RulesViolation("Can't push that kind of piece.".to_string()));
+            }
             if !src_land.desc.adjacent.contains(l) {
                 return Err(StepFailure::RulesViolation("Can only push to adjacent lands.".to_string()));
             }
-            if *i < src_land.get_count(pk) {
+            if *i >= src_land.get_count(pk) {
                 return Err(StepFailure::InternalError("push source index out of bounds.".to_string()));
             }
         }
 
         // 3. Perform it
-        // TODO
-
-        Ok(())
+        move_pieces(game, sequence.into_iter().map(|(l, pk, i)| (self.land_index, l, pk, i)).collect())
     }
 
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
@@ -99,10 +167,44 @@ impl Effect for PushDecision {
 }
 
 impl Decision for PushDecision {
-    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
-        vec![
-            DecisionChoice::AreaPieceSequence(vec![])
-        ]
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        // TODO, resultify
+        let src_land = game.get_land(self.land_index).ok().unwrap();
+        let destinations: Vec<u8> = game.get_adjacent_lands(self.land_index).ok().unwrap().iter()
+            .filter(|l| l.is_in_play)
+            .map(|l| l.desc.index_on_table)
+            .collect();
+        if destinations.is_empty() {
+            return vec![DecisionChoice::AreaPieceSequence(vec![])];
+        }
+
+        let groups = piece_groups(src_land, &self.kinds);
+        let sizes: Vec<usize> = groups.iter().map(|(_, indexes)| indexes.len()).collect();
+        let (least, most) = move_count_range(self.count, self.may, sizes.iter().sum());
+
+        // pieces that can't be told apart only differ by where they go, not in which goes where
+        let mut res = Vec::new();
+        for counts in pick_counts(&sizes, least, most) {
+            let mut sequences: Vec<Vec<(u8, PieceKind, usize)>> = vec![Vec::new()];
+            for ((pk, indexes), taken) in groups.iter().zip(counts) {
+                if taken == 0 {
+                    continue;
+                }
+                sequences = sequences.into_iter()
+                    .flat_map(|sequence| {
+                        destinations.iter()
+                            .combinations_with_replacement(taken)
+                            .map(move |to| {
+                                let mut sequence = sequence.clone();
+                                sequence.extend(to.into_iter().zip(indexes.iter()).map(|(l, i)| (*l, *pk, *i)));
+                                sequence
+                            })
+                    })
+                    .collect();
+            }
+            res.extend(sequences.into_iter().map(DecisionChoice::AreaPieceSequence));
+        }
+        res
     }
 }
 
@@ -173,15 +275,16 @@ impl Effect for GatherDecision {
             }
 
             let src_land = game.get_land(*l)?;
-            if *i < src_land.get_count(pk) {
+            if !self.kinds.contains(pk) {
+                return Err(StepFailure::RulesViolation("Can't gather that kind of piece.".to_string()));
+            }
+            if *i >= src_land.get_count(pk) {
                 return Err(StepFailure::InternalError("gather target index out of bounds.".to_string()));
             }
         }
 
         // 3. Perform it
-        // TODO
-
-        Ok(())
+        move_pieces(game, sequence.into_iter().map(|(l, pk, i)| (l, self.land_index, pk, i)).collect())
     }
 
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
@@ -191,9 +294,20 @@ impl Effect for GatherDecision {
 }
 
 impl Decision for GatherDecision {
-    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
-        vec![
-            DecisionChoice::AreaPieceSequence(vec![])
-        ]
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        // TODO, resultify
+        let groups: Vec<(u8, PieceKind, Vec<usize>)> = game.get_adjacent_lands(self.land_index).ok().unwrap().iter()
+            .filter(|l| l.is_in_play)
+            .flat_map(|l| piece_groups(l, &self.kinds).into_iter().map(move |(pk, indexes)| (l.desc.index_on_table, pk, indexes)))
+            .collect();
+        let sizes: Vec<usize> = groups.iter().map(|(_, _, indexes)| indexes.len()).collect();
+        let (least, most) = move_count_range(self.count, self.may, sizes.iter().sum());
+
+        pick_counts(&sizes, least, most).into_iter()
+            .map(|counts| DecisionChoice::AreaPieceSequence(
+                groups.iter().zip(counts)
+                    .flat_map(|((l, pk, indexes), taken)| indexes[..taken].iter().map(move |i| (*l, *pk, *i)))
+                    .collect()))
+            .collect()
     }
 }
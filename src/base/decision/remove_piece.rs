@@ -0,0 +1,130 @@
+use std::{
+    any::Any,
+};
+
+use super::*;
+use super::move_piece::{pick_counts};
+
+
+// Removes invaders from a land (without destroying them), any of them `allowed` together. With
+// `downgrade` each is replaced by the next smaller kind of invader instead (a city by a town, a
+// town by an explorer).
+#[derive(Clone)]
+pub struct RemoveInvadersDecision {
+    pub land_index: u8,
+    pub allowed: fn(land: &LandState, removed: &[Invader]) -> bool,
+    pub may: bool,
+    pub downgrade: bool,
+}
+
+impl RemoveInvadersDecision {
+    // The invaders that may be removed as indexes into the invaders of the land, leaving out the
+    // ones that can't be told apart from another.
+    fn removals(&self, land: &LandState) -> Vec<Vec<usize>> {
+        let mut groups: Vec<(InvaderKind, u8, u8, u16, Vec<usize>)> = Vec::new();
+        for (index, i) in land.invaders.iter().enumerate() {
+            match groups.iter_mut().find(|g| (g.0, g.1, g.2, g.3) == (i.kind, i.health_cur, i.health_max, i.attack)) {
+                Some(group) => group.4.push(index),
+                None => groups.push((i.kind, i.health_cur, i.health_max, i.attack, vec![index])),
+            }
+        }
+
+        let sizes: Vec<usize> = groups.iter().map(|g| g.4.len()).collect();
+        pick_counts(&sizes, if self.may { 0 } else { 1 }, land.invaders.len()).into_iter()
+            .map(|counts| groups.iter().zip(counts)
+                .flat_map(|(g, taken)| g.4[..taken].iter().cloned())
+                .collect::<Vec<usize>>())
+            .filter(|removal| {
+                let removed: Vec<Invader> = removal.iter().map(|i| land.invaders[*i]).collect();
+                (self.allowed)(land, &removed)
+            })
+            .collect()
+    }
+}
+
+impl Effect for RemoveInvadersDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let land = game.get_land(self.land_index)?;
+        let removals = self.removals(land);
+
+        // 1. Sanity check
+        if removals.is_empty() {
+            game.log_effect(format_args!("removing invaders in {} (but no matching invaders!).", self.land_index));
+            return Ok(());
+        }
+
+        // 2. Get the decision, unless there is nothing to choose
+        let mut removal = if removals.len() == 1 {
+            removals[0].clone()
+        } else {
+            let sequence = match game.consume_choice()?
+            {
+                DecisionChoice::PieceSequence(res) => Ok(res),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?;
+
+            // 2a. Verify it
+            let invaders = &game.get_land(self.land_index)?.invaders;
+            let mut removal = Vec::new();
+            for (pk, i) in sequence {
+                match (pk, invaders.get(i)) {
+                    (PieceKind::Invader(ik), Some(invader)) if invader.kind == ik => {},
+                    _ => return Err(StepFailure::InternalError("mismatched index and piece kind.".to_string())),
+                }
+                if removal.contains(&i) {
+                    return Err(StepFailure::InternalError("duplicate invaders to remove!".to_string()));
+                }
+                removal.push(i);
+            }
+
+            let removed: Vec<Invader> = removal.iter().map(|i| invaders[*i]).collect();
+            if !(self.allowed)(game.get_land(self.land_index)?, &removed) || (!self.may && removed.is_empty()) {
+                return Err(StepFailure::RulesViolation("Can't remove those invaders.".to_string()));
+            }
+            removal
+        };
+
+        if self.downgrade {
+            game.log_decision(format_args!("replacing {} invaders in {}.", removal.len(), self.land_index));
+        } else {
+            game.log_decision(format_args!("removing {} invaders in {}.", removal.len(), self.land_index));
+        }
+
+        // 3. Perform it
+        removal.sort();
+        removal.reverse(); // so that higher indexes are first
+        for invader_index in removal {
+            let kind = game.get_land(self.land_index)?.invaders[invader_index].kind;
+            game.do_effect(RemoveInvaderEffect{land_index: self.land_index, invader_index, destroyed: false})?;
+
+            if self.downgrade {
+                let replacement = match kind {
+                    InvaderKind::City => Some(InvaderKind::Town),
+                    InvaderKind::Town => Some(InvaderKind::Explorer),
+                    InvaderKind::Explorer => None,
+                };
+                if let Some(kind) = replacement {
+                    game.do_effect(AddInvaderEffect{land_index: self.land_index, kind, count: 1})?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for RemoveInvadersDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        // TODO, resultify
+        let land = game.get_land(self.land_index).ok().unwrap();
+        self.removals(land).into_iter()
+            .map(|removal| DecisionChoice::PieceSequence(
+                removal.into_iter().map(|i| (PieceKind::Invader(land.invaders[i].kind), i)).collect()))
+            .collect()
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    any::Any,
+};
+
+use itertools::Itertools;
+
+use super::*;
+
+
+// A spirit gains `count` different elements of its choice, as indexes into the `ElementMap`. The
+// `also` spirit gains the same elements.
+#[derive(Clone)]
+pub struct GainElementsDecision {
+    pub spirit_index: u8,
+    pub count: usize,
+    pub also: Option<u8>,
+}
+
+impl Effect for GainElementsDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision
+        let sequence = match game.consume_choice()?
+        {
+            DecisionChoice::Sequence(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        // 1a. Verify it
+        if sequence.len() != self.count || sequence.iter().any(|e| *e >= 8) || sequence.iter().unique().count() != sequence.len() {
+            return Err(StepFailure::RulesViolation("Must gain that many different elements.".to_string()));
+        }
+
+        let mut elements = ElementMap::new(false);
+        for e in sequence {
+            elements.0[e] = true;
+        }
+
+        game.log_decision(format_args!("{} gaining elements |{}|.", self.spirit_index, elements));
+
+        // 2. Gain them
+        game.get_spirit_mut(self.spirit_index)?.elements.gain(&elements);
+        if let Some(spirit_index) = self.also {
+            game.log_subeffect(format_args!("{} also gaining them.", spirit_index));
+            game.get_spirit_mut(spirit_index)?.elements.gain(&elements);
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for GainElementsDecision {
+    fn valid_choices(&self, _game: &GameState) -> Vec<DecisionChoice> {
+        (0..8).combinations(self.count)
+            .map(DecisionChoice::Sequence)
+            .collect()
+    }
+}
+
+
+// A spirit destroys one of its presence on the island, from a land of its choice.
+#[derive(Clone)]
+pub struct DestroyPresenceDecision {
+    pub spirit_index: u8,
+}
+
+impl DestroyPresenceDecision {
+    fn lands(&self, game: &GameState) -> Vec<u8> {
+        game.table.lands.iter()
+            .filter(|l| l.presence[self.spirit_index] > 0)
+            .map(|l| l.desc.index_on_table)
+            .collect()
+    }
+}
+
+impl Effect for DestroyPresenceDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision, unless there is nothing to choose from
+        let lands = self.lands(game);
+        let land_index = match lands.as_slice() {
+            [] => {
+                game.log_effect(format_args!("destroying presence of {} (but no presence!).", self.spirit_index));
+                return Ok(());
+            },
+            [land_index] => *land_index,
+            _ => match game.consume_choice()? {
+                DecisionChoice::TargetLand{target_land, ..} => Ok(target_land),
+                _ => Err(StepFailure::DecisionMismatch),
+            }?,
+        };
+
+        if !lands.contains(&land_index) {
+            return Err(StepFailure::RulesViolation("No presence to destroy there.".to_string()));
+        }
+
+        game.log_decision(format_args!("{} destroying presence in {}.", self.spirit_index, land_index));
+
+        // 2. Destroy it
+        game.do_effect(DestroyPresenceEffect{ land_index, spirit_index: self.spirit_index })
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for DestroyPresenceDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        self.lands(game).into_iter()
+            .map(|l| DecisionChoice::TargetLand{target_land: l, source_land: l})
+            .collect()
+    }
+}
+
+
+// A spirit may gift one of the power cards in its hand to another spirit: Choice(0) gifts nothing,
+// Choice(i + 1) gifts the i-th card of the hand.
+#[derive(Clone)]
+pub struct GiftPowerCardDecision {
+    pub spirit_index: u8,
+    pub other_spirit_index: u8,
+}
+
+impl Effect for GiftPowerCardDecision {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Get the decision, unless there is nothing to gift
+        let hand_len = game.get_spirit(self.spirit_index)?.deck.hand.len();
+        if hand_len == 0 {
+            game.log_effect(format_args!("{} may gift a power card (but no cards in hand!).", self.spirit_index));
+            return Ok(());
+        }
+
+        let choice = match game.consume_choice()? {
+            DecisionChoice::Choice(res) => Ok(res),
+            _ => Err(StepFailure::DecisionMismatch),
+        }?;
+
+        if choice > hand_len {
+            return Err(StepFailure::InternalError("choice out of range".to_string()));
+        }
+        if choice == 0 {
+            game.log_decision(format_args!("{} gifts no power card.", self.spirit_index));
+            return Ok(());
+        }
+
+        // 2. Move the card
+        let card = game.get_spirit_mut(self.spirit_index)?.deck.hand.remove(choice - 1);
+        game.log_decision(format_args!("{} gifts |{}| to {}.", self.spirit_index, card.desc, self.other_spirit_index));
+        game.get_spirit_mut(self.other_spirit_index)?.deck.hand.push(card);
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+
+    fn as_decision(&self) -> Option<Box<dyn Decision>> { Some(Box::new(self.clone())) }
+}
+
+impl Decision for GiftPowerCardDecision {
+    fn valid_choices(&self, game: &GameState) -> Vec<DecisionChoice> {
+        let hand_len = game.get_spirit(self.spirit_index).map_or(0, |spirit| spirit.deck.hand.len());
+        (0..=hand_len).map(DecisionChoice::Choice).collect()
+    }
+
+    fn choice_label(&self, game: &GameState, choice: &DecisionChoice) -> String {
+        match (choice, game.get_spirit(self.spirit_index)) {
+            (DecisionChoice::Choice(0), _) => "gift nothing".to_string(),
+            (DecisionChoice::Choice(index), Ok(spirit)) if *index <= spirit.deck.hand.len() =>
+                format!("gift {}", spirit.deck.hand[*index - 1].desc.name),
+            _ => choice.to_string(),
+        }
+    }
+}
@@ -11,8 +11,12 @@
 mod invader;
 mod fear;
 mod power;
+mod blight;
+mod event;
 
 pub use self::invader::*;
 pub use self::fear::*;
 pub use self::power::*;
+pub use self::blight::*;
+pub use self::event::*;
 
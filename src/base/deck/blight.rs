@@ -0,0 +1,99 @@
+// This file contains copyrighted assets owned by Greater Than Games.
+
+use std::{
+    sync::{Arc},
+    clone::Clone,
+    iter::*,
+};
+
+use rand::prelude::*;
+
+use crate::base::{
+    effect::{Effect},
+};
+
+
+/*
+    The blight card starts on its Healthy Island side, which is the same for every card (2 blight
+    per player, plus 1). When the card runs out of blight it flips:
+
+    * Blighted Island: the card adds its blight per player and its effects start, running out of
+      blight again is a defeat.
+    * Still-Healthy Island: the card adds its blight per player, when it runs out again the next
+      card of the deck comes into play already flipped.
+*/
+
+#[derive(Clone)]
+pub struct BlightCardDescription {
+    pub name: &'static str,
+
+    pub still_healthy: bool,
+    pub blight_per_player: u8,
+
+    // When the card flips.
+    pub immediate: Option<Box<dyn Effect>>,
+    // At the start of every invader phase once flipped.
+    pub each_invader_phase: Option<Box<dyn Effect>>,
+}
+
+
+#[derive(Clone)]
+pub struct BlightCard {
+    pub desc: Arc<BlightCardDescription>,
+    pub index: usize
+}
+
+
+#[derive(Clone)]
+pub struct BlightDeck {
+    pub draw: Vec<BlightCard>,
+    // The card in play, none only for games that predate the blight deck (where running out of
+    // blight is a defeat).
+    pub card: Option<BlightCard>,
+    pub flipped: bool,
+    pub discard: Vec<BlightCard>,
+}
+
+impl BlightDeck {
+    pub const HEALTHY_BLIGHT_PER_PLAYER: u8 = 2;
+
+    pub fn new() -> BlightDeck {
+        BlightDeck {
+            draw: Vec::new(),
+            card: None,
+            flipped: false,
+            discard: Vec::new(),
+        }
+    }
+
+    pub fn init(&mut self, desc: &[Arc<BlightCardDescription>], rng: Option<&mut dyn RngCore>) {
+        self.draw = desc.iter()
+            .enumerate()
+            .map(|(i, d)| BlightCard{ desc: d.clone(), index: i })
+            .collect();
+        if let Some(mut rng) = rng {
+            self.draw.shuffle(&mut rng);
+        }
+    }
+
+    // Puts the top card of the draw pile into play (healthy side up), returns false if there is
+    // none left.
+    pub fn draw_into_play(&mut self) -> bool {
+        if let Some(card) = self.card.take() {
+            self.discard.push(card);
+        }
+
+        self.card = self.draw.pop();
+        self.flipped = false;
+
+        self.card.is_some()
+    }
+
+    // Whether the Blighted Island side is up (and the game is lost when the card runs out).
+    pub fn is_blighted(&self) -> bool {
+        match &self.card {
+            Some(card) => self.flipped && !card.desc.still_healthy,
+            None => true,
+        }
+    }
+}
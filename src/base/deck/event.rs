@@ -0,0 +1,106 @@
+// This file contains copyrighted assets owned by Greater Than Games.
+
+use std::{
+    sync::{Arc},
+    clone::Clone,
+    iter::*,
+};
+
+use rand::prelude::*;
+
+use crate::base::{
+    effect::{Effect},
+};
+
+
+/*
+    Event cards (the events variant) are drawn each invader phase after the blighted island step,
+    except on the first turn. A card resolves in parts, each of which is a step of its own:
+
+    * Part 0: the main event.
+    * Part 1: the token event (beasts, disease, wilds, strife...), not every card has one.
+    * Part 2: the dahan event, not every card has one.
+*/
+
+#[derive(Clone)]
+pub struct EventCardDescription {
+    pub name: &'static str,
+
+    pub main: Box<dyn Effect>,
+    pub token: Option<Box<dyn Effect>>,
+    pub dahan: Option<Box<dyn Effect>>,
+}
+
+impl EventCardDescription {
+    pub const PARTS: u8 = 3;
+
+    pub fn part(&self, part: u8) -> Option<Box<dyn Effect>> {
+        match part {
+            0 => Some(self.main.clone()),
+            1 => self.token.clone(),
+            2 => self.dahan.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn part_name(part: u8) -> &'static str {
+        match part {
+            0 => "Main",
+            1 => "Token",
+            _ => "Dahan",
+        }
+    }
+}
+
+
+#[derive(Clone)]
+pub struct EventCard {
+    pub desc: Arc<EventCardDescription>,
+    pub index: usize
+}
+
+
+#[derive(Clone)]
+pub struct EventDeck {
+    pub draw: Vec<EventCard>,
+    pub pending: Vec<EventCard>,
+    pub discard: Vec<EventCard>,
+}
+
+impl EventDeck {
+    pub fn new() -> EventDeck {
+        EventDeck {
+            draw: Vec::new(),
+            pending: Vec::new(),
+            discard: Vec::new(),
+        }
+    }
+
+    pub fn init(&mut self, desc: &[Arc<EventCardDescription>], rng: &mut dyn RngCore) {
+        self.draw = desc.iter()
+            .enumerate()
+            .map(|(i, d)| EventCard{ desc: d.clone(), index: i })
+            .collect();
+        self.draw.shuffle(rng);
+    }
+
+    // Whether the game is played with events at all.
+    pub fn is_in_play(&self) -> bool {
+        !(self.draw.is_empty() && self.pending.is_empty() && self.discard.is_empty())
+    }
+
+    pub fn draw_into_pending(&mut self, rng: &mut dyn RngCore) {
+        if self.draw.is_empty() {
+            self.draw.append(&mut self.discard);
+            self.draw.shuffle(rng);
+        }
+
+        if let Some(card) = self.draw.pop() {
+            self.pending.push(card);
+        }
+    }
+
+    pub fn discard_pending(&mut self) {
+        self.discard.append(&mut self.pending);
+    }
+}
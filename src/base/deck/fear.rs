@@ -39,6 +39,10 @@ pub struct FearDeck {
     pub draw: Vec<FearCard>,
     pub pending: Vec<FearCard>,
     pub discard: Vec<FearCard>,
+    // The fear cards set aside during setup, not part of the game.
+    pub unused: Vec<FearCard>,
+    // How many cards were resolved in the last invader phase.
+    pub resolved: usize,
 }
 
 impl FearDeck {
@@ -50,18 +54,23 @@ impl FearDeck {
             draw: Vec::new(),
             pending: Vec::new(),
             discard: Vec::new(),
+            unused: Vec::new(),
+            resolved: 0,
         }
     }
 
     pub fn init(&mut self, 
             desc: &Vec<Arc<FearCardDescription>>,
-            mut rng: &mut dyn RngCore,
+            rng: Option<&mut dyn RngCore>,
             fear_card_counts: (u8, u8, u8)) {
         let mut all_cards: Vec<FearCard> = desc.iter()
             .enumerate()
             .map(|(i, d)| FearCard{ desc: d.clone(), index: i })
             .collect();
-        all_cards.shuffle(&mut rng);
+        // without an rng the cards stay in order (when fate decides the draws)
+        if let Some(mut rng) = rng {
+            all_cards.shuffle(&mut rng);
+        }
 
         let (t1, t2, t3) = fear_card_counts;
 
@@ -71,6 +80,23 @@ impl FearDeck {
         for _ in 0..(t3 + t2 + t1) {
             self.draw.push(all_cards.pop().unwrap());
         }
+        self.unused = all_cards;
+    }
+
+    // The number of cards left in the draw pile at which terror level II and III begin.
+    pub fn terror_thresholds(&self) -> (usize, usize) {
+        (self.tier2_count, self.tier3_count)
+    }
+
+    pub fn set_state(&mut self, draw: Vec<FearCard>, pending: Vec<FearCard>, discard: Vec<FearCard>, unused: Vec<FearCard>, terror_thresholds: (usize, usize)) {
+        self.draw = draw;
+        self.pending = pending;
+        self.discard = discard;
+        self.unused = unused;
+
+        let (tier2_count, tier3_count) = terror_thresholds;
+        self.tier2_count = tier2_count;
+        self.tier3_count = tier3_count;
     }
 
     pub fn terror_level(&self) -> TerrorLevel {
@@ -100,6 +126,7 @@ impl FearDeck {
     }
 
     pub fn advance(&mut self) {
+        self.resolved = self.pending.len();
         self.discard.append(&mut self.pending);
     }
 }
@@ -5,6 +5,7 @@ use std::{
     fmt,
     clone::Clone,
     collections::VecDeque,
+    str::FromStr,
 };
 
 use rand::prelude::*;
@@ -14,7 +15,7 @@ use crate::base::{
     board::{LandDescription},
 };
 
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
 pub enum InvaderCard {
     Phase1(LandKind),
     Phase2(LandKind),
@@ -32,7 +33,34 @@ impl fmt::Display for InvaderCard {
     }
 }
 
+// The inverse of the Display impl.
+impl FromStr for InvaderCard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["Phase", "I", kind] => Ok(InvaderCard::Phase1(kind.parse()?)),
+            ["Phase", "II", "Coastal"] => Ok(InvaderCard::Phase2(LandKind::Ocean)),
+            ["Phase", "II", kind, "+"] => Ok(InvaderCard::Phase2(kind.parse()?)),
+            ["Phase", "III", kinds] => match kinds.split('/').collect::<Vec<_>>().as_slice() {
+                [kind_a, kind_b] => Ok(InvaderCard::Phase3(kind_a.parse()?, kind_b.parse()?)),
+                _ => Err(format!("unknown invader card `{}`", s)),
+            },
+            _ => Err(format!("unknown invader card `{}`", s)),
+        }
+    }
+}
+
 impl InvaderCard {
+    pub fn phase(&self) -> u8 {
+        match *self {
+            InvaderCard::Phase1(_) => 1,
+            InvaderCard::Phase2(_) => 2,
+            InvaderCard::Phase3(_, _) => 3,
+        }
+    }
+
     pub fn can_target(&self, land: &Arc<LandDescription>) -> bool {
         match *self {
             InvaderCard::Phase1(kind) => kind == land.kind,
@@ -65,6 +93,31 @@ pub fn generate_invader_deck() -> Vec<InvaderCard> {
     ]
 }
 
+// The standard deck, from the top: 3 stage I cards, 4 stage II cards then 5 stage III cards.
+pub const STANDARD_INVADER_STAGES: [u8; 12] = [1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3];
+
+// Stacks a draw pile with a card of each stage in `stages` (from the top), taken from `cards` at
+// random. Without an rng the cards are taken in order (when fate decides the draws). The cards
+// left over are not used this game.
+pub fn stack_invader_deck(stages: &[u8], cards: &[InvaderCard], mut rng: Option<&mut dyn RngCore>) -> Result<Vec<InvaderCard>, String> {
+    let mut by_stage: Vec<Vec<InvaderCard>> = (1..=3)
+        .map(|stage| cards.iter().filter(|c| c.phase() == stage).cloned().collect())
+        .collect();
+    if let Some(rng) = rng.as_mut() {
+        for pile in by_stage.iter_mut() {
+            pile.shuffle(rng);
+        }
+    }
+
+    // the top of the pile is last
+    stages.iter().rev()
+        .map(|stage| (*stage as usize).checked_sub(1)
+            .and_then(|i| by_stage.get_mut(i))
+            .and_then(|pile| pile.pop())
+            .ok_or_else(|| format!("not enough stage {} invader cards", stage)))
+        .collect()
+}
+
 
 
 #[derive(Clone)]
@@ -101,18 +154,6 @@ impl InvaderDeck {
         for _step in self.sequence.iter() { self.pending.push_back(Vec::new()); }
     }
 
-    pub fn shuffle_draw(&mut self, mut rng: &mut dyn RngCore) {
-        // have to shuffle specific parts
-        let partition2 = self.draw.iter().position(|&x| if let InvaderCard::Phase2(_) = x { true } else { false }).unwrap();
-        let partition1 = self.draw.iter().position(|&x| if let InvaderCard::Phase1(_) = x { true } else { false }).unwrap();
-
-        assert!(partition2 < partition1);
-
-        self.draw[0..partition2].shuffle(&mut rng);
-        self.draw[partition2..partition1].shuffle(&mut rng);
-        self.draw[partition1..15].shuffle(&mut rng);
-    }
-
     pub fn draw(&mut self, count: usize) -> Vec<InvaderCard> {
         let mut res = Vec::new();
         for _ in 0..count {
@@ -134,3 +175,40 @@ impl InvaderDeck {
         self.pending.push_back(Vec::new());
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaChaRng;
+
+    fn stages_of(deck: &[InvaderCard]) -> Vec<u8> {
+        deck.iter().rev().map(|c| c.phase()).collect()
+    }
+
+    #[test]
+    fn stacking_follows_the_stages() {
+        let cards = generate_invader_deck();
+        assert_eq!(cards.iter().filter(|c| c.phase() == 1).count(), 4);
+        assert_eq!(cards.iter().filter(|c| c.phase() == 2).count(), 5);
+        assert_eq!(cards.iter().filter(|c| c.phase() == 3).count(), 6);
+
+        // in order, one card of each stage is left out
+        let deck = stack_invader_deck(&STANDARD_INVADER_STAGES, &cards, None).unwrap();
+        assert_eq!(stages_of(&deck), STANDARD_INVADER_STAGES.to_vec());
+        assert!(deck == stack_invader_deck(&STANDARD_INVADER_STAGES, &cards, None).unwrap());
+
+        let mut rng = ChaChaRng::seed_from_u64(1);
+        let shuffled = stack_invader_deck(&[3, 1, 2], &cards, Some(&mut rng)).unwrap();
+        assert_eq!(stages_of(&shuffled), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn stacking_needs_enough_cards() {
+        let cards = generate_invader_deck();
+        assert!(stack_invader_deck(&[1; 4], &cards, None).is_ok());
+        assert!(stack_invader_deck(&[1; 5], &cards, None).is_err());
+        assert!(stack_invader_deck(&[0], &cards, None).is_err());
+        assert!(stack_invader_deck(&[4], &cards, None).is_err());
+    }
+}
@@ -38,10 +38,17 @@ impl fmt::Display for PowerCardKind {
     }
 }
 
+// Land powers are targeted from a land (`src`, also given the spirit using the power) to a land in
+// range of it (`dst`).
 #[derive(Copy, Clone)]
 pub enum PowerTargetFilter {
     Spirit(fn(&SpiritState) -> bool),
-    Land{range: u8, src: fn(&LandState) -> bool, dst: fn(&LandState) -> bool},
+    Land{range: u8, src: fn(&LandState, u8) -> bool, dst: fn(&LandState) -> bool},
+}
+
+// A `src` filter for powers targeted from one of the spirit's sacred sites.
+pub fn sacred_site(land: &LandState, spirit_index: u8) -> bool {
+    land.presence[spirit_index] >= 2
 }
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
@@ -115,6 +122,66 @@ impl Effect for PowerCardDescription {
 }
 
 
+#[derive(Clone)]
+pub struct InnatePowerLevel {
+    pub elements: ElementMap<u8>,
+    // "Instead": the level replaces the levels before it rather than adding to them.
+    pub instead: bool,
+
+    pub effect: SubEffect,
+}
+
+#[derive(Clone)]
+pub struct InnatePowerDescription {
+    pub name: &'static str,
+
+    pub speed: PowerSpeed,
+    pub target_filter: PowerTargetFilter,
+
+    // lowest threshold first
+    pub levels: Vec<InnatePowerLevel>,
+}
+
+impl InnatePowerDescription {
+    // The levels whose thresholds the elements meet (in order), after dropping the ones replaced
+    // by an "instead" level.
+    pub fn levels_met(&self, elements: &ElementMap<u8>) -> Vec<usize> {
+        let met: Vec<usize> = (0..self.levels.len())
+            .filter(|i| elements.meets(&self.levels[*i].elements))
+            .collect();
+
+        match met.iter().rposition(|i| self.levels[*i].instead) {
+            Some(start) => met[start..].to_vec(),
+            None => met,
+        }
+    }
+}
+
+impl fmt::Display for InnatePowerDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Innate - {}", self.name)
+    }
+}
+
+impl Effect for InnatePowerDescription {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let spirit_index = game.get_power_usage()?.using_spirit_index;
+        let levels = self.levels_met(&game.get_spirit(spirit_index)?.elements);
+
+        game.log_effect(format_args!("using innate power |{}| (level {})", self, levels.last().map_or(0, |l| l + 1)));
+
+        for level in levels {
+            (self.levels[level].effect)(game)?;
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
 #[derive(Clone)]
 pub struct PowerCard {
     pub desc: Arc<PowerCardDescription>,
@@ -140,31 +207,41 @@ impl PowerDeck {
 
     pub fn init(&mut self, 
             desc: Vec<Arc<PowerCardDescription>>,
-            mut rng: &mut dyn RngCore) {
+            rng: Option<&mut dyn RngCore>) {
         self.draw
             = desc.into_iter()
                 .enumerate()
                 .map(|(index, desc)| PowerCard{ index, desc })
                 .collect();
-        self.draw.shuffle(&mut rng);
+        // without an rng the cards stay in order (when fate decides the draws)
+        if let Some(mut rng) = rng {
+            self.draw.shuffle(&mut rng);
+        }
     }
 
+    // Draws up to `count` cards, reshuffling the discard when the draw pile runs out (fewer when
+    // both are empty).
     pub fn draw(&mut self, rng: &mut dyn RngCore, count: usize) -> Vec<PowerCard> {
         let mut res = Vec::new();
-        // TODO resultify
         // TODO make not a for a loop
         for _ in 0..count {
-            if let Some(card) = self.draw.pop() {
-                res.insert(0, card);
-            } else {
+            if self.draw.is_empty() {
                 self.shuffle_discard_into_draw(rng);
-                res.insert(0, self.draw.pop().unwrap());
+            }
+            match self.draw.pop() {
+                Some(card) => res.insert(0, card),
+                None => break,
             }
         }
 
         res
     }
 
+    // Whether any card is left to draw (after reshuffling the discard).
+    pub fn has_cards(&self) -> bool {
+        !self.draw.is_empty() || !self.discard.is_empty()
+    }
+
     pub fn draw_into_pending(&mut self, rng: &mut dyn RngCore, count: usize) {
         let drawn = self.draw(rng, count);
         self.pending.extend(drawn);
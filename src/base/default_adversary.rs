@@ -1,7 +1,6 @@
 use super::concept::{AdversaryDescription, InvaderActionKind};
-use super::step::{GameStep};
+use super::step::{StepFailure};
 use super::game::{GameState};
-use super::deck::{InvaderCard};
 
 pub struct DefaultAdversaryDescription {
 
@@ -10,43 +9,25 @@ pub struct DefaultAdversaryDescription {
 impl DefaultAdversaryDescription {
     pub fn new() -> DefaultAdversaryDescription {
         DefaultAdversaryDescription {
-            
+
         }
     }
 }
 
-pub fn invader_deck_setup_standard(game: &mut GameState)
-{
-    let phase3 = game.invader.draw.iter().position(|&x| if let InvaderCard::Phase3(_, _) = x { true } else { false }).unwrap();
-    game.log_effect(format_args!("Removing {{{}}}", game.invader.draw.get(phase3).unwrap()));
-    game.invader.draw.remove(phase3);
-
-    let phase2 = game.invader.draw.iter().position(|&x| if let InvaderCard::Phase2(_) = x { true } else { false }).unwrap();
-    game.log_effect(format_args!("Removing {{{}}}", game.invader.draw.get(phase2).unwrap()));
-    game.invader.draw.remove(phase2);
-
-    let phase1 = game.invader.draw.iter().position(|&x| if let InvaderCard::Phase1(_) = x { true } else { false }).unwrap();
-    game.log_effect(format_args!("Removing {{{}}}", game.invader.draw.get(phase1).unwrap()));
-    game.invader.draw.remove(phase1);
-}
-
 
 impl AdversaryDescription for DefaultAdversaryDescription {
-    fn fear_cards(&self) -> (u8, u8, u8) { 
+    fn name(&self) -> &'static str { "none" }
+    fn level(&self) -> u8 { 0 }
+    fn difficulty(&self) -> u8 { 0 }
+
+    fn fear_cards(&self) -> (u8, u8, u8) {
         (3, 3, 3)
     }
     fn invader_steps(&self) -> Vec<InvaderActionKind> {
         vec![InvaderActionKind::Ravage, InvaderActionKind::Build, InvaderActionKind::Explore]
     }
 
-    fn setup(&self, game: &mut GameState) {
-        let step = game.step;
-
-        match step {
-            GameStep::Init => {
-                invader_deck_setup_standard(game);
-            },
-            _ => {},
-        }
+    fn setup(&self, _game: &mut GameState) -> Result<(), StepFailure> {
+        Ok(())
     }
-}
\ No newline at end of file
+}
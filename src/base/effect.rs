@@ -23,25 +23,33 @@ impl Clone for Box<dyn Effect> {
 
 
 pub type SubEffect = fn (&mut GameState) -> Result<(), StepFailure>;
+// A sub effect on a land (by index on the table).
+pub type LandEffect = fn (&mut GameState, u8) -> Result<(), StepFailure>;
+// A sub effect on a spirit (by index).
+pub type SpiritEffect = fn (&mut GameState, u8) -> Result<(), StepFailure>;
 
 
 mod add_piece;
+mod blight;
 mod do_damage;
 mod fear;
 mod growth;
 mod invader_action;
 mod meta;
+mod move_piece;
 mod persist;
 mod remove_piece;
 mod spirit;
 
 pub use self::add_piece::{AddBlightEffect, AddPresenceEffect, AddInvaderEffect, AddDahanEffect};
+pub use self::blight::{FlipBlightCardEffect};
 pub use self::do_damage::{DoDamageToLandEffect, DoInvaderAttackEffect, DoDahanAttackEffect, DoDamageToEachInvaderEffect};
 pub use self::fear::{GenerateFearEffect};
-pub use self::growth::{GenerateEnergyEffect, ReclaimAllEffect};
+pub use self::growth::{GenerateEnergyEffect, ReclaimAllEffect, TrackIncomeEffect};
 pub use self::invader_action::{ExploreEffect, BuildEffect, RavageEffect};
-pub use self::meta::{NotImplementedEffect, ForAllLandsDoEffect};
-pub use self::persist::{PersistDefenseEffect};
-pub use self::remove_piece::{RemoveBlightEffect, RemoveDahanEffect, RemoveInvaderEffect};
-pub use self::spirit::{MayPlaySlowsAsFastsEffect};
+pub use self::meta::{ForAllLandsDoEffect, DoSubEffect};
+pub use self::move_piece::{MovePieceEffect};
+pub use self::persist::{PersistDefenseEffect, SkipInvaderActionEffect, PersistVengeanceEffect};
+pub use self::remove_piece::{RemoveBlightEffect, DestroyPresenceEffect, RemoveDahanEffect, RemoveInvaderEffect, AvengeDestroyedEffect};
+pub use self::spirit::{MayPlaySlowsAsFastsEffect, ExtraRangeEffect, MayRepeatPowersEffect, EntwinePresenceEffect, ProtectDahanEffect};
 
@@ -15,22 +15,32 @@ impl Effect for AddBlightEffect {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
         game.log_effect(format_args!("blighting land {}.", self.land_index));
 
-        // 1. Remove blight from card
+        // 1. Remove blight from card, flipping it when it runs out
         if game.blight_remaining == 0 {
             game.do_defeat("No blight is left.")?;
         }
 
         game.blight_remaining -= 1;
+        if game.blight_remaining == 0 {
+            game.do_effect(FlipBlightCardEffect {})?;
+        }
 
         // 2. Add blight to the land
+        let spirit_count = game.spirits.len() as u8;
         let land = game.get_land_mut(self.land_index)?;
         land.tokens[TokenKind::Blight] += 1;
-
-        // 3. Kill presence
-        //land.destroy_presence();
+        let blight = land.tokens[TokenKind::Blight];
+
+        // 3. Kill presence, one of each spirit here
+        let spirits_here: Vec<u8> = (0..spirit_count)
+            .filter(|s| land.presence[*s] > 0)
+            .collect();
+        for spirit_index in spirits_here {
+            game.do_effect(DestroyPresenceEffect { land_index: self.land_index, spirit_index })?;
+        }
 
         // 4. Check for cascade
-        if land.tokens[TokenKind::Blight] > 1 {
+        if blight > 1 {
             game.do_effect(CascadeBlightDecision {src_land_index: self.land_index})?;
         }
         
@@ -82,11 +92,14 @@ impl Effect for AddInvaderEffect {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
         game.log_effect(format_args!("adding {} {} invader(s) to {}.", self.count, self.kind, self.land_index));
 
+        let mut invader = Invader::new(self.kind);
+        game.desc.adversary.modify_invader(&mut invader);
+
         let land = game.get_land_mut(self.land_index)?;
 
         // 1. Add the invaders
         for _ in 0..self.count {
-            land.invaders.push(Invader::new(self.kind));
+            land.invaders.push(invader);
         }
         
         Ok(())
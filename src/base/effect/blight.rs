@@ -0,0 +1,91 @@
+use std::{
+    any::Any
+};
+
+use super::*;
+
+
+// The blight card ran out of blight, see `BlightDeck`.
+#[derive(Clone)]
+pub struct FlipBlightCardEffect {
+}
+
+impl Effect for FlipBlightCardEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // 1. Running out on the blighted side loses the game
+        if game.blight.is_blighted() {
+            game.do_defeat("No blight is left.")?;
+        }
+
+        // 2. A still-healthy card brings the next card into play
+        if game.blight.flipped {
+            game.fate_draw(FateDeck::Blight)?;
+            if !game.blight.draw_into_play() {
+                game.do_defeat("No blight cards are left.")?;
+            }
+        }
+
+        // 3. Flip the card
+        let card = game.blight.card.clone().unwrap();
+        game.blight.flipped = true;
+        game.blight_remaining += card.desc.blight_per_player * game.spirits.len() as u8;
+        game.log_effect(format_args!("flipping the blight card: {} ({}).",
+            card.desc.name, if card.desc.still_healthy { "still-healthy island" } else { "blighted island" }));
+
+        if let Some(effect) = card.desc.immediate.clone() {
+            game.do_effect_box(effect)?;
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::testing::set_up_game;
+
+    fn card(name: &'static str, still_healthy: bool, blight_per_player: u8, index: usize) -> BlightCard {
+        BlightCard {
+            desc: Arc::new(BlightCardDescription { name, still_healthy, blight_per_player, immediate: None, each_invader_phase: None }),
+            index,
+        }
+    }
+
+    #[test]
+    fn still_healthy_cards_bring_the_next_card_into_play() {
+        let mut game = set_up_game(&["river", "lightning"], &["A", "B"]);
+        game.blight.card = Some(card("Healthy", true, 1, 0));
+        game.blight.flipped = false;
+        game.blight.draw = vec![card("Blighted", false, 4, 1)];
+        game.blight_remaining = 0;
+
+        game.do_effect(FlipBlightCardEffect {}).unwrap();
+        assert!(game.blight.flipped && !game.blight.is_blighted());
+        assert_eq!(game.blight_remaining, 2);
+
+        game.blight_remaining = 0;
+        game.do_effect(FlipBlightCardEffect {}).unwrap();
+        assert_eq!(game.blight.card.as_ref().unwrap().desc.name, "Blighted");
+        assert_eq!(game.blight.discard.len(), 1);
+        assert!(game.blight.is_blighted());
+        assert_eq!(game.blight_remaining, 8);
+
+        assert!(matches!(game.do_effect(FlipBlightCardEffect {}), Err(StepFailure::GameOverDefeat)));
+    }
+
+    #[test]
+    fn running_out_of_blight_cards_loses() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        game.blight.card = Some(card("Healthy", true, 1, 0));
+        game.blight.flipped = true;
+        game.blight.draw.clear();
+
+        assert!(matches!(game.do_effect(FlipBlightCardEffect {}), Err(StepFailure::GameOverDefeat)));
+    }
+}
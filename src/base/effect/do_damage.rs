@@ -67,7 +67,13 @@ impl Effect for DoInvaderAttackEffect {
         game.do_effect(DoDamageToDahanDecision{land_index: self.land_index, damage: invader_damage, efficent: true})?;
 
         // 2b. Damage to land
+        let blight_before = game.get_land(self.land_index)?.tokens[TokenKind::Blight];
         game.do_effect(DoDamageToLandEffect{land_index: self.land_index, damage: invader_damage})?;
+        let blighted = game.get_land(self.land_index)?.tokens[TokenKind::Blight] > blight_before;
+
+        // 3. Adversary rules
+        let desc = game.desc.clone();
+        desc.adversary.after_ravage_damage(game, self.land_index, invader_damage, blighted)?;
 
         Ok(())
     }
@@ -148,6 +154,7 @@ impl Effect for DoDamageToEachInvaderEffect {
         for invader_index in destroyed_invaders {
             game.do_effect(RemoveInvaderEffect{land_index: self.land_index, invader_index, destroyed: true})?;
         }
+        game.do_effect(AvengeDestroyedEffect{land_index: self.land_index})?;
 
         game.log_subeffect(format_args!("{} total damage in {}.", total_damage, self.land_index));
 
@@ -30,6 +30,7 @@ impl Effect for GenerateFearEffect {
             if game.fear_pool == 0 {
                 game.log_subeffect(format_args!("drawing a fear card!"));
 
+                game.fate_draw(FateDeck::Fear)?;
                 game.fear.draw_into_pending();
                 game.fear_pool = game.fear_generated;
                 game.fear_generated = 0;
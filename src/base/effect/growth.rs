@@ -45,3 +45,99 @@ impl Effect for ReclaimAllEffect {
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
     fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
 }
+
+
+// Gives a spirit the income of its uncovered presence track spots (see `PresenceTrack`).
+#[derive(Clone)]
+pub struct TrackIncomeEffect {
+    pub spirit_index: u8,
+}
+
+impl Effect for TrackIncomeEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let tracks = game.get_spirit_desc(self.spirit_index)?.get_presence_tracks();
+        let spirit = game.get_spirit_mut(self.spirit_index)?;
+
+        // 1. Sum up the uncovered spots
+        let mut energy = 0;
+        let mut plays = 0;
+        let mut reclaim_one = false;
+        for track in tracks.iter() {
+            for slot in track.uncovered(&spirit.presence) {
+                match *slot {
+                    TrackSlot::Energy(value) => energy = value,
+                    TrackSlot::Plays(value) => plays = value,
+                    TrackSlot::Element(element) => spirit.elements[element] += 1,
+                    TrackSlot::ExtraEnergy(value) => energy += value,
+                    TrackSlot::ReclaimOne => reclaim_one = true,
+                }
+            }
+        }
+
+        game.log_effect(format_args!("income for {}: {} card plays.", self.spirit_index, plays));
+
+        // 2. Gain it
+        game.get_spirit_mut(self.spirit_index)?.plays = plays;
+        game.do_effect(GenerateEnergyEffect{ spirit_index: self.spirit_index, energy })?;
+        if reclaim_one {
+            game.do_effect(ReclaimOneDecision{ spirit_index: self.spirit_index })?;
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::set_up_game;
+
+    // The energy and plays of the spirit's income with the presence at `uncovered` (indices
+    // into its presence) taken off its tracks.
+    fn income(game: &GameState, uncovered: std::ops::Range<usize>) -> (u8, u8) {
+        let mut game = game.clone();
+        let spirit = &mut game.spirits[0];
+        for index in uncovered {
+            spirit.presence[index] = PresenceState::OnBoard(0);
+        }
+        spirit.energy = 0;
+        spirit.plays = 0;
+
+        game.do_effect(TrackIncomeEffect{spirit_index: 0}).unwrap();
+        (game.spirits[0].energy, game.spirits[0].plays)
+    }
+
+    #[test]
+    fn river_income_follows_the_printed_tracks() {
+        let game = set_up_game(&["river"], &["A"]);
+
+        // the energy track starts at presence #1, the plays track at presence #7
+        let energy = [1, 2, 2, 3, 4, 4, 5];
+        for (count, value) in energy.iter().enumerate() {
+            assert_eq!(income(&game, 1..1 + count), (*value, 1), "{} uncovered", count);
+        }
+        let plays = [1, 2, 2, 3, 3, 4, 5];
+        for (count, value) in plays.iter().enumerate() {
+            assert_eq!(income(&game, 7..7 + count), (1, *value), "{} uncovered", count);
+        }
+    }
+
+    #[test]
+    fn lightning_income_follows_the_printed_tracks() {
+        let game = set_up_game(&["lightning"], &["A"]);
+
+        // the energy track starts at presence #2, the plays track at presence #9
+        let energy = [1, 1, 2, 2, 3, 4, 4, 5];
+        for (count, value) in energy.iter().enumerate() {
+            assert_eq!(income(&game, 2..2 + count), (*value, 2), "{} uncovered", count);
+        }
+        let plays = [2, 3, 4, 5, 6];
+        for (count, value) in plays.iter().enumerate() {
+            assert_eq!(income(&game, 9..9 + count), (1, *value), "{} uncovered", count);
+        }
+    }
+}
@@ -11,18 +11,27 @@ pub struct ExploreEffect {
     pub land_index: u8,
 }
 
-impl Effect for ExploreEffect {
-    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
-        game.log_effect(format_args!("Exploring in land {}.", self.land_index));
+impl ExploreEffect {
+    // Whether an explorer arrives, exploring never changes this.
+    pub fn will_explore(&self, game: &GameState) -> bool {
+        if game.get_land(self.land_index).ok().unwrap().skip_explore {
+            return false;
+        }
 
         let adj_lands = game.table.desc.get_adjacent_lands(self.land_index);
-        let will_explore = adj_lands.iter().any(|l|
+        adj_lands.iter().any(|l|
             game.get_land(l.index_on_table).ok().unwrap()
                 .invaders.iter()
                 .any(|i| i.is_building())
-        );
+        )
+    }
+}
+
+impl Effect for ExploreEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("Exploring in land {}.", self.land_index));
 
-        if will_explore {
+        if self.will_explore(game) {
             game.do_effect(AddInvaderEffect {
                 land_index: self.land_index,
                 kind: InvaderKind::Explorer,
@@ -49,7 +58,7 @@ impl Effect for BuildEffect {
 
         let land = game.get_land(self.land_index)?;
 
-        if land.invaders.len() != 0 {
+        if land.invaders.len() != 0 || game.desc.adversary.builds_without_invaders(game, self.land_index) {
             let building_type_distance : i8 = land.invaders.iter().map(|i|
                 match i.kind {
                     InvaderKind::Town => -1,
@@ -57,9 +66,15 @@ impl Effect for BuildEffect {
                     _ => 0,
                 }).sum();
 
+            let kind = if building_type_distance >= 0 { InvaderKind::Town } else { InvaderKind::City };
+            if land.skip_build[kind] {
+                game.log_subeffect(format_args!("build of {} skipped.", kind));
+                return Ok(());
+            }
+
             game.do_effect(AddInvaderEffect {
                 land_index: self.land_index,
-                kind: if building_type_distance >= 0 { InvaderKind::Town } else { InvaderKind::City },
+                kind,
                 count: 1
             })?;
         }
@@ -83,6 +98,11 @@ impl Effect for RavageEffect {
 
         let land = game.get_land(self.land_index)?;
 
+        if land.skip_ravage {
+            game.log_subeffect(format_args!("ravage skipped."));
+            return Ok(());
+        }
+
         if land.invaders.len() != 0 {
             // 1. Invaders to damage
             game.do_effect(DoInvaderAttackEffect { land_index: self.land_index })?;
@@ -7,23 +7,6 @@ use super::*;
 
 
 
-#[derive(Clone)]
-pub struct NotImplementedEffect {
-    pub what: &'static str,
-}
-
-impl Effect for NotImplementedEffect {
-    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
-        game.log_effect(format_args!("NOT IMPLEMENTED {}.", self.what));
-
-        Ok(())
-    }
-
-    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
-    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
-}
-
-
 #[derive(Clone)]
 pub struct ForAllLandsDoEffect {
     pub filter: fn(land: &LandState) -> bool,
@@ -50,3 +33,19 @@ impl Effect for ForAllLandsDoEffect {
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
     fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
 }
+
+
+// Runs a sub effect, for when an effect is more than one of the others in a row.
+#[derive(Clone)]
+pub struct DoSubEffect {
+    pub effect: SubEffect,
+}
+
+impl Effect for DoSubEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        (self.effect)(game)
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
@@ -0,0 +1,54 @@
+use std::{
+    any::Any
+};
+
+use super::*;
+
+
+// Moves a piece between lands, the index is among the pieces of its kind in the land it comes
+// from. The piece keeps its state (damage and so on).
+#[derive(Clone)]
+pub struct MovePieceEffect {
+    pub from_land_index: u8,
+    pub to_land_index: u8,
+    pub kind: PieceKind,
+    pub index: usize,
+}
+
+impl Effect for MovePieceEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("moving {} {} from {} to {}.", self.kind, self.index, self.from_land_index, self.to_land_index));
+
+        // 1. Take the piece
+        let src_land = game.get_land_mut(self.from_land_index)?;
+        if self.index >= src_land.get_count(&self.kind) {
+            return Err(StepFailure::InternalError("Bad Index!".to_string()));
+        }
+
+        match self.kind {
+            PieceKind::Token(tk) => {
+                src_land.tokens[tk] -= 1;
+                game.get_land_mut(self.to_land_index)?.tokens[tk] += 1;
+            },
+            PieceKind::Invader(ik) => {
+                let position = src_land.invaders.iter()
+                    .enumerate()
+                    .filter(|(_, i)| i.kind == ik)
+                    .nth(self.index)
+                    .map(|(p, _)| p)
+                    .unwrap();
+                let invader = src_land.invaders.remove(position);
+                game.get_land_mut(self.to_land_index)?.invaders.push(invader);
+            },
+            PieceKind::Dahan => {
+                let dahan = src_land.dahan.remove(self.index);
+                game.get_land_mut(self.to_land_index)?.dahan.push(dahan);
+            },
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
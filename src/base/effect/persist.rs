@@ -25,4 +25,60 @@ impl Effect for PersistDefenseEffect {
 
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
     fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
-}
\ No newline at end of file
+}
+
+
+// The invaders skip an action in a land this round, a build only when it would be of one of
+// `buildings`.
+#[derive(Clone)]
+pub struct SkipInvaderActionEffect {
+    pub land_index: u8,
+    pub action: InvaderActionKind,
+    pub buildings: InvaderMap<bool>,
+}
+
+impl Effect for SkipInvaderActionEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("skipping {} in land {}.", self.action, self.land_index));
+
+        let land = game.get_land_mut(self.land_index)?;
+        match self.action {
+            InvaderActionKind::Explore => land.skip_explore = true,
+            InvaderActionKind::Build => {
+                for kind in [InvaderKind::Town, InvaderKind::City] {
+                    land.skip_build[kind] |= self.buildings[kind];
+                }
+            },
+            InvaderActionKind::Ravage => land.skip_ravage = true,
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+// Each town, city or dahan destroyed in a land this round does damage to the invaders there (or in
+// an adjacent land when it spreads).
+#[derive(Clone)]
+pub struct PersistVengeanceEffect {
+    pub land_index: u8,
+    pub spreads: bool,
+}
+
+impl Effect for PersistVengeanceEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("vengeance for the destroyed in land {}.", self.land_index));
+
+        let land = game.get_land_mut(self.land_index)?;
+        land.vengeance += 1;
+        land.vengeance_spreads |= self.spreads;
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
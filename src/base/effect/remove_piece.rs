@@ -34,6 +34,42 @@ impl Effect for RemoveBlightEffect {
 
 
 
+#[derive(Clone)]
+pub struct DestroyPresenceEffect {
+    pub land_index: u8,
+    pub spirit_index: u8,
+}
+
+impl Effect for DestroyPresenceEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("destroying presence of {} in land {}.", self.spirit_index, self.land_index));
+
+        // 1. Destroy the presence (which one doesn't matter, they are all on the island)
+        let spirit = game.get_spirit_mut(self.spirit_index)?;
+        let presence_index = spirit.presence.iter()
+            .rposition(|p| *p == PresenceState::OnBoard(self.land_index))
+            .ok_or(StepFailure::InternalError("no presence to destroy".to_string()))?;
+        spirit.presence[presence_index] = PresenceState::Destroyed;
+
+        let land = game.get_land_mut(self.land_index)?;
+        land.presence[self.spirit_index] -= 1;
+
+        // 2. A spirit without presence on the island is destroyed
+        let on_island = game.get_spirit(self.spirit_index)?.presence.iter()
+            .any(|p| matches!(p, PresenceState::OnBoard(_)));
+        if !on_island {
+            game.do_defeat("A spirit has no presence on the island.")?;
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+
 #[derive(Clone)]
 pub struct RemoveDahanEffect {
     pub land_index: u8,
@@ -43,6 +79,11 @@ pub struct RemoveDahanEffect {
 
 impl Effect for RemoveDahanEffect {
     fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        if self.destroyed && game.get_dahan_protection(self.land_index)?.1 {
+            game.log_effect(format_args!("destoying dahan {} in {} (but they ignore it!).", self.dahan_index, self.land_index));
+            return Ok(());
+        }
+
         if self.destroyed {
             game.log_effect(format_args!("destoying dahan {} in {}.", self.dahan_index, self.land_index));
         } else {
@@ -52,15 +93,17 @@ impl Effect for RemoveDahanEffect {
         let land = game.get_land_mut(self.land_index)?;
 
         // 1. Remove the dahan
-        if !self.dahan_index < land.dahan.len() {
+        if self.dahan_index >= land.dahan.len() {
             return Err(StepFailure::InternalError("Bad Index!".to_string()));
         }
 
         land.dahan.remove(self.dahan_index);
 
-        // 2. Destroy triggers
-        // TODO
-        
+        // 2. Destroy triggers, done once the destroying effect is (see `AvengeDestroyedEffect`)
+        if self.destroyed && land.vengeance > 0 {
+            land.vengeance_owed += 1;
+        }
+
         Ok(())
     }
 
@@ -87,23 +130,66 @@ impl Effect for RemoveInvaderEffect {
 
         let land = game.get_land_mut(self.land_index)?;
 
-        // 1. Remove the dahan
-        if !self.invader_index < land.invaders.len() {
+        // 1. Remove the invader
+        if self.invader_index >= land.invaders.len() {
             return Err(StepFailure::InternalError("Bad Index!".to_string()));
         }
         
         let removed = land.invaders.remove(self.invader_index);
 
-        // 2. Fear
+        // 2. Fear, only for destroying buildings
         match removed.kind {
-            InvaderKind::City => { game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(self.land_index)})?; },
-            InvaderKind::Town => { game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(self.land_index)})?; },
+            InvaderKind::City if self.destroyed => { game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(self.land_index)})?; },
+            InvaderKind::Town if self.destroyed => { game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(self.land_index)})?; },
             _ => {},
         }
 
-        // 3. Destroy triggers
-        // TODO
-        
+        // 3. Destroy triggers, done once the destroying effect is (see `AvengeDestroyedEffect`)
+        let land = game.get_land_mut(self.land_index)?;
+        if self.destroyed && removed.is_building() && land.vengeance > 0 {
+            land.vengeance_owed += 1;
+        }
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+
+// Done after each effect that destroys pieces: the vengeance for the towns, cities and dahan it
+// destroyed in a land (see `PersistVengeanceEffect`), 1 damage each per vengeance.
+#[derive(Clone)]
+pub struct AvengeDestroyedEffect {
+    pub land_index: u8,
+}
+
+impl Effect for AvengeDestroyedEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        let land = game.get_land_mut(self.land_index)?;
+        let damage = land.vengeance_owed as u16 * land.vengeance as u16;
+        let spreads = land.vengeance_spreads;
+        land.vengeance_owed = 0;
+        if damage == 0 {
+            return Ok(());
+        }
+
+        game.log_effect(format_args!("{} damage of vengeance from land {}.", damage, self.land_index));
+
+        // 1 damage at a time, to wherever invaders are left
+        for _ in 0..damage {
+            let mut lands = vec![self.land_index];
+            if spreads {
+                lands.extend(game.get_land_desc(self.land_index)?.adjacent.iter().cloned());
+            }
+            lands.retain(|l| game.table.lands[*l as usize].is_in_play && !game.table.lands[*l as usize].invaders.is_empty());
+
+            game.do_effect(ChooseLandDecision{lands,
+                effect: |game, land_index| game.do_effect(DoDamageToInvadersDecision{land_index, damage: 1})})?;
+        }
+
         Ok(())
     }
 
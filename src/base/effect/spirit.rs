@@ -25,3 +25,90 @@ impl Effect for MayPlaySlowsAsFastsEffect {
     fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
     fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
 }
+
+
+#[derive(Clone)]
+pub struct ExtraRangeEffect {
+    pub spirit_index: u8,
+    pub range: u8,
+}
+
+impl Effect for ExtraRangeEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("{} gets +{} range with all powers.", self.spirit_index, self.range));
+
+        game.get_spirit_mut(self.spirit_index)?.extra_range += self.range;
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+#[derive(Clone)]
+pub struct MayRepeatPowersEffect {
+    pub spirit_index: u8,
+    pub amount: u8,
+}
+
+impl Effect for MayRepeatPowersEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("{} may repeat {} power cards.", self.spirit_index, self.amount));
+
+        game.get_spirit_mut(self.spirit_index)?.may_repeat_powers += self.amount;
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+// Two spirits may target powers from each other's presence this turn.
+#[derive(Clone)]
+pub struct EntwinePresenceEffect {
+    pub spirit_index: u8,
+    pub other_spirit_index: u8,
+}
+
+impl Effect for EntwinePresenceEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("{} and {} may target powers from each other's presence.", self.spirit_index, self.other_spirit_index));
+
+        game.get_spirit_mut(self.spirit_index)?.entwined_with[self.other_spirit_index] = true;
+        game.get_spirit_mut(self.other_spirit_index)?.entwined_with[self.spirit_index] = true;
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
+
+
+// Dahan in the spirit's lands have more health this turn, or ignore damage and destruction.
+#[derive(Clone)]
+pub struct ProtectDahanEffect {
+    pub spirit_index: u8,
+    pub health: u8,
+    pub ignore_damage: bool,
+}
+
+impl Effect for ProtectDahanEffect {
+    fn apply_effect(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("dahan in the lands of {} get +{} health{}.", self.spirit_index, self.health,
+            if self.ignore_damage { " and ignore damage and destruction" } else { "" }));
+
+        let spirit = game.get_spirit_mut(self.spirit_index)?;
+        spirit.dahan_health_bonus += self.health;
+        spirit.dahan_ignore_damage |= self.ignore_damage;
+
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Effect> { Box::new(self.clone()) }
+    fn as_any(&self) -> Box<dyn Any> { Box::new(self.clone()) }
+}
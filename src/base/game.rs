@@ -4,7 +4,8 @@ use std::{
     fmt,
     sync::{Arc},
     clone::Clone,
-    collections::VecDeque,
+    collections::{VecDeque, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
 };
 
 use super::*;
@@ -18,6 +19,9 @@ pub struct GameDescription {
 
     pub fear: Vec<Arc<FearCardDescription>>,
     pub powers: Vec<Arc<PowerCardDescription>>,
+    pub blight: Vec<Arc<BlightCardDescription>>,
+    // empty without the events variant
+    pub events: Vec<Arc<EventCardDescription>>,
 }
 
 impl GameDescription {
@@ -26,8 +30,11 @@ impl GameDescription {
         adversary: Box<dyn AdversaryDescription>,
         spirits: Vec<Box<dyn SpiritDescription>>,
         table: Box<TableDescription>,
+        events: bool,
     ) -> GameDescription {
         let fear_cards = join_fear_cards(&content);
+        let blight_cards = join_blight_cards(&content);
+        let event_cards = if events { join_event_cards(&content) } else { Vec::new() };
         let mut power_cards = join_power_cards(&content);
 
         for (index, spirit) in spirits.iter().enumerate() {
@@ -42,6 +49,8 @@ impl GameDescription {
 
             fear: fear_cards.into_iter().map(Arc::from).collect(),
             powers: power_cards.into_iter().map(Arc::from).collect(),
+            blight: blight_cards.into_iter().map(Arc::from).collect(),
+            events: event_cards.into_iter().map(Arc::from).collect(),
         }
     }
 }
@@ -55,6 +64,11 @@ pub struct GameState {
     pub rng: Box<dyn DeterministicRng>,
 
     pub enable_logging: bool,
+    // Which decks fate (the solver) draws from instead of the rng.
+    pub fate: FateOptions,
+    // With the power progression variant, gaining a power card gives the spirit the next card of
+    // its progression instead of a draft.
+    pub power_progression: Option<ProgressionUnderflow>,
 
     pub step: GameStep,
     pub next_step: GameStep,
@@ -74,8 +88,11 @@ pub struct GameState {
     pub fear_generated: u8,
     pub fear_generated_total: u8,
 
+    pub blight: BlightDeck,
     pub blight_remaining: u8,
 
+    pub events: EventDeck,
+
     pub spirits: Vec<SpiritState>,
 
     pub minor_powers: PowerDeck,
@@ -90,6 +107,8 @@ impl GameState {
             rng,
 
             enable_logging: false,
+            fate: FateOptions::none(),
+            power_progression: None,
 
             step: GameStep::Init,
             next_step: GameStep::Init,
@@ -109,7 +128,10 @@ impl GameState {
             fear_generated: 0,
             fear_generated_total: 0,
 
-            blight_remaining: 5,
+            blight: BlightDeck::new(),
+            blight_remaining: 0,
+
+            events: EventDeck::new(),
 
             spirits: Vec::new(),
 
@@ -190,6 +212,16 @@ impl GameState {
             .ok_or(StepFailure::InternalError("no current power usage".to_string()))
     }
 
+    // The extra health of dahan in a land this turn, and whether they ignore damage and
+    // destruction there, from the spirits with presence in it.
+    pub fn get_dahan_protection(&self, land_index: u8) -> Result<(u8, bool), StepFailure> {
+        let land = self.get_land(land_index)?;
+        Ok(self.spirits.iter().enumerate()
+            .filter(|(index, _)| land.presence[*index as u8] > 0)
+            .fold((0, false), |(health, ignore), (_, spirit)|
+                (health + spirit.dahan_health_bonus, ignore || spirit.dahan_ignore_damage)))
+    }
+
 
     pub fn consume_choice(&mut self) -> Result<DecisionChoice, StepFailure> {
         match self.choices.pop_front() {
@@ -213,6 +245,34 @@ impl GameState {
         self.do_effect_box(effect.box_clone())
     }
 
+    // In fate mode, decides the card on top of the deck before it is drawn.
+    pub fn fate_draw(&mut self, deck: FateDeck) -> Result<(), StepFailure> {
+        if self.fate.is_fated(deck) {
+            self.do_effect(FateDrawDecision { deck })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn draw_powers_into_pending(&mut self, kind: PowerCardKind, count: usize) -> Result<(), StepFailure> {
+        let fate_deck = match kind {
+            PowerCardKind::Minor => FateDeck::Minor,
+            PowerCardKind::Major => FateDeck::Major,
+            PowerCardKind::Spirit(_) => return Err(StepFailure::InternalError("spirit powers are not drawn".to_string())),
+        };
+
+        // fated draws are decided one card at a time
+        let (draws, draw_count) = if self.fate.is_fated(fate_deck) { (count, 1) } else { (1, count) };
+        for _ in 0..draws {
+            self.fate_draw(fate_deck)?;
+
+            let deck = if kind == PowerCardKind::Minor { &mut self.minor_powers } else { &mut self.major_powers };
+            deck.draw_into_pending(self.rng.get_rng(), draw_count);
+        }
+
+        Ok(())
+    }
+
     pub fn do_defeat(&mut self, defeat_reason: &str) -> Result<(), StepFailure> {
         self.game_over_reason = Some(defeat_reason.to_string());
         self.step = GameStep::Defeat;
@@ -227,9 +287,90 @@ impl GameState {
         Err(StepFailure::GameOverVictory)
     }
 
+    /*
+        A hash of the position, equal for states the rest of the game can't tell apart: everything
+        that changes during play, with piles whose order doesn't matter (hands, played cards,
+        discards, pieces in a land) sorted. Only meaningful between steps, the effect stack and
+        choices are ignored.
+
+        The rng position is part of the position, the same board with a different future is a
+        different state.
+    */
+    pub fn canonical_hash(&self) -> u64 {
+        fn sorted<T: Ord>(mut v: Vec<T>) -> Vec<T> { v.sort(); v }
+        // indices are within each kind of card, spirit cards, minors and majors can all be in a hand
+        fn power_cards(cards: &[PowerCard]) -> Vec<(u8, usize)> {
+            cards.iter()
+                .map(|c| match c.desc.kind {
+                    PowerCardKind::Minor => (0, c.index),
+                    PowerCardKind::Major => (1, c.index),
+                    PowerCardKind::Spirit(spirit) => (2 + spirit, c.index),
+                })
+                .collect()
+        }
+        fn fear_cards(cards: &[FearCard]) -> Vec<usize> { cards.iter().map(|c| c.index).collect() }
+
+        let mut hasher = DefaultHasher::new();
+
+        self.step.hash(&mut hasher);
+        self.rng.get_position().hash(&mut hasher);
+
+        for land in self.table.lands.iter() {
+            land.is_in_play.hash(&mut hasher);
+            land.tokens.hash(&mut hasher);
+            land.presence.hash(&mut hasher);
+            sorted(land.invaders.iter().map(|i| (i.kind as u8, i.health_cur, i.health_max, i.attack)).collect()).hash(&mut hasher);
+            sorted(land.dahan.iter().map(|d| (d.health_cur, d.health_max, d.attack)).collect()).hash(&mut hasher);
+            land.defense.hash(&mut hasher);
+            land.fear_generated_here_this_round.hash(&mut hasher);
+            (land.skip_explore, land.skip_build, land.skip_ravage).hash(&mut hasher);
+            (land.vengeance, land.vengeance_spreads).hash(&mut hasher);
+        }
+
+        self.invader.sequence.hash(&mut hasher);
+        self.invader.draw.hash(&mut hasher);
+        self.invader.pending.hash(&mut hasher);
+        self.invader.discard.hash(&mut hasher);
+
+        fear_cards(&self.fear.draw).hash(&mut hasher);
+        fear_cards(&self.fear.pending).hash(&mut hasher);
+        sorted(fear_cards(&self.fear.discard)).hash(&mut hasher);
+        sorted(fear_cards(&self.fear.unused)).hash(&mut hasher);
+        self.fear.terror_thresholds().hash(&mut hasher);
+        self.fear.resolved.hash(&mut hasher);
+        (self.fear_pool, self.fear_generated, self.fear_generated_total).hash(&mut hasher);
+
+        self.blight_remaining.hash(&mut hasher);
+        self.blight.draw.iter().map(|c| c.index).collect::<Vec<_>>().hash(&mut hasher);
+        (self.blight.card.as_ref().map(|c| c.index), self.blight.flipped).hash(&mut hasher);
+
+        for pile in [&self.events.draw, &self.events.pending] {
+            pile.iter().map(|c| c.index).collect::<Vec<_>>().hash(&mut hasher);
+        }
+        sorted(self.events.discard.iter().map(|c| c.index).collect()).hash(&mut hasher);
+
+        for spirit in self.spirits.iter() {
+            spirit.presence.hash(&mut hasher);
+            (spirit.energy, spirit.plays, spirit.may_play_slows_as_fasts, spirit.extra_range, spirit.may_repeat_powers).hash(&mut hasher);
+            (spirit.entwined_with, spirit.dahan_health_bonus, spirit.dahan_ignore_damage).hash(&mut hasher);
+            spirit.elements.hash(&mut hasher);
+            sorted(power_cards(&spirit.deck.hand)).hash(&mut hasher);
+            sorted(power_cards(&spirit.deck.pending)).hash(&mut hasher);
+            sorted(power_cards(&spirit.deck.discard)).hash(&mut hasher);
+            sorted(power_cards(&spirit.deck.forgotten)).hash(&mut hasher);
+        }
+
+        for deck in [&self.minor_powers, &self.major_powers] {
+            power_cards(&deck.draw).hash(&mut hasher);
+            power_cards(&deck.pending).hash(&mut hasher);
+            sorted(power_cards(&deck.discard)).hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
     pub fn score_game(&self) -> i16 {
-        // TODO: calculate this
-        let difficulty: i16 = 0;
+        let difficulty: i16 = self.desc.adversary.difficulty() as i16;
 
         let mut score = 0;
 
@@ -255,7 +396,33 @@ impl GameState {
     }
 
     pub fn step_to_next_event(&mut self) -> Result<InvaderStep, StepFailure> {
-        Ok(self.step_to_next_fear()?)
+        let (turn, next) = match &self.step {
+            GameStep::Turn(turn, TurnStep::Invader(InvaderStep::Event(card, part))) => (*turn, Some((*card, *part + 1))),
+            GameStep::Turn(turn, _) => (*turn, None),
+            _ => (0, None),
+        };
+
+        let (next_card, next_part) = match next {
+            Some(next) => next,
+            None => {
+                // No event is drawn on the first turn
+                if !self.events.is_in_play() || turn == 0 {
+                    return self.step_to_next_fear();
+                }
+
+                self.events.draw_into_pending(self.rng.get_rng());
+                (0, 0)
+            },
+        };
+
+        if next_part < EventCardDescription::PARTS {
+            Ok(InvaderStep::Event(next_card, next_part))
+        } else if ((next_card + 1) as usize) < self.events.pending.len() {
+            Ok(InvaderStep::Event(next_card + 1, 0))
+        } else {
+            self.events.discard_pending();
+            self.step_to_next_fear()
+        }
     }
     pub fn step_to_next_fear(&mut self) -> Result<InvaderStep, StepFailure> {
         let next_card = match &self.step {
@@ -267,6 +434,9 @@ impl GameState {
             Ok(InvaderStep::FearEffect(next_card))
         }
         else {
+            // the earned cards are resolved
+            self.fear.advance();
+
             Ok(self.step_to_next_invader()?)
         }
     }
@@ -297,6 +467,7 @@ impl GameState {
             if self.invader.draw.len() == 0 {
                 self.do_defeat("Invader deck empty!")?;
             } else {
+                self.fate_draw(FateDeck::Invader)?;
                 self.invader.draw_into_pending();
             }
 
@@ -316,14 +487,19 @@ impl GameState {
 
         self.next_step = match step {
             GameStep::Init => {
-                let invaders = generate_invader_deck();
+                let invader_rng = if self.fate.invader { None } else { Some(self.rng.get_rng()) };
+                let invaders = stack_invader_deck(&desc.adversary.invader_deck_stages(), &generate_invader_deck(), invader_rng)
+                    .map_err(StepFailure::InternalError)?;
                 self.invader.set_state(invaders, Vec::new(), self.desc.adversary.invader_steps());
-                self.invader.shuffle_draw(&mut self.rng.get_rng());
 
-                self.fear.init(&desc.fear, &mut self.rng.get_rng(), desc.adversary.fear_cards());
+                let fear_rng = if self.fate.fear { None } else { Some(self.rng.get_rng()) };
+                self.fear.init(&desc.fear, fear_rng, desc.adversary.fear_cards());
                 self.fear_pool = 4 * desc.spirits.len() as u8;
 
-                desc.adversary.setup(self);
+                for invader in self.table.lands.iter_mut().flat_map(|l| l.invaders.iter_mut()) {
+                    desc.adversary.modify_invader(invader);
+                }
+                desc.adversary.setup(self)?;
 
                 for spirit_desc in desc.spirits.iter() {
                     self.spirits.push(SpiritState::new(spirit_desc));
@@ -334,13 +510,20 @@ impl GameState {
                         .filter(|pcd| pcd.kind == PowerCardKind::Minor)
                         .map(|pcd| Arc::clone(pcd))
                         .collect(),
-                    &mut self.rng.get_rng());
+                    if self.fate.minor { None } else { Some(self.rng.get_rng()) });
                 self.major_powers.init(
                     self.desc.powers.iter()
                         .filter(|pcd| pcd.kind == PowerCardKind::Major)
                         .map(|pcd| Arc::clone(pcd))
                         .collect(),
-                    &mut self.rng.get_rng());
+                    if self.fate.major { None } else { Some(self.rng.get_rng()) });
+
+                self.blight.init(&desc.blight, if self.fate.blight { None } else { Some(self.rng.get_rng()) });
+                self.fate_draw(FateDeck::Blight)?;
+                self.blight.draw_into_play();
+                self.blight_remaining = BlightDeck::HEALTHY_BLIGHT_PER_PLAYER * desc.spirits.len() as u8 + 1;
+
+                self.events.init(&desc.events, self.rng.get_rng());
 
                 GameStep::SetupSpirit
             }
@@ -365,6 +548,7 @@ impl GameState {
             }
             GameStep::SetupExplore => {
                 // The initial explore
+                self.fate_draw(FateDeck::Invader)?;
                 self.invader.draw_into_pending();
 
                 let &card = self.invader.pending.back().unwrap().first().unwrap();
@@ -377,7 +561,7 @@ impl GameState {
 
                 self.invader.advance();
 
-                // TODO: Post setup-explore adversary setup?
+                desc.adversary.after_setup_explore(self)?;
 
                 GameStep::Turn(0, TurnStep::Spirit(SpiritStep::Growth))
             }
@@ -420,18 +604,32 @@ impl GameState {
                         }
                     }
                     TurnStep::FastPower => {
-                        self.do_effect(DoCardPlaysDecision{power_speed: PowerSpeed::Fast})?;
+                        self.do_effect(DoCardPlaysDecision::new(PowerSpeed::Fast))?;
+                        self.do_effect(DoInnatePowersEffect{power_speed: PowerSpeed::Fast})?;
 
                         GameStep::Turn(turn, TurnStep::Invader(InvaderStep::BlightedIsland))
                     }
                     TurnStep::Invader(inv_step) => {
                         match &inv_step {
                             InvaderStep::BlightedIsland => {
-                                // TODO: blight
+                                if self.blight.flipped {
+                                    let card = self.blight.card.clone().unwrap();
+                                    if let Some(effect) = card.desc.each_invader_phase.clone() {
+                                        self.log_effect(format_args!("Blight Card: {}", card.desc.name));
+                                        self.do_effect_box(effect)?;
+                                    }
+                                }
+
                                 GameStep::Turn(turn, TurnStep::Invader(self.step_to_next_event()?))
                             }
-                            InvaderStep::Event(_event_card, _event_part) => {
-                                // TODO: events
+                            InvaderStep::Event(event_card, event_part) => {
+                                let card = self.events.pending[*event_card as usize].clone();
+
+                                if let Some(effect) = card.desc.part(*event_part) {
+                                    self.log_effect(format_args!("Event Card ({}): {}", EventCardDescription::part_name(*event_part), card.desc.name));
+                                    self.do_effect_box(effect)?;
+                                }
+
                                 GameStep::Turn(turn, TurnStep::Invader(self.step_to_next_event()?))
                             }
                             InvaderStep::FearEffect(fear_card) => {
@@ -479,23 +677,30 @@ impl GameState {
                                     }
                                 }
 
+                                desc.adversary.after_invader_card(self, inv_kind, card)?;
+
                                 GameStep::Turn(turn, TurnStep::Invader(self.step_to_next_invader()?))
                             }
                             InvaderStep::InvaderAdvance => {
                                 self.invader.advance();
+                                desc.adversary.after_invader_advance(self)?;
 
                                 GameStep::Turn(turn, TurnStep::SlowPower)
                             }
                         }
                     }
                     TurnStep::SlowPower => {
-                        self.do_effect(DoCardPlaysDecision{power_speed: PowerSpeed::Slow})?;
+                        self.do_effect(DoCardPlaysDecision::new(PowerSpeed::Slow))?;
+                        self.do_effect(DoInnatePowersEffect{power_speed: PowerSpeed::Slow})?;
 
                         GameStep::Turn(turn, TurnStep::TimePasses)
                     }
                     TurnStep::TimePasses => {
                         for land in self.table.lands.iter_mut() {
                             land.time_passes();
+                            for invader in land.invaders.iter_mut() {
+                                desc.adversary.modify_invader(invader);
+                            }
                         }
 
                         for spirit in self.spirits.iter_mut() {
@@ -520,6 +725,10 @@ impl GameState {
             return Err(StepFailure::InternalError("There are unconsumed choices!".to_string()));
         }
 
+        if let Some(reason) = desc.adversary.check_loss(self) {
+            self.do_defeat(reason)?;
+        }
+
         Ok(())
     }
 
@@ -529,3 +738,45 @@ impl GameState {
         Ok(())
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::RunConfig, testing::set_up};
+
+    #[test]
+    fn canonical_hash_of_a_new_game() {
+        let config = RunConfig::parse(include_str!("../../configurations/sample.yaml")).unwrap();
+        let game = config.make_state().unwrap();
+
+        assert_eq!(game.canonical_hash(), game.clone().canonical_hash());
+    }
+
+    #[test]
+    fn events_resolve_part_by_part() {
+        let config = RunConfig::parse("seed: test\ncontent: [core, test-events]\ngame:\n  spirits: [river]\n  boards: [A]\n  variants:\n    events: yes\n").unwrap();
+        let mut game = config.make_state().unwrap();
+        set_up(&mut game);
+        let fear = game.fear_generated_total;
+        let dahan = game.table.lands[1].dahan.len();
+
+        // the invader phase of the second turn, none is drawn on the first
+        game.step = GameStep::Turn(1, TurnStep::Invader(InvaderStep::BlightedIsland));
+        let mut steps = Vec::new();
+        loop {
+            game.step().unwrap();
+            game.advance().unwrap();
+            match game.step {
+                GameStep::Turn(_, TurnStep::Invader(InvaderStep::Event(card, part))) => steps.push((card, part)),
+                _ => break,
+            }
+        }
+
+        assert_eq!(steps, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(game.fear_generated_total, fear + 1);
+        assert_eq!(game.table.lands[1].dahan.len(), dahan + 1);
+        assert!(game.events.pending.is_empty());
+        assert_eq!(game.events.discard.len(), 1);
+    }
+}
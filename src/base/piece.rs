@@ -2,6 +2,7 @@
 
 use std::{
     fmt,
+    str::FromStr,
 };
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
@@ -27,7 +28,7 @@ impl fmt::Display for TokenKind {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Hash, Copy, Clone)]
 pub struct TokenMap<T>( [T; 6] );
 
 impl<T> TokenMap<T> {
@@ -98,7 +99,7 @@ impl fmt::Display for InvaderKind {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Hash, Copy, Clone)]
 pub struct InvaderMap<T>( [T; 3] );
 
 impl<T> InvaderMap<T> 
@@ -196,3 +197,24 @@ impl fmt::Display for PieceKind {
        }
     }
 }
+
+// The inverse of the Display impl.
+impl FromStr for PieceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Blight" => Ok(PieceKind::Token(TokenKind::Blight)),
+            "Beast" => Ok(PieceKind::Token(TokenKind::Beast)),
+            "Wilds" => Ok(PieceKind::Token(TokenKind::Wilds)),
+            "Disease" => Ok(PieceKind::Token(TokenKind::Disease)),
+            "Strife" => Ok(PieceKind::Token(TokenKind::Strife)),
+            "Badlands" => Ok(PieceKind::Token(TokenKind::Badlands)),
+            "Explorer" => Ok(PieceKind::Invader(InvaderKind::Explorer)),
+            "Town" => Ok(PieceKind::Invader(InvaderKind::Town)),
+            "City" => Ok(PieceKind::Invader(InvaderKind::City)),
+            "Dahan" => Ok(PieceKind::Dahan),
+            _ => Err(format!("unknown piece kind `{}`", s)),
+        }
+    }
+}
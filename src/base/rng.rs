@@ -4,35 +4,70 @@ use std::{
 
 use rand::prelude::*;
 use rand_chacha::{ChaChaRng};
+use crypto::digest::Digest;
+use crypto::sha1::Sha1;
 
 
 /* 
     Our RNG needs to be deterministic and copyable.
 */
 
+// Seeds are human readable strings, hashed down into the seed the rng wants.
+pub fn make_seed(seed: &str) -> [u8; 32] {
+    let mut result: [u8; 32] = [0; 32];
+    let mut hasher = Sha1::new();
+    hasher.input_str(seed);
+    hasher.result(&mut result);
+
+    result
+}
+
 pub trait DeterministicRng : Send + Sync {
     fn get_rng<'a>(&'a mut self) -> &'a mut dyn RngCore;
     fn box_clone(&self) -> Box<dyn DeterministicRng>;
+
+    // How far into its stream the rng is, with the seed this is enough to recreate it.
+    fn get_position(&self) -> u128;
+    fn set_position(&mut self, position: u128);
 }
 
+// `ChaChaRng::get_word_pos` underflows before the rng has generated its first block, so until
+// the rng is handed out (or positioned) we know we are at the start of the stream.
 pub struct DeterministicChaCha {
-    rng: ChaChaRng
+    rng: ChaChaRng,
+    started: bool,
 }
 
 impl DeterministicChaCha {
     pub fn new(rng: ChaChaRng) -> Self {
         DeterministicChaCha {
-            rng
+            rng,
+            started: false,
         }
     }
 }
 
 impl DeterministicRng for DeterministicChaCha {
     fn get_rng<'a>(&'a mut self) -> &'a mut dyn RngCore {
+        if !self.started {
+            // generates the first block, the stream is unchanged
+            self.set_position(0);
+        }
         &mut self.rng
     }
     fn box_clone(&self) -> Box<dyn DeterministicRng> {
-        Box::new(DeterministicChaCha::new(self.rng.clone()))
+        Box::new(DeterministicChaCha {
+            rng: self.rng.clone(),
+            started: self.started,
+        })
+    }
+
+    fn get_position(&self) -> u128 {
+        if self.started { self.rng.get_word_pos() } else { 0 }
+    }
+    fn set_position(&mut self, position: u128) {
+        self.rng.set_word_pos(position);
+        self.started = true;
     }
 }
 
@@ -41,3 +76,22 @@ impl Clone for Box<dyn DeterministicRng> {
         self.box_clone()
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_an_unused_rng_is_the_start() {
+        let mut rng = DeterministicChaCha::new(ChaChaRng::from_seed(make_seed("test")));
+        assert_eq!(rng.get_position(), 0);
+
+        let first = rng.get_rng().next_u32();
+        assert_eq!(rng.get_position(), 1);
+
+        let mut replayed = DeterministicChaCha::new(ChaChaRng::from_seed(make_seed("test")));
+        replayed.set_position(0);
+        assert_eq!(replayed.get_rng().next_u32(), first);
+    }
+}
@@ -6,13 +6,16 @@ use std::{
 };
 
 use super::{
-    deck::{PowerCardDescription, SpiritPowerDeck},
+    deck::{InnatePowerDescription, PowerCardDescription, SpiritPowerDeck},
     step::{StepFailure},
     game::{GameState},
+    board::{LandDescription},
+    concept::{LandKind},
+    effect::{TrackIncomeEffect},
 };
 
 
-#[derive(Copy, Clone)]
+#[derive(Hash, Copy, Clone)]
 pub struct SpiritMap<T>( pub [T; 6] );
 
 impl<T> SpiritMap<T> {
@@ -66,7 +69,7 @@ impl fmt::Display for Element {
 }
 
 
-#[derive(Copy, Clone)]
+#[derive(Hash, Copy, Clone)]
 pub struct ElementMap<T>( pub [T; 8] );
 
 impl<T> ElementMap<T>
@@ -99,6 +102,22 @@ impl ElementMap<bool> {
     }
 }
 
+impl ElementMap<u8> {
+    // Adds the elements of a card.
+    pub fn gain(&mut self, elements: &ElementMap<bool>) {
+        for (count, has) in self.0.iter_mut().zip(elements.0.iter()) {
+            if *has {
+                *count += 1;
+            }
+        }
+    }
+
+    // Whether there are at least as many of each element as the threshold asks for.
+    pub fn meets(&self, threshold: &ElementMap<u8>) -> bool {
+        self.0.iter().zip(threshold.0.iter()).all(|(count, needed)| count >= needed)
+    }
+}
+
 impl fmt::Display for ElementMap<bool> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.0[Element::Sun as usize] { write!(f, "{}", Element::Sun)?; } else { write!(f, " ")?; }
@@ -134,13 +153,75 @@ pub trait SpiritDescription : Send + Sync {
 
     fn get_power_cards(&self, spirit_index: u8) -> Vec<PowerCardDescription>;
     fn get_power_progression(&self) -> Vec<&'static str>;
+    fn get_innate_powers(&self) -> Vec<InnatePowerDescription> {
+        Vec::new()
+    }
 
     fn do_setup(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure>;
 
-    fn may_place_presence(&self, state: &[PresenceState; 13], presence_index: usize) -> Result<bool, StepFailure>;
+    // The presence tracks of the spirit, income and which presence may be placed follow from them.
+    fn get_presence_tracks(&self) -> Vec<PresenceTrack>;
+
+    // Presence on a track may only be placed from the left most covered spot of that track.
+    fn may_place_presence(&self, state: &[PresenceState; 13], presence_index: usize) -> Result<bool, StepFailure> {
+        match state[presence_index] {
+            PresenceState::OnTrack(track_loc) => {
+                let track = self.get_presence_tracks().into_iter()
+                    .find(|t| t.covers(track_loc))
+                    .ok_or(StepFailure::InternalError("presence not on a track".to_string()))?;
+
+                if track_loc == track.first {
+                    Ok(true)
+                } else {
+                    Ok(state[(track_loc - 1) as usize] != PresenceState::OnTrack(track_loc - 1))
+                }
+            },
+            _ => Ok(true)
+        }
+    }
+    // The lands presence may be added to, spirits that can live in the ocean override this.
+    fn may_place_presence_in(&self, land: &LandDescription) -> bool {
+        land.kind != LandKind::Ocean
+    }
 
     fn do_growth(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure>;
-    fn do_income(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure>;
+    fn do_income(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure> {
+        game.do_effect(TrackIncomeEffect{ spirit_index: spirit_index as u8 })
+    }
+}
+
+// What a spot on a presence track gives once it is uncovered. Energy and card plays are the right
+// most uncovered value, everything else is gained each income for every uncovered spot.
+#[derive(Copy, Clone)]
+pub enum TrackSlot {
+    Energy(u8),
+    Plays(u8),
+    Element(Element),
+    ExtraEnergy(u8),
+    ReclaimOne,
+}
+
+// A presence track, `slots[0]` is printed on the panel and always uncovered, `slots[i]` is uncovered
+// once the presence on track spot `first + i - 1` has left it.
+#[derive(Clone)]
+pub struct PresenceTrack {
+    pub first: u8,
+    pub slots: Vec<TrackSlot>,
+}
+
+impl PresenceTrack {
+    pub fn covers(&self, track_loc: u8) -> bool {
+        track_loc >= self.first && ((track_loc - self.first) as usize) < self.slots.len() - 1
+    }
+
+    pub fn uncovered<'a>(&'a self, state: &'a [PresenceState; 13]) -> impl Iterator<Item = &'a TrackSlot> {
+        self.slots.iter().enumerate()
+            .filter(move |(i, _)| {
+                let track_loc = self.first + *i as u8;
+                *i == 0 || state[(track_loc - 1) as usize] != PresenceState::OnTrack(track_loc - 1)
+            })
+            .map(|(_, slot)| slot)
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
@@ -157,7 +238,7 @@ impl fmt::Display for PresenceState {
             // removed emojis due to not being fixed width
             PresenceState::OnBoard(spot) => write!(f, "(board: {})", spot),
             PresenceState::OnTrack(spot) => write!(f, "(track: {})", spot),
-            PresenceState::Destroyed => write!(f, "(destroyed"),
+            PresenceState::Destroyed => write!(f, "(destroyed)"),
             PresenceState::RemovedFromGame => write!(f, "(removed)"),
        }
     }
@@ -177,6 +258,13 @@ pub struct SpiritState {
 
     // specific effects:
     pub may_play_slows_as_fasts: u8,
+    pub extra_range: u8,
+    pub may_repeat_powers: u8,
+    // spirits whose presence this one may target powers from
+    pub entwined_with: SpiritMap<bool>,
+    // dahan in the spirit's lands have more health, or ignore damage and destruction
+    pub dahan_health_bonus: u8,
+    pub dahan_ignore_damage: bool,
 }
 
 impl SpiritState {
@@ -192,6 +280,11 @@ impl SpiritState {
             elements: ElementMap::new(0),
 
             may_play_slows_as_fasts: 0,
+            extra_range: 0,
+            may_repeat_powers: 0,
+            entwined_with: SpiritMap::new(|| false),
+            dahan_health_bonus: 0,
+            dahan_ignore_damage: false,
         }
     }
 
@@ -206,5 +299,10 @@ impl SpiritState {
         self.elements.set_all(0);
 
         self.may_play_slows_as_fasts = 0;
+        self.extra_range = 0;
+        self.may_repeat_powers = 0;
+        self.entwined_with = SpiritMap::new(|| false);
+        self.dahan_health_bonus = 0;
+        self.dahan_ignore_damage = false;
     }
 }
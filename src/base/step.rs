@@ -88,7 +88,7 @@ impl fmt::Display for GameStep {
 
 
 // The state of the game state is invalid
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub enum StepFailure {
     InternalError(String),
     RulesViolation(String),
@@ -0,0 +1,112 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::Write,
+    time::Instant,
+};
+
+use crate::config::{RunConfig, Choice, SolverEngine};
+use crate::solve;
+
+
+/*
+    A batch runs every game described by a configuration (see `RunConfig::expand`) and reports a
+    row of statistics for each of them.
+*/
+
+pub struct BatchRow {
+    pub spirits: Vec<String>,
+    pub boards: Vec<String>,
+    pub adversary: String,
+    pub difficulty: u8,
+
+    pub stats: solve::BasicStatistics,
+    pub elapsed: f64,
+}
+
+impl BatchRow {
+    pub fn header() -> &'static str {
+        "spirits,boards,adversary,difficulty,victories,defeats,errors,min_score,max_score,elapsed"
+    }
+
+    pub fn to_csv(&self) -> String {
+        format!("\"{}\",\"{}\",\"{}\",{},{},{},{},{},{},{:.2}",
+            self.spirits.join(" "), self.boards.join(" "), self.adversary, self.difficulty,
+            self.stats.victories, self.stats.defeats, self.stats.errors,
+            self.stats.min_score, self.stats.max_score, self.elapsed)
+    }
+}
+
+fn choice_names(choices: &[Choice<String>]) -> Vec<String> {
+    choices.iter().map(|c| c.to_string()).collect()
+}
+
+pub fn run_batch(config: &RunConfig, threads: usize, output: Option<&str>) -> Result<Vec<BatchRow>, Box<dyn Error>> {
+    let options = match &config.solver.engine {
+        SolverEngine::Tree(options) => options,
+        SolverEngine::Mcts(_) => bail!("Batches run the tree engine only, the mcts engine answers a single game."),
+    };
+
+    let runs = config.expand()?;
+    if runs.is_empty() {
+        bail!("Configuration describes no games (are there enough distinct spirits and boards?).");
+    }
+
+    // Fail before running anything if a combination can't be built.
+    for run in runs.iter() {
+        run.make_description()?;
+        run.make_strategy()?;
+    }
+
+    let mut file = match output {
+        Some(path) => {
+            let mut file = File::create(path)
+                .map_err(|e| format!("Could not create `{}`: {}", path, e))?;
+            writeln!(file, "{}", BatchRow::header())?;
+            Some(file)
+        }
+        None => None,
+    };
+
+    let mut rows = Vec::new();
+    for (i, run) in runs.iter().enumerate() {
+        println!("=== {}/{}: spirits: {}  boards: {}  adversary: {} ({})", i + 1, runs.len(),
+            choice_names(&run.game.spirits).join(" "), choice_names(&run.game.boards).join(" "),
+            run.game.adversary.name, run.game.adversary.difficulty);
+
+        let state = run.make_state()?;
+        let strategy = run.make_strategy()?;
+
+        let start = Instant::now();
+        let mut solver = solve::SolveEngine::new(&state, strategy, run.make_evaluator(), options.clone(), run.solver.transpositions);
+        let stats = solver.main(threads)?;
+        let elapsed = start.elapsed().as_secs_f64();
+
+        let row = BatchRow {
+            spirits: choice_names(&run.game.spirits),
+            boards: choice_names(&run.game.boards),
+            adversary: run.game.adversary.name.to_string(),
+            difficulty: match run.game.adversary.difficulty {
+                Choice::One(difficulty) => difficulty,
+                Choice::All => unreachable!("expanded configurations have a single difficulty"),
+            },
+            stats,
+            elapsed,
+        };
+
+        // Written as we go so a long batch can be inspected (or interrupted) midway.
+        if let Some(file) = file.as_mut() {
+            writeln!(file, "{}", row.to_csv())?;
+            file.flush()?;
+        }
+
+        rows.push(row);
+    }
+
+    println!("{}", BatchRow::header());
+    for row in rows.iter() {
+        println!("{}", row.to_csv());
+    }
+
+    Ok(rows)
+}
@@ -0,0 +1,893 @@
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    fs,
+    sync::{Arc},
+};
+
+use rand::prelude::*;
+use rand_chacha::{ChaChaRng};
+use yaml_rust::{Yaml, YamlLoader, yaml};
+
+use crate::base;
+use crate::core::{CoreContent, AdversaryDescriptionBrandenburgPrussia, AdversaryDescriptionEngland, AdversaryDescriptionSweden};
+use crate::solve;
+
+
+/*
+    Run configurations describe a whole experiment in a file, see `configurations/` for examples.
+
+    A configuration has four sections:
+
+    * `seed`: the seed string for the random system (`off` uses the default seed).
+    * `content`: the content packs to load.
+    * `game`: the spirits, boards, adversary and variants of the game.
+    * `solver`: the search engine (`tree` or `mcts`), the weights of the evaluator used to score
+      unfinished games and a layered list of strategies the engine uses.
+
+    Spirits, boards, the adversary and its difficulty may be `all`, which makes the configuration
+    a sweep: `expand` turns it into every combination, each of which is a single runnable game.
+*/
+
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Choice<T> {
+    All,
+    One(T),
+}
+
+impl<T: fmt::Display> fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Choice::All => write!(f, "all"),
+            Choice::One(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AdversaryConfig {
+    pub name: Choice<String>,
+    pub difficulty: Choice<u8>,
+}
+
+#[derive(Clone)]
+pub struct GameConfig {
+    pub spirits: Vec<Choice<String>>,
+    pub boards: Vec<Choice<String>>,
+    pub adversary: AdversaryConfig,
+
+    pub events: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum PlayOrder {
+    Simple,
+    Rng,
+}
+
+#[derive(Clone)]
+pub enum StrategyLayer {
+    SetupPowerProgression {
+        on_underflow: base::ProgressionUnderflow,
+    },
+    SetupBranch {
+        take: u8,
+        fear: bool,
+        invader: bool,
+        blight: bool,
+        minor: bool,
+        major: bool,
+        events: bool,
+    },
+    PlayBranch {
+        take: u8,
+        order: PlayOrder,
+    },
+}
+
+impl StrategyLayer {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            StrategyLayer::SetupPowerProgression{..} => "setup-power-progression",
+            StrategyLayer::SetupBranch{..} => "setup-branch",
+            StrategyLayer::PlayBranch{..} => "play-branch",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum SolverEngine {
+    Tree(solve::TreeOptions),
+    Mcts(solve::MctsOptions),
+}
+
+// How many positions the tree engine remembers (see `solve::TranspositionTable`) by default.
+pub const DEFAULT_TRANSPOSITIONS: usize = 1 << 20;
+
+#[derive(Clone)]
+pub struct SolverConfig {
+    pub engine: SolverEngine,
+    pub transpositions: usize,
+    pub evaluator: solve::EvaluationWeights,
+    pub strategy: Vec<StrategyLayer>,
+}
+
+#[derive(Clone)]
+pub struct RunConfig {
+    pub seed: String,
+    pub content: Vec<String>,
+    pub game: GameConfig,
+    pub solver: SolverConfig,
+}
+
+
+pub fn make_content_pack(name: &str) -> Option<Box<dyn base::ContentPack>> {
+    match name {
+        "core" => Some(Box::new(CoreContent::new())),
+        #[cfg(test)]
+        "test-events" => Some(Box::new(crate::testing::TestEventContent {})),
+        _ => None,
+    }
+}
+
+// The `difficulty` of the configuration is the level of the adversary.
+pub fn make_adversary(name: &str, level: u8) -> Option<Box<dyn base::AdversaryDescription>> {
+    if !all_adversary_difficulties(name).contains(&level) {
+        return None;
+    }
+
+    match name {
+        "none" => Some(Box::new(base::DefaultAdversaryDescription::new())),
+        "brandenburg-prussia" => Some(Box::new(AdversaryDescriptionBrandenburgPrussia::new(level))),
+        "england" => Some(Box::new(AdversaryDescriptionEngland::new(level))),
+        "sweden" => Some(Box::new(AdversaryDescriptionSweden::new(level))),
+        _ => None,
+    }
+}
+
+pub fn all_adversary_names() -> Vec<&'static str> {
+    vec!["none", "brandenburg-prussia", "england", "sweden"]
+}
+
+pub fn all_adversary_difficulties(name: &str) -> Vec<u8> {
+    match name {
+        "none" => vec![0],
+        "brandenburg-prussia" | "england" | "sweden" => (0..=6).collect(),
+        _ => Vec::new(),
+    }
+}
+
+
+// Each position is either fixed or any of `every`, positions never repeat a value (no game uses
+// the same spirit or board twice).
+fn expand_distinct(positions: &[Choice<String>], every: &[String]) -> Vec<Vec<String>> {
+    let mut result: Vec<Vec<String>> = vec![Vec::new()];
+
+    for position in positions.iter() {
+        let options: Vec<String> = match position {
+            Choice::All => every.to_vec(),
+            Choice::One(value) => vec![value.clone()],
+        };
+
+        result = result.into_iter()
+            .flat_map(|prefix| {
+                options.iter()
+                    .filter(|o| !prefix.contains(o))
+                    .map(|o| {
+                        let mut next = prefix.clone();
+                        next.push(o.clone());
+                        next
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+
+    result
+}
+
+
+// -- yaml helpers --
+
+pub fn check_keys(yaml: &Yaml, section: &str, known: &[&str]) -> Result<(), Box<dyn Error>> {
+    match yaml {
+        Yaml::Hash(hash) => {
+            for key in hash.keys() {
+                let key = key.as_str().unwrap_or("<not a string>");
+                if !known.contains(&key) {
+                    bail!("Unknown key `{}` in `{}` (expected one of: {}).", key, section, known.join(", "));
+                }
+            }
+            Ok(())
+        },
+        _ => bail!("`{}` must be a mapping.", section),
+    }
+}
+
+pub fn as_string(yaml: &Yaml, what: &str) -> Result<String, Box<dyn Error>> {
+    match yaml {
+        Yaml::String(s) => Ok(s.clone()),
+        Yaml::Integer(i) => Ok(i.to_string()),
+        Yaml::Boolean(b) => Ok(b.to_string()),
+        Yaml::BadValue | Yaml::Null => bail!("`{}` is required.", what),
+        _ => bail!("`{}` must be a string.", what),
+    }
+}
+
+pub fn as_string_list(yaml: &Yaml, what: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    match yaml {
+        Yaml::Array(list) => list.iter().map(|v| as_string(v, what)).collect(),
+        Yaml::BadValue | Yaml::Null => bail!("`{}` is required.", what),
+        single => Ok(vec![as_string(single, what)?]),
+    }
+}
+
+// yaml-rust follows YAML 1.2, so the YAML 1.1 style `yes`/`off` switches come through as strings.
+pub fn as_switch(yaml: &Yaml, what: &str, default: bool) -> Result<bool, Box<dyn Error>> {
+    match yaml {
+        Yaml::Boolean(b) => Ok(*b),
+        Yaml::BadValue | Yaml::Null => Ok(default),
+        Yaml::String(s) => match s.as_str() {
+            "yes" | "on" | "true" => Ok(true),
+            "no" | "off" | "false" => Ok(false),
+            _ => bail!("`{}` must be one of yes/no/on/off, found `{}`.", what, s),
+        },
+        _ => bail!("`{}` must be one of yes/no/on/off.", what),
+    }
+}
+
+pub fn as_number<T: TryFrom<i64>>(yaml: &Yaml, what: &str) -> Result<T, Box<dyn Error>> {
+    match yaml {
+        Yaml::Integer(i) => match T::try_from(*i) {
+            Ok(v) => Ok(v),
+            Err(_) => bail!("`{}` is out of range ({}).", what, i),
+        },
+        Yaml::BadValue | Yaml::Null => bail!("`{}` is required.", what),
+        _ => bail!("`{}` must be a number.", what),
+    }
+}
+
+pub fn as_real(yaml: &Yaml, what: &str) -> Result<f64, Box<dyn Error>> {
+    match yaml {
+        Yaml::Integer(i) => Ok(*i as f64),
+        Yaml::Real(_) => Ok(yaml.as_f64().unwrap()),
+        Yaml::BadValue | Yaml::Null => bail!("`{}` is required.", what),
+        _ => bail!("`{}` must be a number.", what),
+    }
+}
+
+// `all` (or 0) means take every choice, like `--solver-take 0`.
+fn as_take(yaml: &Yaml, what: &str) -> Result<u8, Box<dyn Error>> {
+    match yaml {
+        Yaml::Integer(i) if *i >= 0 && *i <= 255 => Ok(*i as u8),
+        Yaml::String(s) if s == "all" => Ok(0),
+        Yaml::BadValue | Yaml::Null => Ok(0),
+        _ => bail!("`{}` must be `all` or a number between 0 and 255.", what),
+    }
+}
+
+fn as_difficulty(yaml: &Yaml) -> Result<Choice<u8>, Box<dyn Error>> {
+    match yaml {
+        Yaml::Integer(i) if *i >= 0 && *i <= 6 => Ok(Choice::One(*i as u8)),
+        Yaml::String(s) if s == "all" => Ok(Choice::All),
+        Yaml::BadValue | Yaml::Null => Ok(Choice::One(0)),
+        _ => bail!("`game.adversary.difficulty` must be `all` or a level from 0 to 6."),
+    }
+}
+
+fn as_choice(yaml: &Yaml, what: &str) -> Result<Choice<String>, Box<dyn Error>> {
+    let value = as_string(yaml, what)?;
+    if value == "all" {
+        Ok(Choice::All)
+    } else {
+        Ok(Choice::One(value))
+    }
+}
+
+
+impl RunConfig {
+    pub fn load(path: &str) -> Result<RunConfig, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| Box::<dyn Error>::from(format!("Could not read `{}`: {}", path, e)))?;
+
+        RunConfig::parse(&text)
+            .map_err(|e| Box::<dyn Error>::from(format!("In `{}`: {}", path, e)))
+    }
+
+    pub fn parse(text: &str) -> Result<RunConfig, Box<dyn Error>> {
+        let docs = YamlLoader::load_from_str(text)?;
+        let doc = match docs.first() {
+            Some(doc) => doc,
+            None => bail!("Configuration is empty."),
+        };
+
+        check_keys(doc, "<root>", &["seed", "content", "game", "solver"])?;
+
+        RunConfig::from_yaml(doc)
+    }
+
+    // Reads the sections of a configuration from `doc`, other documents (like game records) embed
+    // a configuration and check their own keys.
+    pub fn from_yaml(doc: &Yaml) -> Result<RunConfig, Box<dyn Error>> {
+        let seed = match &doc["seed"] {
+            Yaml::BadValue | Yaml::Null => "default".to_string(),
+            Yaml::String(s) if s == "off" => "default".to_string(),
+            Yaml::Boolean(false) => "default".to_string(),
+            v => as_string(v, "seed")?,
+        };
+
+        let content = match &doc["content"] {
+            Yaml::BadValue | Yaml::Null => vec!["core".to_string()],
+            v => as_string_list(v, "content")?,
+        };
+
+        let config = RunConfig {
+            seed,
+            content,
+            game: RunConfig::parse_game(&doc["game"])?,
+            solver: RunConfig::parse_solver(&doc["solver"])?,
+        };
+
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    // The seed, content and game sections, everything needed to recreate the initial game state. In
+    // fate mode the `setup-branch` layer changes how the decks are drawn so it is kept too, as is
+    // the `setup-power-progression` layer changing how power cards are gained.
+    pub fn to_header_yaml(&self) -> yaml::Hash {
+        fn string(s: &str) -> Yaml {
+            Yaml::String(s.to_string())
+        }
+        fn choice_list(choices: &[Choice<String>]) -> Yaml {
+            Yaml::Array(choices.iter().map(|c| string(&c.to_string())).collect())
+        }
+
+        let mut adversary = yaml::Hash::new();
+        adversary.insert(string("name"), string(&self.game.adversary.name.to_string()));
+        adversary.insert(string("difficulty"), match self.game.adversary.difficulty {
+            Choice::All => string("all"),
+            Choice::One(difficulty) => Yaml::Integer(difficulty as i64),
+        });
+
+        let mut variants = yaml::Hash::new();
+        variants.insert(string("events"), Yaml::Boolean(self.game.events));
+
+        let mut game = yaml::Hash::new();
+        game.insert(string("spirits"), choice_list(&self.game.spirits));
+        game.insert(string("boards"), choice_list(&self.game.boards));
+        game.insert(string("adversary"), Yaml::Hash(adversary));
+        game.insert(string("variants"), Yaml::Hash(variants));
+
+        let mut header = yaml::Hash::new();
+        header.insert(string("seed"), string(&self.seed));
+        header.insert(string("content"), Yaml::Array(self.content.iter().map(|c| string(c)).collect()));
+        header.insert(string("game"), Yaml::Hash(game));
+
+        if self.make_fate().any() || self.make_power_progression().is_some() {
+            let setup_layers: Vec<Yaml> = self.solver.strategy.iter()
+                .filter_map(|layer| match layer {
+                    StrategyLayer::SetupPowerProgression{on_underflow} => {
+                        let mut hash = yaml::Hash::new();
+                        hash.insert(string("type"), string(layer.type_name()));
+                        hash.insert(string("on-underflow"), string(match on_underflow {
+                            base::ProgressionUnderflow::Nothing => "nothing",
+                            base::ProgressionUnderflow::Draft => "draft",
+                        }));
+                        Some(Yaml::Hash(hash))
+                    },
+                    StrategyLayer::SetupBranch{take, fear, invader, blight, minor, major, events} => {
+                        let mut hash = yaml::Hash::new();
+                        hash.insert(string("type"), string(layer.type_name()));
+                        hash.insert(string("take"), Yaml::Integer(*take as i64));
+                        for (name, value) in [("fear", fear), ("invader", invader), ("blight", blight), ("minor", minor), ("major", major), ("events", events)] {
+                            hash.insert(string(name), Yaml::Boolean(*value));
+                        }
+                        Some(Yaml::Hash(hash))
+                    },
+                    _ => None,
+                })
+                .collect();
+            let mut solver = yaml::Hash::new();
+            solver.insert(string("strategy"), Yaml::Array(setup_layers));
+            header.insert(string("solver"), Yaml::Hash(solver));
+        }
+
+        header
+    }
+
+    fn parse_game(yaml: &Yaml) -> Result<GameConfig, Box<dyn Error>> {
+        check_keys(yaml, "game", &["spirits", "boards", "adversary", "variants"])?;
+
+        let spirits = match &yaml["spirits"] {
+            Yaml::Array(list) => list.iter().map(|v| as_choice(v, "game.spirits")).collect::<Result<Vec<_>, _>>()?,
+            Yaml::BadValue | Yaml::Null => bail!("`game.spirits` is required."),
+            single => vec![as_choice(single, "game.spirits")?],
+        };
+        let boards = match &yaml["boards"] {
+            Yaml::Array(list) => list.iter().map(|v| as_choice(v, "game.boards")).collect::<Result<Vec<_>, _>>()?,
+            Yaml::BadValue | Yaml::Null => bail!("`game.boards` is required."),
+            single => vec![as_choice(single, "game.boards")?],
+        };
+
+        let adversary_yaml = &yaml["adversary"];
+        let adversary = match adversary_yaml {
+            Yaml::BadValue | Yaml::Null => AdversaryConfig { name: Choice::One("none".to_string()), difficulty: Choice::One(0) },
+            Yaml::String(_) => AdversaryConfig { name: as_choice(adversary_yaml, "game.adversary")?, difficulty: Choice::One(0) },
+            _ => {
+                check_keys(adversary_yaml, "game.adversary", &["name", "difficulty"])?;
+                AdversaryConfig {
+                    name: as_choice(&adversary_yaml["name"], "game.adversary.name")?,
+                    difficulty: as_difficulty(&adversary_yaml["difficulty"])?,
+                }
+            }
+        };
+
+        let variants = &yaml["variants"];
+        let events = match variants {
+            Yaml::BadValue | Yaml::Null => false,
+            _ => {
+                check_keys(variants, "game.variants", &["events"])?;
+                as_switch(&variants["events"], "game.variants.events", false)?
+            }
+        };
+
+        Ok(GameConfig {
+            spirits,
+            boards,
+            adversary,
+            events,
+        })
+    }
+
+    // The engine is either just its type or a mapping with the type and its settings.
+    fn parse_engine(yaml: &Yaml) -> Result<SolverEngine, Box<dyn Error>> {
+        let kind = match yaml {
+            Yaml::BadValue | Yaml::Null => return Ok(SolverEngine::Tree(solve::TreeOptions::new())),
+            Yaml::String(s) => s.clone(),
+            _ => as_string(&yaml["type"], "solver.engine.type")?,
+        };
+
+        match kind.as_str() {
+            "tree" => {
+                if let Yaml::Hash(_) = yaml {
+                    check_keys(yaml, "solver.engine (tree)", &["type", "horizon-turns", "horizon-decisions"])?;
+                }
+
+                let horizon_turns = match &yaml["horizon-turns"] {
+                    Yaml::BadValue | Yaml::Null => None,
+                    v => Some(as_number::<u8>(v, "solver.engine.horizon-turns")?),
+                };
+                let horizon_decisions = match &yaml["horizon-decisions"] {
+                    Yaml::BadValue | Yaml::Null => None,
+                    v => Some(as_number::<usize>(v, "solver.engine.horizon-decisions")?),
+                };
+                if horizon_turns == Some(0) || horizon_decisions == Some(0) {
+                    bail!("A horizon of 0 doesn't search anything.");
+                }
+
+                Ok(SolverEngine::Tree(solve::TreeOptions {
+                    horizon_turns,
+                    horizon_decisions,
+                }))
+            },
+            "mcts" => {
+                if let Yaml::Hash(_) = yaml {
+                    check_keys(yaml, "solver.engine (mcts)", &["type", "iterations", "seconds", "exploration", "rollout-turns"])?;
+                }
+
+                let iterations = match &yaml["iterations"] {
+                    Yaml::BadValue | Yaml::Null => None,
+                    v => Some(as_number::<usize>(v, "solver.engine.iterations")?),
+                };
+                let seconds = match &yaml["seconds"] {
+                    Yaml::BadValue | Yaml::Null => None,
+                    v => Some(as_real(v, "solver.engine.seconds")?),
+                };
+                let exploration = match &yaml["exploration"] {
+                    Yaml::BadValue | Yaml::Null => std::f64::consts::SQRT_2,
+                    v => as_real(v, "solver.engine.exploration")?,
+                };
+                let rollout_turns = match &yaml["rollout-turns"] {
+                    Yaml::BadValue | Yaml::Null => None,
+                    v => Some(as_number::<u8>(v, "solver.engine.rollout-turns")?),
+                };
+
+                Ok(SolverEngine::Mcts(solve::MctsOptions {
+                    // Without any budget run a modest number of iterations.
+                    iterations: if iterations.is_none() && seconds.is_none() { Some(1000) } else { iterations },
+                    seconds,
+                    exploration,
+                    rollout_turns,
+                }))
+            },
+            other => bail!("Unknown solver engine `{}` (expected tree or mcts).", other),
+        }
+    }
+
+    // Any weight not given keeps its default.
+    fn parse_evaluator(yaml: &Yaml) -> Result<solve::EvaluationWeights, Box<dyn Error>> {
+        let mut weights = solve::EvaluationWeights::new();
+        if let Yaml::BadValue | Yaml::Null = yaml {
+            return Ok(weights);
+        }
+
+        check_keys(yaml, "solver.evaluator", &["blight", "explorer", "town", "city", "dahan", "fear", "presence", "energy", "invader-cards"])?;
+        for (key, weight) in [
+                ("blight", &mut weights.blight),
+                ("explorer", &mut weights.explorer),
+                ("town", &mut weights.town),
+                ("city", &mut weights.city),
+                ("dahan", &mut weights.dahan),
+                ("fear", &mut weights.fear),
+                ("presence", &mut weights.presence),
+                ("energy", &mut weights.energy),
+                ("invader-cards", &mut weights.invader_cards)] {
+            if let Yaml::BadValue | Yaml::Null = &yaml[key] {
+                continue;
+            }
+            *weight = as_real(&yaml[key], &format!("solver.evaluator.{}", key))?;
+        }
+
+        Ok(weights)
+    }
+
+    fn parse_solver(yaml: &Yaml) -> Result<SolverConfig, Box<dyn Error>> {
+        let (engine_yaml, strategy_yaml) = match yaml {
+            Yaml::BadValue | Yaml::Null => return Ok(SolverConfig {
+                engine: SolverEngine::Tree(solve::TreeOptions::new()),
+                transpositions: DEFAULT_TRANSPOSITIONS,
+                evaluator: solve::EvaluationWeights::new(),
+                strategy: Vec::new(),
+            }),
+            _ => {
+                check_keys(yaml, "solver", &["engine", "transpositions", "evaluator", "strategy"])?;
+                (&yaml["engine"], &yaml["strategy"])
+            }
+        };
+
+        let engine = RunConfig::parse_engine(engine_yaml)?;
+        let transpositions = match &yaml["transpositions"] {
+            Yaml::BadValue | Yaml::Null => DEFAULT_TRANSPOSITIONS,
+            v => as_number(v, "solver.transpositions")?,
+        };
+        let evaluator = RunConfig::parse_evaluator(&yaml["evaluator"])?;
+
+        let layers = match strategy_yaml {
+            Yaml::Array(list) => list,
+            Yaml::BadValue | Yaml::Null => return Ok(SolverConfig { engine, transpositions, evaluator, strategy: Vec::new() }),
+            _ => bail!("`solver.strategy` must be a list."),
+        };
+
+        let mut strategy = Vec::new();
+        for layer in layers.iter() {
+            let kind = as_string(&layer["type"], "solver.strategy.type")?;
+            let section = format!("solver.strategy ({})", kind);
+
+            strategy.push(match kind.as_str() {
+                "setup-power-progression" => {
+                    check_keys(layer, &section, &["type", "on-underflow"])?;
+                    let on_underflow = match &layer["on-underflow"] {
+                        Yaml::BadValue | Yaml::Null => base::ProgressionUnderflow::Nothing,
+                        v => match as_string(v, "on-underflow")?.as_str() {
+                            "nothing" => base::ProgressionUnderflow::Nothing,
+                            "draft" => base::ProgressionUnderflow::Draft,
+                            other => bail!("Unknown on-underflow `{}` (expected nothing or draft).", other),
+                        }
+                    };
+                    StrategyLayer::SetupPowerProgression { on_underflow }
+                },
+                "setup-branch" => {
+                    check_keys(layer, &section, &["type", "take", "fear", "invader", "blight", "minor", "major", "events"])?;
+                    StrategyLayer::SetupBranch {
+                        take: as_take(&layer["take"], "take")?,
+                        fear: as_switch(&layer["fear"], "fear", false)?,
+                        invader: as_switch(&layer["invader"], "invader", false)?,
+                        blight: as_switch(&layer["blight"], "blight", false)?,
+                        minor: as_switch(&layer["minor"], "minor", false)?,
+                        major: as_switch(&layer["major"], "major", false)?,
+                        events: as_switch(&layer["events"], "events", false)?,
+                    }
+                },
+                "play-branch" => {
+                    check_keys(layer, &section, &["type", "take", "order"])?;
+                    let order = match &layer["order"] {
+                        Yaml::BadValue | Yaml::Null => PlayOrder::Simple,
+                        v => match as_string(v, "order")?.as_str() {
+                            "simple" => PlayOrder::Simple,
+                            "rng" => PlayOrder::Rng,
+                            other => bail!("Unknown play-branch order `{}` (expected simple or rng).", other),
+                        }
+                    };
+                    StrategyLayer::PlayBranch {
+                        take: as_take(&layer["take"], "take")?,
+                        order,
+                    }
+                },
+                other => bail!("Unknown solver strategy type `{}` (expected one of: setup-power-progression, setup-branch, play-branch).", other),
+            });
+        }
+
+        Ok(SolverConfig { engine, transpositions, evaluator, strategy })
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        let content = self.make_content()?;
+
+        if self.game.spirits.is_empty() {
+            bail!("`game.spirits` must name at least one spirit.");
+        }
+        for spirit in self.game.spirits.iter() {
+            if let Choice::One(name) = spirit {
+                if base::search_for_spirit(&content, name).is_none() {
+                    bail!("Spirit `{}` not found.", name);
+                }
+            }
+        }
+
+        if self.game.boards.len() != self.game.spirits.len() {
+            bail!("`game.boards` must list one board per spirit ({} spirits, {} boards).", self.game.spirits.len(), self.game.boards.len());
+        }
+        for (index, board) in self.game.boards.iter().enumerate() {
+            if let Choice::One(name) = board {
+                if base::search_for_board(&content, name).is_none() {
+                    bail!("Board `{}` not found.", name);
+                }
+                // the same board twice would put its lands on the island twice
+                if self.game.boards[..index].contains(board) {
+                    bail!("Board `{}` is listed twice, each spirit needs its own board.", name);
+                }
+            }
+        }
+
+        if let Choice::One(name) = &self.game.adversary.name {
+            if !all_adversary_names().contains(&name.as_str()) {
+                bail!("Adversary `{}` not found (expected one of: {}).", name, all_adversary_names().join(", "));
+            }
+            if let Choice::One(level) = self.game.adversary.difficulty {
+                if !all_adversary_difficulties(name).contains(&level) {
+                    bail!("Adversary `{}` has no level {}.", name, level);
+                }
+            }
+        }
+
+        // a card missing from the content would be skipped without a word
+        if self.make_power_progression().is_some() {
+            let powers = base::join_power_cards(&content);
+            for spirit in self.game.spirits.iter() {
+                if let Choice::One(name) = spirit {
+                    let spirit = base::search_for_spirit(&content, name).unwrap();
+                    for card in spirit.get_power_progression() {
+                        if !powers.iter().any(|p| p.name == card) {
+                            bail!("The power progression of `{}` names `{}`, a power card that was not found.", spirit.name(), card);
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.game.events && base::join_event_cards(&content).is_empty() {
+            bail!("The events variant needs content with event cards, there are none in: {}.", self.content.join(", "));
+        }
+
+        Ok(())
+    }
+
+
+    // The cross product of every `all` in the game section, each entry is a single game.
+    pub fn expand(&self) -> Result<Vec<RunConfig>, Box<dyn Error>> {
+        let content = self.make_content()?;
+
+        let all_spirits: Vec<String> = content.iter()
+            .flat_map(|c| c.get_spirits().into_iter().map(|s| s.name().to_string()))
+            .collect();
+        let all_boards: Vec<String> = content.iter()
+            .flat_map(|c| c.get_boards().into_iter().map(|b| b.name.to_string()))
+            .collect();
+
+        let spirit_sets = expand_distinct(&self.game.spirits, &all_spirits);
+        let board_sets = expand_distinct(&self.game.boards, &all_boards);
+
+        let adversary_names = match &self.game.adversary.name {
+            Choice::All => all_adversary_names().into_iter().map(|n| n.to_string()).collect(),
+            Choice::One(name) => vec![name.clone()],
+        };
+
+        let mut result = Vec::new();
+        for spirits in spirit_sets.iter() {
+            for boards in board_sets.iter() {
+                for adversary in adversary_names.iter() {
+                    let difficulties = match &self.game.adversary.difficulty {
+                        Choice::All => all_adversary_difficulties(adversary),
+                        Choice::One(difficulty) => vec![*difficulty],
+                    };
+
+                    for difficulty in difficulties {
+                        let mut config = self.clone();
+                        config.game.spirits = spirits.iter().cloned().map(Choice::One).collect();
+                        config.game.boards = boards.iter().cloned().map(Choice::One).collect();
+                        config.game.adversary = AdversaryConfig {
+                            name: Choice::One(adversary.clone()),
+                            difficulty: Choice::One(difficulty),
+                        };
+
+                        result.push(config);
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn make_content(&self) -> Result<Vec<Box<dyn base::ContentPack>>, Box<dyn Error>> {
+        let mut content = Vec::new();
+        for name in self.content.iter() {
+            match make_content_pack(name) {
+                Some(pack) => content.push(pack),
+                None => bail!("Content pack `{}` not found.", name),
+            }
+        }
+
+        Ok(content)
+    }
+
+    pub fn make_description(&self) -> Result<base::GameDescription, Box<dyn Error>> {
+        let content = self.make_content()?;
+
+        let mut spirits = Vec::new();
+        for spirit in self.game.spirits.iter() {
+            match spirit {
+                Choice::One(name) => match base::search_for_spirit(&content, name) {
+                    Some(spirit) => spirits.push(spirit),
+                    None => bail!("Spirit `{}` not found.", name),
+                },
+                Choice::All => bail!("`spirits: all` describes every spirit, use --batch to sweep over them."),
+            }
+        }
+
+        let mut boards = Vec::new();
+        for board in self.game.boards.iter() {
+            match board {
+                Choice::One(name) => boards.push(name.as_str()),
+                Choice::All => bail!("`boards: all` describes every board, use --batch to sweep over them."),
+            }
+        }
+
+        let adversary = match (&self.game.adversary.name, &self.game.adversary.difficulty) {
+            (Choice::One(name), Choice::One(difficulty)) => match make_adversary(name, *difficulty) {
+                Some(adversary) => adversary,
+                None => bail!("Adversary `{}` not found.", name),
+            },
+            _ => bail!("`adversary: all` describes every adversary, use --batch to sweep over them."),
+        };
+
+        let map = Box::new(base::make_map(&content, boards));
+
+        Ok(base::GameDescription::new(content, adversary, spirits, map, self.game.events))
+    }
+
+    pub fn make_state(&self) -> Result<base::GameState, Box<dyn Error>> {
+        let description = Arc::new(self.make_description()?);
+        let rng = Box::new(base::DeterministicChaCha::new(ChaChaRng::from_seed(base::make_seed(&self.seed))));
+
+        let mut state = base::GameState::new(description, rng);
+        state.fate = self.make_fate();
+        state.power_progression = self.make_power_progression();
+
+        Ok(state)
+    }
+
+    // The decks the `setup-branch` strategy draws from by fate instead of at random.
+    pub fn make_fate(&self) -> base::FateOptions {
+        let mut fate = base::FateOptions::none();
+        for layer in self.solver.strategy.iter() {
+            if let StrategyLayer::SetupBranch{fear, invader, blight, minor, major, ..} = layer {
+                fate.fear |= *fear;
+                fate.invader |= *invader;
+                fate.blight |= *blight;
+                fate.minor |= *minor;
+                fate.major |= *major;
+            }
+        }
+
+        fate
+    }
+
+    // What gaining a power card does once a spirit's power progression is used up, when the
+    // `setup-power-progression` strategy plays the progression instead of drafting.
+    pub fn make_power_progression(&self) -> Option<base::ProgressionUnderflow> {
+        self.solver.strategy.iter()
+            .find_map(|layer| match layer {
+                StrategyLayer::SetupPowerProgression{on_underflow} => Some(*on_underflow),
+                _ => None,
+            })
+    }
+
+    pub fn make_evaluator(&self) -> Box<dyn solve::Evaluator> {
+        solve::WeightedEvaluator::new(self.solver.evaluator.clone())
+    }
+
+    pub fn make_strategy(&self) -> Result<Box<dyn solve::SolveStrategy>, Box<dyn Error>> {
+        let mut play_strategy = None;
+        let mut fate_strategy = None;
+
+        for layer in self.solver.strategy.iter() {
+            match layer {
+                StrategyLayer::PlayBranch{take, order} => {
+                    if play_strategy.is_some() {
+                        bail!("Only one `play-branch` strategy may be given.");
+                    }
+
+                    play_strategy = Some(match order {
+                        PlayOrder::Simple => solve::SimpleDecisionMaker::new(*take) as Box<dyn solve::SolveStrategy>,
+                        PlayOrder::Rng => {
+                            let solver_rng = Box::new(base::DeterministicChaCha::new(ChaChaRng::from_seed(base::make_seed(&self.seed))));
+                            solve::StochasticDecisionMaker::new(solver_rng, *take) as Box<dyn solve::SolveStrategy>
+                        },
+                    });
+                },
+                StrategyLayer::SetupBranch{take, fear, invader, blight, minor, major, events} => {
+                    if *events {
+                        bail!("The `{}` strategy (take: {}) does not support events yet.", layer.type_name(), take);
+                    }
+                    if fate_strategy.is_some() {
+                        bail!("Only one `setup-branch` strategy may be given.");
+                    }
+
+                    if *fear || *invader || *blight || *minor || *major {
+                        fate_strategy = Some(solve::SimpleDecisionMaker::new(*take) as Box<dyn solve::SolveStrategy>);
+                    }
+                },
+                // changes the game rather than how it is solved, see `make_power_progression`
+                StrategyLayer::SetupPowerProgression{..} => {},
+            }
+        }
+
+        let play_strategy = play_strategy.unwrap_or_else(|| solve::SimpleDecisionMaker::new(2));
+
+        Ok(match fate_strategy {
+            Some(fate_strategy) => solve::FateDecisionMaker::new(fate_strategy, play_strategy),
+            None => play_strategy,
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boards_may_not_repeat() {
+        let error = RunConfig::parse("game:\n  spirits: [river, lightning]\n  boards: [A, A]\n").err().unwrap();
+        assert!(error.to_string().contains("listed twice"));
+
+        assert!(RunConfig::parse("game:\n  spirits: [river, lightning]\n  boards: [A, B]\n").is_ok());
+    }
+
+    #[test]
+    fn header_round_trips() {
+        let texts = [
+            include_str!("../configurations/sample.yaml"),
+            "seed: round\ncontent: [core]\ngame:\n  spirits: [river, lightning]\n  boards: [C, D]\n  adversary:\n    name: england\n    difficulty: 4\n  variants:\n    events: no\n",
+            "game:\n  spirits: [lightning]\n  boards: [B]\nsolver:\n  strategy:\n    - type: setup-power-progression\n      on-underflow: draft\n    - type: setup-branch\n      take: 2\n      fear: yes\n      blight: yes\n",
+        ];
+        for text in texts.iter() {
+            let config = RunConfig::parse(text).unwrap();
+            let header = config.to_header_yaml();
+
+            let mut emitted = String::new();
+            yaml_rust::YamlEmitter::new(&mut emitted).dump(&Yaml::Hash(header.clone())).unwrap();
+            let again = RunConfig::parse(&emitted).unwrap();
+
+            assert!(again.to_header_yaml() == header, "{}", emitted);
+            assert_eq!(again.seed, config.seed);
+            assert!(again.make_fate() == config.make_fate());
+            assert!(again.make_power_progression() == config.make_power_progression());
+        }
+    }
+}
@@ -3,18 +3,22 @@
 use crate::base::{
     ContentPack,
     SpiritDescription, BoardDescription,
-    FearCardDescription, PowerCardDescription
+    FearCardDescription, PowerCardDescription, BlightCardDescription, EventCardDescription,
 };
 
 mod spirit;
 mod board;
 mod fear;
 mod power;
+mod blight;
+mod adversary;
 
 pub use spirit::{SpiritDescriptionRiver, SpiritDescriptionLightning};
-use board::{make_board_a};
+pub use adversary::{AdversaryDescriptionBrandenburgPrussia, AdversaryDescriptionEngland, AdversaryDescriptionSweden};
+use board::{make_board_a, make_board_b, make_board_c, make_board_d};
 use fear::{make_fear_cards};
 use power::{make_minor_power_cards, make_major_power_cards};
+use blight::{make_blight_cards};
 
 pub struct CoreContent {
 
@@ -31,6 +35,9 @@ impl ContentPack for CoreContent {
     fn get_boards(&self) -> Vec<BoardDescription> {
         vec![
             make_board_a(),
+            make_board_b(),
+            make_board_c(),
+            make_board_d(),
         ]
     }
 
@@ -45,6 +52,15 @@ impl ContentPack for CoreContent {
 
         res
     }
+
+    fn get_blight_cards(&self) -> Vec<BlightCardDescription> {
+        make_blight_cards()
+    }
+
+    fn get_event_cards(&self) -> Vec<EventCardDescription> {
+        // events come with Branch and Claw
+        Vec::new()
+    }
 }
 
 impl CoreContent {
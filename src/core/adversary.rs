@@ -0,0 +1,518 @@
+// This file contains copyrighted assets owned by Greater Than Games.
+
+use crate::base::{
+    GameState, StepFailure, AdversaryDescription, InvaderActionKind,
+    InvaderCard, FateDeck, Invader, InvaderKind, TokenKind, LandState,
+    GameStep, TurnStep, InvaderStep,
+    STANDARD_INVADER_STAGES,
+    effect::*, decision::*,
+};
+
+
+/*
+    The adversaries of the base game. The rules of each level add to the ones below it.
+*/
+
+// The land with the given number on each board, by index on the table.
+fn land_on_each_board(game: &GameState, number: u8) -> Vec<u8> {
+    game.desc.table.lands.iter()
+        .filter(|l| l.index_on_board == number)
+        .map(|l| l.index_on_table)
+        .collect()
+}
+
+// The lands in play of each board, by index on the table.
+fn lands_of_each_board(game: &GameState) -> Vec<Vec<u8>> {
+    (0..game.desc.table.boards.len() as u8)
+        .map(|board| game.table.lands.iter()
+            .filter(|l| l.is_in_play && l.desc.parent_board_index == board)
+            .map(|l| l.desc.index_on_table)
+            .collect())
+        .collect()
+}
+
+fn building_count(land: &LandState) -> usize {
+    land.invaders.iter().filter(|i| i.is_building()).count()
+}
+
+fn add_town(game: &mut GameState, land_index: u8) -> Result<(), StepFailure> {
+    game.do_effect(AddInvaderEffect { land_index, kind: InvaderKind::Town, count: 1 })
+}
+
+fn add_city(game: &mut GameState, land_index: u8) -> Result<(), StepFailure> {
+    game.do_effect(AddInvaderEffect { land_index, kind: InvaderKind::City, count: 1 })
+}
+
+// Blight from the blight card that neither destroys presence nor cascades.
+fn add_blight_quietly(game: &mut GameState, land_index: u8) -> Result<(), StepFailure> {
+    game.log_effect(format_args!("adding blight to land {} (without destroying presence or cascading).", land_index));
+
+    if game.blight_remaining == 0 {
+        game.do_defeat("No blight is left.")?;
+    }
+
+    game.blight_remaining -= 1;
+    if game.blight_remaining == 0 {
+        game.do_effect(FlipBlightCardEffect {})?;
+    }
+
+    game.get_land_mut(land_index)?.tokens[TokenKind::Blight] += 1;
+
+    Ok(())
+}
+
+// Blight from the box, rather than from the blight card.
+fn add_blight_from_box(game: &mut GameState, land_index: u8) -> Result<(), StepFailure> {
+    game.log_effect(format_args!("adding blight from the box to land {}.", land_index));
+    game.get_land_mut(land_index)?.tokens[TokenKind::Blight] += 1;
+
+    Ok(())
+}
+
+
+pub struct AdversaryDescriptionBrandenburgPrussia {
+    level: u8,
+}
+
+impl AdversaryDescriptionBrandenburgPrussia {
+    pub fn new(level: u8) -> AdversaryDescriptionBrandenburgPrussia {
+        AdversaryDescriptionBrandenburgPrussia {
+            level,
+        }
+    }
+}
+
+impl AdversaryDescription for AdversaryDescriptionBrandenburgPrussia {
+    fn name(&self) -> &'static str { "Brandenburg-Prussia" }
+    fn level(&self) -> u8 { self.level }
+    fn difficulty(&self) -> u8 {
+        [1, 2, 4, 6, 7, 9, 10][self.level as usize]
+    }
+
+    fn fear_cards(&self) -> (u8, u8, u8) {
+        match self.level {
+            0..=2 => (3, 3, 3),
+            3 => (3, 4, 3),
+            4 | 5 => (4, 4, 3),
+            _ => (4, 4, 4),
+        }
+    }
+    fn invader_deck_stages(&self) -> Vec<u8> {
+        match self.level {
+            0 | 1 => STANDARD_INVADER_STAGES.to_vec(),
+            // Surge of Colonists: a stage III card between stage I and stage II
+            2 => vec![1, 1, 1, 3, 2, 2, 2, 2, 3, 3, 3, 3],
+            // Efficient: one less stage I card
+            3 => vec![1, 1, 3, 2, 2, 2, 2, 3, 3, 3, 3],
+            // Aggressive Timetable: one less stage II card
+            4 => vec![1, 1, 3, 2, 2, 2, 3, 3, 3, 3],
+            // Ruthlessly Efficient: one less stage I card
+            5 => vec![1, 3, 2, 2, 2, 3, 3, 3, 3],
+            // Terrifyingly Efficient: no stage I cards
+            _ => vec![3, 2, 2, 2, 3, 3, 3, 3],
+        }
+    }
+    fn invader_steps(&self) -> Vec<InvaderActionKind> {
+        vec![InvaderActionKind::Ravage, InvaderActionKind::Build, InvaderActionKind::Explore]
+    }
+
+    fn setup(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        if self.level >= 1 {
+            game.log_effect(format_args!("Fast Start: a town in land #3 of each board."));
+            for land_index in land_on_each_board(game, 3) {
+                add_town(game, land_index)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn escalation(&self, game: &mut GameState, _card: InvaderCard) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("Land Rush: a town on each board with a town or city, in a land without a town."));
+        for lands in lands_of_each_board(game) {
+            if !lands.iter().any(|l| building_count(&game.table.lands[*l as usize]) > 0) {
+                continue;
+            }
+
+            let lands = lands.into_iter()
+                .filter(|l| !game.table.lands[*l as usize].invaders.iter().any(|i| i.kind == InvaderKind::Town))
+                .collect();
+            game.do_effect(ChooseLandDecision { lands, effect: add_town })?;
+        }
+
+        Ok(())
+    }
+}
+
+
+pub struct AdversaryDescriptionEngland {
+    level: u8,
+}
+
+impl AdversaryDescriptionEngland {
+    pub fn new(level: u8) -> AdversaryDescriptionEngland {
+        AdversaryDescriptionEngland {
+            level,
+        }
+    }
+}
+
+impl AdversaryDescription for AdversaryDescriptionEngland {
+    fn name(&self) -> &'static str { "England" }
+    fn level(&self) -> u8 { self.level }
+    fn difficulty(&self) -> u8 {
+        [1, 3, 4, 6, 7, 9, 11][self.level as usize]
+    }
+
+    fn fear_cards(&self) -> (u8, u8, u8) {
+        match self.level {
+            0 => (3, 3, 3),
+            1 => (3, 4, 3),
+            2 => (4, 4, 3),
+            3 => (4, 5, 4),
+            4 => (4, 5, 5),
+            _ => (4, 5, 4),
+        }
+    }
+    fn invader_steps(&self) -> Vec<InvaderActionKind> {
+        // High Immigration: a build before the ravage
+        if self.level >= 3 {
+            vec![InvaderActionKind::Build, InvaderActionKind::Ravage, InvaderActionKind::Build, InvaderActionKind::Explore]
+        } else {
+            vec![InvaderActionKind::Ravage, InvaderActionKind::Build, InvaderActionKind::Explore]
+        }
+    }
+
+    fn setup(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        if self.level >= 2 {
+            game.log_effect(format_args!("Criminals and Servants: a city in land #1 and a town in land #2 of each board."));
+            for land_index in land_on_each_board(game, 1) {
+                add_city(game, land_index)?;
+            }
+            for land_index in land_on_each_board(game, 2) {
+                add_town(game, land_index)?;
+            }
+        }
+
+        if self.level >= 6 {
+            game.log_effect(format_args!("Independent Resolve: 1 more fear in the pool per player."));
+            game.fear_pool += game.desc.spirits.len() as u8;
+        }
+
+        Ok(())
+    }
+
+    fn escalation(&self, game: &mut GameState, _card: InvaderCard) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("Building Boom: a build on each board with a town or city, in the land with the most."));
+        for lands in lands_of_each_board(game) {
+            let most = lands.iter().map(|l| building_count(&game.table.lands[*l as usize])).max().unwrap_or(0);
+            if most == 0 {
+                continue;
+            }
+
+            let lands = lands.into_iter()
+                .filter(|l| building_count(&game.table.lands[*l as usize]) == most)
+                .collect();
+            game.do_effect(ChooseLandDecision { lands, effect: |game, land_index| game.do_effect(BuildEffect { land_index }) })?;
+        }
+
+        Ok(())
+    }
+
+    fn after_invader_card(&self, game: &mut GameState, action: InvaderActionKind, card: InvaderCard) -> Result<(), StepFailure> {
+        if action == InvaderActionKind::Explore && card.phase() == 2 {
+            self.escalation(game, card)?;
+        }
+
+        // Independent Resolve: the High Immigration build happens twice in invader phases where no
+        // fear card was resolved
+        let high_immigration = game.invader.step_count() == 4
+            && matches!(game.step, GameStep::Turn(_, TurnStep::Invader(InvaderStep::InvaderAction(0, _))));
+        if self.level >= 6 && high_immigration && game.fear.resolved == 0 {
+            game.log_effect(format_args!("Independent Resolve: High Immigration builds again."));
+            for land_index in game.desc.table.lands.iter().filter(|l| card.can_target(l)).map(|l| l.index_on_table).collect::<Vec<u8>>() {
+                game.do_effect(BuildEffect { land_index })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn after_invader_advance(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        // High Immigration (I): the tile leaves (with the card) when a stage II card reaches it
+        let high_immigration = game.invader.step_count() == 4;
+        let reached = game.invader.pending.front().is_some_and(|cards| cards.iter().any(|c| c.phase() == 2));
+        if self.level == 3 && high_immigration && reached {
+            game.log_effect(format_args!("High Immigration: the tile is removed."));
+
+            let mut cards = game.invader.pending.pop_front().unwrap_or_default();
+            game.invader.discard.append(&mut cards);
+            game.invader.sequence.remove(0);
+        }
+
+        Ok(())
+    }
+
+    fn builds_without_invaders(&self, game: &GameState, land_index: u8) -> bool {
+        // Indentured Servants Earn Land: lands next to at least 2 towns/cities
+        self.level >= 1
+            && game.table.desc.get_adjacent_lands(land_index).iter()
+                .map(|l| building_count(&game.table.lands[l.index_on_table as usize]))
+                .sum::<usize>() >= 2
+    }
+
+    fn modify_invader(&self, invader: &mut Invader) {
+        // Local Autonomy
+        if self.level >= 5 && invader.is_building() {
+            invader.health_max += 1;
+            invader.health_cur += 1;
+        }
+    }
+
+    fn check_loss(&self, game: &GameState) -> Option<&'static str> {
+        if game.table.lands.iter().any(|l| building_count(l) >= 7) {
+            Some("Proud & Mighty Capital: 7 towns/cities in one land.")
+        } else {
+            None
+        }
+    }
+}
+
+
+pub struct AdversaryDescriptionSweden {
+    level: u8,
+}
+
+impl AdversaryDescriptionSweden {
+    pub fn new(level: u8) -> AdversaryDescriptionSweden {
+        AdversaryDescriptionSweden {
+            level,
+        }
+    }
+}
+
+impl AdversaryDescription for AdversaryDescriptionSweden {
+    fn name(&self) -> &'static str { "Sweden" }
+    fn level(&self) -> u8 { self.level }
+    fn difficulty(&self) -> u8 {
+        [1, 2, 3, 5, 6, 7, 8][self.level as usize]
+    }
+
+    fn fear_cards(&self) -> (u8, u8, u8) {
+        match self.level {
+            0 => (3, 3, 3),
+            1 => (3, 4, 3),
+            2 => (4, 4, 3),
+            3 | 4 => (4, 4, 4),
+            _ => (4, 5, 4),
+        }
+    }
+    fn invader_steps(&self) -> Vec<InvaderActionKind> {
+        vec![InvaderActionKind::Ravage, InvaderActionKind::Build, InvaderActionKind::Explore]
+    }
+
+    fn setup(&self, game: &mut GameState) -> Result<(), StepFailure> {
+        if self.level >= 2 {
+            game.log_effect(format_args!("Population Pressure at Home: a city in land #4 of each board, its starting blight goes to land #5."));
+            for (land_index, next_index) in land_on_each_board(game, 4).into_iter().zip(land_on_each_board(game, 5)) {
+                add_city(game, land_index)?;
+
+                let land = game.get_land_mut(land_index)?;
+                if land.desc.starting_tokens[TokenKind::Blight] > 0 && land.tokens[TokenKind::Blight] > 0 {
+                    land.tokens[TokenKind::Blight] -= 1;
+                    game.get_land_mut(next_index)?.tokens[TokenKind::Blight] += 1;
+                }
+            }
+        }
+
+        if self.level >= 6 {
+            game.log_effect(format_args!("Prospection at Any Cost: a town and blight in land #8 of each board."));
+            for land_index in land_on_each_board(game, 8) {
+                add_town(game, land_index)?;
+                add_blight_from_box(game, land_index)?;
+            }
+        }
+
+        // after every other invader is added
+        if self.level >= 4 {
+            game.fate_draw(FateDeck::Invader)?;
+            let card = game.invader.draw.pop()
+                .ok_or(StepFailure::InternalError("invader deck is empty".to_string()))?;
+            game.invader.discard.push(card);
+
+            game.log_effect(format_args!("Royal Backing: discarding {}, a town on each board in the land of it with the fewest invaders.", card));
+            for lands in lands_of_each_board(game) {
+                let lands: Vec<u8> = lands.into_iter()
+                    .filter(|l| card.can_target(&game.table.lands[*l as usize].desc))
+                    .collect();
+                let fewest = lands.iter().map(|l| game.table.lands[*l as usize].invaders.len()).min().unwrap_or(0);
+
+                let lands = lands.into_iter()
+                    .filter(|l| game.table.lands[*l as usize].invaders.len() == fewest)
+                    .collect();
+                game.do_effect(ChooseLandDecision { lands, effect: add_town })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn escalation(&self, game: &mut GameState, card: InvaderCard) -> Result<(), StepFailure> {
+        game.log_effect(format_args!("Swayed by the Invaders: in lands explored with at least as many invaders as dahan, a dahan becomes a town."));
+        let explored: Vec<u8> = game.table.lands.iter()
+            .filter(|l| card.can_target(&l.desc))
+            .map(|l| l.desc.index_on_table)
+            .filter(|l| ExploreEffect { land_index: *l }.will_explore(game))
+            .collect();
+
+        for land_index in explored {
+            let land = game.get_land(land_index)?;
+            if land.dahan.is_empty() || land.invaders.len() < land.dahan.len() {
+                continue;
+            }
+
+            // the most damaged dahan, if any are
+            let dahan_index = (0..land.dahan.len())
+                .min_by_key(|d| land.dahan[*d].health_cur)
+                .unwrap_or(0);
+
+            game.do_effect(RemoveDahanEffect { land_index, dahan_index, destroyed: false })?;
+            add_town(game, land_index)?;
+        }
+
+        Ok(())
+    }
+
+    fn after_ravage_damage(&self, game: &mut GameState, land_index: u8, damage: u16, blighted: bool) -> Result<(), StepFailure> {
+        if self.level >= 1 && damage >= 6 {
+            game.log_effect(format_args!("Heavy Mining: 6 or more damage to the land."));
+            add_blight_quietly(game, land_index)?;
+        }
+
+        if self.level >= 5 && blighted {
+            game.log_effect(format_args!("Mining Rush: a town in an adjacent land without a town or city."));
+            let lands = game.table.desc.get_adjacent_lands(land_index).iter()
+                .map(|l| l.index_on_table)
+                .filter(|l| game.table.lands[*l as usize].is_in_play && building_count(&game.table.lands[*l as usize]) == 0)
+                .collect();
+            game.do_effect(ChooseLandDecision { lands, effect: add_town })?;
+        }
+
+        Ok(())
+    }
+
+    fn modify_invader(&self, invader: &mut Invader) {
+        // Fine Steel for Tools and Guns
+        if self.level >= 3 {
+            match invader.kind {
+                InvaderKind::Town => invader.attack = 3,
+                InvaderKind::City => invader.attack = 5,
+                InvaderKind::Explorer => {},
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base::InvaderCard, config::RunConfig, testing::set_up};
+
+    fn invaders_in(game: &GameState, card: InvaderCard) -> usize {
+        game.desc.table.lands.iter()
+            .filter(|l| card.can_target(l))
+            .map(|l| game.table.lands[l.index_on_table as usize].invaders.len())
+            .sum()
+    }
+
+    // A game on boards A and B against the adversary, through its `Init` step.
+    fn initialized_game(adversary: &str, level: u8) -> GameState {
+        let config = RunConfig::parse(&format!("seed: test\ngame:\n  spirits: [river, lightning]\n  boards: [A, B]\n  adversary:\n    name: {}\n    difficulty: {}\n", adversary, level)).unwrap();
+        let mut game = config.make_state().unwrap();
+        game.step().unwrap();
+        game
+    }
+
+    fn count_of(game: &GameState, land_index: u8, kind: InvaderKind) -> usize {
+        game.table.lands[land_index as usize].invaders.iter().filter(|i| i.kind == kind).count()
+    }
+
+    #[test]
+    fn prussia_stacks_the_deck_by_level() {
+        let expected: [&[u8]; 7] = [
+            &STANDARD_INVADER_STAGES,
+            &STANDARD_INVADER_STAGES,
+            &[1, 1, 1, 3, 2, 2, 2, 2, 3, 3, 3, 3],
+            &[1, 1, 3, 2, 2, 2, 2, 3, 3, 3, 3],
+            &[1, 1, 3, 2, 2, 2, 3, 3, 3, 3],
+            &[1, 3, 2, 2, 2, 3, 3, 3, 3],
+            &[3, 2, 2, 2, 3, 3, 3, 3],
+        ];
+        for (level, stages) in expected.iter().enumerate() {
+            let game = initialized_game("brandenburg-prussia", level as u8);
+            let drawn: Vec<u8> = game.invader.draw.iter().rev().map(|c| c.phase()).collect();
+            assert_eq!(drawn, stages.to_vec(), "level {}", level);
+        }
+
+        for (adversary, level) in [("none", 0), ("england", 6), ("sweden", 3)].iter() {
+            let game = initialized_game(adversary, *level);
+            let drawn: Vec<u8> = game.invader.draw.iter().rev().map(|c| c.phase()).collect();
+            assert_eq!(drawn, STANDARD_INVADER_STAGES.to_vec(), "{}", adversary);
+        }
+    }
+
+    #[test]
+    fn adversaries_set_up_on_each_board() {
+        let plain = initialized_game("none", 0);
+
+        let game = initialized_game("brandenburg-prussia", 1);
+        for land_index in land_on_each_board(&game, 3) {
+            assert_eq!(count_of(&game, land_index, InvaderKind::Town), count_of(&plain, land_index, InvaderKind::Town) + 1);
+        }
+        let game = initialized_game("brandenburg-prussia", 0);
+        assert_eq!(game.table.lands.iter().map(|l| l.invaders.len()).sum::<usize>(), plain.table.lands.iter().map(|l| l.invaders.len()).sum::<usize>());
+
+        let game = initialized_game("england", 2);
+        for land_index in land_on_each_board(&game, 1) {
+            assert_eq!(count_of(&game, land_index, InvaderKind::City), count_of(&plain, land_index, InvaderKind::City) + 1);
+        }
+        for land_index in land_on_each_board(&game, 2) {
+            assert_eq!(count_of(&game, land_index, InvaderKind::Town), count_of(&plain, land_index, InvaderKind::Town) + 1);
+        }
+
+        let game = initialized_game("sweden", 2);
+        for (land_index, next_index) in land_on_each_board(&game, 4).into_iter().zip(land_on_each_board(&game, 5)) {
+            assert_eq!(count_of(&game, land_index, InvaderKind::City), count_of(&plain, land_index, InvaderKind::City) + 1);
+
+            let blight = |game: &GameState, l: u8| game.table.lands[l as usize].tokens[TokenKind::Blight];
+            if blight(&plain, land_index) > 0 {
+                assert_eq!(blight(&game, land_index), blight(&plain, land_index) - 1);
+                assert_eq!(blight(&game, next_index), blight(&plain, next_index) + 1);
+            }
+        }
+        let blight_total = |game: &GameState| game.table.lands.iter().map(|l| l.tokens[TokenKind::Blight]).sum::<u8>();
+        assert_eq!(blight_total(&game), blight_total(&plain));
+    }
+
+    #[test]
+    fn independent_resolve_builds_twice_without_fear() {
+        let config = RunConfig::parse("seed: test\ngame:\n  spirits: [river]\n  boards: [A]\n  adversary:\n    name: england\n    difficulty: 6\n").unwrap();
+        let mut game = config.make_state().unwrap();
+        set_up(&mut game);
+        let desc = game.desc.clone();
+
+        // the High Immigration build, in the lands of the city England adds to land #1
+        game.step = GameStep::Turn(0, TurnStep::Invader(InvaderStep::InvaderAction(0, 0)));
+        let card = InvaderCard::Phase1(game.desc.table.lands[land_on_each_board(&game, 1)[0] as usize].kind);
+
+        game.fear.resolved = 1;
+        let before = invaders_in(&game, card);
+        desc.adversary.after_invader_card(&mut game, InvaderActionKind::Build, card).unwrap();
+        assert_eq!(invaders_in(&game, card), before);
+
+        game.fear.resolved = 0;
+        desc.adversary.after_invader_card(&mut game, InvaderActionKind::Build, card).unwrap();
+        assert!(invaders_in(&game, card) > before);
+    }
+}
@@ -0,0 +1,158 @@
+// This file contains copyrighted assets owned by Greater Than Games.
+
+use crate::base::{
+    BlightCardDescription, GameState, StepFailure, InvaderKind,
+    effect::*, decision::*,
+};
+
+
+// Each spirit destroys 1 of its presence.
+fn downward_spiral (game: &mut GameState) -> Result<(), StepFailure> {
+    for spirit_index in 0..game.spirits.len() as u8 {
+        game.do_effect(DestroyPresenceDecision{ spirit_index })?;
+    }
+
+    Ok(())
+}
+
+// Each spirit forgets a power card or destroys 1 of its presence.
+fn memory_fades_to_dust (game: &mut GameState) -> Result<(), StepFailure> {
+    for spirit_index in 0..game.spirits.len() as u8 {
+        game.do_effect(ChooseSpiritEffectDecision{ spirit_index, choices: vec![
+            |game, spirit_index| game.do_effect(ForgetPowerCardDecision{ spirit_index }),
+            |game, spirit_index| game.do_effect(DestroyPresenceDecision{ spirit_index }),
+        ]})?;
+    }
+
+    Ok(())
+}
+
+// Replaces the last invader of `kind` in the land with one of kind `into`.
+fn replace_invader (game: &mut GameState, land_index: u8, kind: InvaderKind, into: InvaderKind) -> Result<(), StepFailure> {
+    let invader_index = game.get_land(land_index)?.invaders.iter()
+        .rposition(|i| i.kind == kind)
+        .ok_or(StepFailure::InternalError("no invader to replace".to_string()))?;
+
+    game.do_effect(RemoveInvaderEffect{ land_index, invader_index, destroyed: false })?;
+    game.do_effect(AddInvaderEffect{ land_index, kind: into, count: 1 })
+}
+
+// A town becomes a city or an explorer becomes a town.
+fn thriving_communities_land (game: &mut GameState, land_index: u8) -> Result<(), StepFailure> {
+    let land = game.get_land(land_index)?;
+    let mut choices: Vec<LandEffect> = Vec::new();
+    if land.invaders.iter().any(|i| i.kind == InvaderKind::Town) {
+        choices.push(|game, land_index| replace_invader(game, land_index, InvaderKind::Town, InvaderKind::City));
+    }
+    if land.invaders.iter().any(|i| i.kind == InvaderKind::Explorer) {
+        choices.push(|game, land_index| replace_invader(game, land_index, InvaderKind::Explorer, InvaderKind::Town));
+    }
+
+    match choices.as_slice() {
+        [only] => only(game, land_index),
+        _ => game.do_effect(ChooseLandEffectDecision{ land_index, choices }),
+    }
+}
+
+// On each board, in 4 different lands with explorers/towns.
+fn thriving_communities (game: &mut GameState) -> Result<(), StepFailure> {
+    for board in 0..game.desc.table.boards.len() as u8 {
+        let lands = game.table.lands.iter()
+            .filter(|l| l.is_in_play && l.desc.parent_board_index == board)
+            .filter(|l| l.invaders.iter().any(|i| i.kind == InvaderKind::Explorer || i.kind == InvaderKind::Town))
+            .map(|l| l.desc.index_on_table)
+            .collect();
+
+        game.do_effect(ChooseDifferentLandsDecision{ lands, count: 4, effect: thriving_communities_land })?;
+    }
+
+    Ok(())
+}
+
+
+pub fn make_blight_cards() -> Vec<BlightCardDescription> {
+    vec![
+        BlightCardDescription {
+            name: "Downward Spiral",
+            still_healthy: false,
+            blight_per_player: 5,
+            immediate: None,
+            each_invader_phase: Some(Box::new(DoSubEffect { effect: downward_spiral })),
+        },
+        BlightCardDescription {
+            name: "Memory Fades to Dust",
+            still_healthy: false,
+            blight_per_player: 4,
+            immediate: None,
+            each_invader_phase: Some(Box::new(DoSubEffect { effect: memory_fades_to_dust })),
+        },
+        BlightCardDescription {
+            name: "Thriving Communities",
+            still_healthy: true,
+            blight_per_player: 4,
+            immediate: Some(Box::new(DoSubEffect { effect: thriving_communities })),
+            each_invader_phase: None,
+        },
+    ]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base::{DecisionChoice, PresenceState, LandKind}, testing::set_up_game};
+
+    #[test]
+    fn downward_spiral_destroys_a_presence() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let land = game.table.lands.iter().position(|l| l.presence[0] > 0).unwrap();
+        game.spirits[0].presence[1] = PresenceState::OnBoard(land as u8);
+        game.table.lands[land].presence[0] += 1;
+
+        downward_spiral(&mut game).unwrap();
+        assert_eq!(game.table.lands[land].presence[0], 1);
+        assert!(game.spirits[0].presence.contains(&PresenceState::Destroyed));
+    }
+
+    #[test]
+    fn memory_fades_to_dust_may_forget_a_power() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let hand = game.spirits[0].deck.hand.len();
+
+        game.choices.extend(vec![DecisionChoice::Choice(0), DecisionChoice::Choice(0)]);
+        memory_fades_to_dust(&mut game).unwrap();
+        assert_eq!(game.spirits[0].deck.hand.len(), hand - 1);
+        assert_eq!(game.spirits[0].deck.forgotten.len(), 1);
+    }
+
+    #[test]
+    fn thriving_communities_upgrades_four_lands() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        for land_index in 0..game.table.lands.len() as u8 {
+            if game.table.lands[land_index as usize].is_in_play && game.desc.table.lands[land_index as usize].kind != LandKind::Ocean {
+                game.do_effect(AddInvaderEffect{ land_index, kind: InvaderKind::Explorer, count: 1 }).unwrap();
+            }
+        }
+        let count = |game: &GameState, kind| game.table.lands.iter()
+            .flat_map(|l| l.invaders.iter())
+            .filter(|i| i.kind == kind)
+            .count();
+        let lands: Vec<usize> = game.table.lands.iter().enumerate()
+            .filter(|(_, l)| l.is_in_play && l.invaders.iter().any(|i| i.kind == InvaderKind::Explorer || i.kind == InvaderKind::Town))
+            .map(|(index, _)| index)
+            .collect();
+        assert!(lands.len() > 4);
+        let before = (count(&game, InvaderKind::Explorer), count(&game, InvaderKind::Town), count(&game, InvaderKind::City));
+
+        // the first 4 lands, upgrading the first kind offered in each
+        game.choices.push_back(DecisionChoice::Sequence(vec![0, 1, 2, 3]));
+        game.choices.extend(vec![DecisionChoice::Choice(0); 4]);
+        thriving_communities(&mut game).unwrap();
+        game.choices.clear();
+
+        let after = (count(&game, InvaderKind::Explorer), count(&game, InvaderKind::Town), count(&game, InvaderKind::City));
+        // each replacement makes one piece bigger
+        assert_eq!(before.0 + before.1 + before.2, after.0 + after.1 + after.2);
+        assert_eq!(after.1 + 2 * after.2, before.1 + 2 * before.2 + 4);
+    }
+}
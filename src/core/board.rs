@@ -157,3 +157,506 @@ pub fn make_board_a() -> BoardDescription {
         lands: lands
     }
 }
+
+pub fn make_board_b() -> BoardDescription {
+    let mut lands = vec![
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Ocean,
+            is_coastal: true,
+            edge_range: Some((2, 8)),
+            index_on_board: 0,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 0,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Wetlands,
+            is_coastal: true,
+            edge_range: Some((8, 11)),
+            index_on_board: 1,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 1,
+
+            index_on_table: 1,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Mountain,
+            is_coastal: true,
+            edge_range: None,
+            index_on_board: 2,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0).map(InvaderKind::City, 1),
+            starting_dahan: 0,
+
+            index_on_table: 2,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Sands,
+            is_coastal: true,
+            edge_range: Some((24, 2)),
+            index_on_board: 3,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 2,
+
+            index_on_table: 3,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Jungle,
+            is_coastal: false,
+            edge_range: None,
+            index_on_board: 4,
+
+            starting_tokens: TokenMap::new(|| 0).map(TokenKind::Blight, 1),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 4,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Sands,
+            is_coastal: false,
+            edge_range: Some((11, 15)),
+            index_on_board: 5,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 5,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Wetlands,
+            is_coastal: false,
+            edge_range: Some((15, 18)),
+            index_on_board: 6,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 1,
+
+            index_on_table: 6,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Mountain,
+            is_coastal: false,
+            edge_range: Some((21, 24)),
+            index_on_board: 7,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 2,
+
+            index_on_table: 7,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Jungle,
+            is_coastal: false,
+            edge_range: Some((18, 21)),
+            index_on_board: 8,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0).map(InvaderKind::Town, 1),
+            starting_dahan: 0,
+
+            index_on_table: 8,
+            parent_board_index: 0,
+        }),
+    ];
+
+    Arc::get_mut(lands.get_mut(0).unwrap()).unwrap().adjacent = vec![1, 2, 3];
+
+    Arc::get_mut(lands.get_mut(1).unwrap()).unwrap().adjacent = vec![0, 2, 4, 5];
+    Arc::get_mut(lands.get_mut(2).unwrap()).unwrap().adjacent = vec![0, 1, 3, 4];
+    Arc::get_mut(lands.get_mut(3).unwrap()).unwrap().adjacent = vec![0, 2, 4, 7];
+
+    Arc::get_mut(lands.get_mut(4).unwrap()).unwrap().adjacent = vec![1, 2, 3, 5, 6, 7];
+    Arc::get_mut(lands.get_mut(5).unwrap()).unwrap().adjacent = vec![1, 4, 6];
+    Arc::get_mut(lands.get_mut(6).unwrap()).unwrap().adjacent = vec![4, 5, 8];
+    Arc::get_mut(lands.get_mut(7).unwrap()).unwrap().adjacent = vec![3, 4, 8];
+    Arc::get_mut(lands.get_mut(8).unwrap()).unwrap().adjacent = vec![6, 7];
+
+    BoardDescription {
+        name: "B",
+        lands,
+    }
+}
+
+pub fn make_board_c() -> BoardDescription {
+    let mut lands = vec![
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Ocean,
+            is_coastal: true,
+            edge_range: Some((2, 8)),
+            index_on_board: 0,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 0,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Jungle,
+            is_coastal: true,
+            edge_range: Some((8, 10)),
+            index_on_board: 1,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 1,
+
+            index_on_table: 1,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Sands,
+            is_coastal: true,
+            edge_range: None,
+            index_on_board: 2,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0).map(InvaderKind::City, 1),
+            starting_dahan: 0,
+
+            index_on_table: 2,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Mountain,
+            is_coastal: true,
+            edge_range: Some((25, 1)),
+            index_on_board: 3,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 2,
+
+            index_on_table: 3,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Jungle,
+            is_coastal: false,
+            edge_range: None,
+            index_on_board: 4,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 4,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Wetlands,
+            is_coastal: false,
+            edge_range: Some((21, 25)),
+            index_on_board: 5,
+
+            starting_tokens: TokenMap::new(|| 0).map(TokenKind::Blight, 1),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 5,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Mountain,
+            is_coastal: false,
+            edge_range: Some((10, 14)),
+            index_on_board: 6,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 1,
+
+            index_on_table: 6,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Sands,
+            is_coastal: false,
+            edge_range: Some((14, 17)),
+            index_on_board: 7,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0).map(InvaderKind::Town, 1),
+            starting_dahan: 0,
+
+            index_on_table: 7,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Wetlands,
+            is_coastal: false,
+            edge_range: Some((17, 21)),
+            index_on_board: 8,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 2,
+
+            index_on_table: 8,
+            parent_board_index: 0,
+        }),
+    ];
+
+    Arc::get_mut(lands.get_mut(0).unwrap()).unwrap().adjacent = vec![1, 2, 3];
+
+    Arc::get_mut(lands.get_mut(1).unwrap()).unwrap().adjacent = vec![0, 2, 4, 6];
+    Arc::get_mut(lands.get_mut(2).unwrap()).unwrap().adjacent = vec![0, 1, 3, 4];
+    Arc::get_mut(lands.get_mut(3).unwrap()).unwrap().adjacent = vec![0, 2, 4, 5];
+
+    Arc::get_mut(lands.get_mut(4).unwrap()).unwrap().adjacent = vec![1, 2, 3, 5, 6, 7];
+    Arc::get_mut(lands.get_mut(5).unwrap()).unwrap().adjacent = vec![3, 4, 7, 8];
+    Arc::get_mut(lands.get_mut(6).unwrap()).unwrap().adjacent = vec![1, 4, 7];
+    Arc::get_mut(lands.get_mut(7).unwrap()).unwrap().adjacent = vec![4, 5, 6, 8];
+    Arc::get_mut(lands.get_mut(8).unwrap()).unwrap().adjacent = vec![5, 7];
+
+    BoardDescription {
+        name: "C",
+        lands,
+    }
+}
+
+pub fn make_board_d() -> BoardDescription {
+    let mut lands = vec![
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Ocean,
+            is_coastal: true,
+            edge_range: Some((2, 8)),
+            index_on_board: 0,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 0,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Wetlands,
+            is_coastal: true,
+            edge_range: Some((8, 12)),
+            index_on_board: 1,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 2,
+
+            index_on_table: 1,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Jungle,
+            is_coastal: true,
+            edge_range: None,
+            index_on_board: 2,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0).map(InvaderKind::City, 1),
+            starting_dahan: 1,
+
+            index_on_table: 2,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Mountain,
+            is_coastal: true,
+            edge_range: Some((25, 1)),
+            index_on_board: 3,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 3,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Sands,
+            is_coastal: false,
+            edge_range: None,
+            index_on_board: 4,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 1,
+
+            index_on_table: 4,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Jungle,
+            is_coastal: false,
+            edge_range: Some((12, 15)),
+            index_on_board: 5,
+
+            starting_tokens: TokenMap::new(|| 0).map(TokenKind::Blight, 1),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 5,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Sands,
+            is_coastal: false,
+            edge_range: Some((22, 25)),
+            index_on_board: 6,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0).map(InvaderKind::Town, 1),
+            starting_dahan: 0,
+
+            index_on_table: 6,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Mountain,
+            is_coastal: false,
+            edge_range: Some((15, 19)),
+            index_on_board: 7,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 2,
+
+            index_on_table: 7,
+            parent_board_index: 0,
+        }),
+        Arc::new(LandDescription {
+            adjacent: Vec::new(),
+            kind: LandKind::Wetlands,
+            is_coastal: false,
+            edge_range: Some((19, 22)),
+            index_on_board: 8,
+
+            starting_tokens: TokenMap::new(|| 0),
+            starting_invaders: InvaderMap::new(0),
+            starting_dahan: 0,
+
+            index_on_table: 8,
+            parent_board_index: 0,
+        }),
+    ];
+
+    Arc::get_mut(lands.get_mut(0).unwrap()).unwrap().adjacent = vec![1, 2, 3];
+
+    Arc::get_mut(lands.get_mut(1).unwrap()).unwrap().adjacent = vec![0, 2, 4, 5];
+    Arc::get_mut(lands.get_mut(2).unwrap()).unwrap().adjacent = vec![0, 1, 3, 4];
+    Arc::get_mut(lands.get_mut(3).unwrap()).unwrap().adjacent = vec![0, 2, 4, 6];
+
+    Arc::get_mut(lands.get_mut(4).unwrap()).unwrap().adjacent = vec![1, 2, 3, 5, 6, 8];
+    Arc::get_mut(lands.get_mut(5).unwrap()).unwrap().adjacent = vec![1, 4, 7, 8];
+    Arc::get_mut(lands.get_mut(6).unwrap()).unwrap().adjacent = vec![3, 4, 8];
+    Arc::get_mut(lands.get_mut(7).unwrap()).unwrap().adjacent = vec![5, 8];
+    Arc::get_mut(lands.get_mut(8).unwrap()).unwrap().adjacent = vec![4, 5, 6, 7];
+
+    BoardDescription {
+        name: "D",
+        lands,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boards() -> Vec<BoardDescription> {
+        vec![make_board_a(), make_board_b(), make_board_c(), make_board_d()]
+    }
+
+    #[test]
+    fn boards_have_an_ocean_and_two_lands_of_each_terrain() {
+        for board in boards() {
+            assert_eq!(board.lands.len(), 9, "board {}", board.name);
+            for (index, land) in board.lands.iter().enumerate() {
+                assert_eq!(land.index_on_board as usize, index, "board {}", board.name);
+                assert_eq!(land.index_on_table as usize, index, "board {}", board.name);
+            }
+
+            assert!(board.lands[0].kind == LandKind::Ocean, "board {}", board.name);
+            for kind in [LandKind::Mountain, LandKind::Wetlands, LandKind::Jungle, LandKind::Sands] {
+                assert_eq!(board.lands.iter().filter(|l| l.kind == kind).count(), 2, "board {}", board.name);
+            }
+        }
+    }
+
+    #[test]
+    fn board_adjacency_is_symmetric() {
+        for board in boards() {
+            for land in board.lands.iter() {
+                assert!(!land.adjacent.contains(&land.index_on_board), "board {} land {}", board.name, land.index_on_board);
+                for adjacent in land.adjacent.iter() {
+                    assert!(board.lands[*adjacent as usize].adjacent.contains(&land.index_on_board),
+                        "board {} land {} and {}", board.name, land.index_on_board, adjacent);
+                }
+                // the coastal lands are the ones by the ocean
+                if land.kind != LandKind::Ocean {
+                    assert_eq!(land.is_coastal, land.adjacent.contains(&0), "board {} land {}", board.name, land.index_on_board);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lands_along_the_edge_are_adjacent_where_they_meet() {
+        for board in boards() {
+            for a in board.lands.iter() {
+                for b in board.lands.iter() {
+                    if let (Some((_, a_end)), Some((b_start, _))) = (a.edge_range, b.edge_range) {
+                        if a.index_on_board != b.index_on_board && a_end == b_start {
+                            assert!(a.adjacent.contains(&b.index_on_board),
+                                "board {} land {} and {}", board.name, a.index_on_board, b.index_on_board);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,72 +1,252 @@
 // This file contains copyrighted assets owned by Greater Than Games.
 
+use std::{
+    cmp::{min},
+};
+
 use crate::base::{
     FearCardDescription,
+    InvaderActionKind,
+    board::{LandState},
+    decision::*,
     effect::*,
+    game::{GameState},
+    piece::*,
+    step::{StepFailure},
 };
 
 
+fn count_of(land: &LandState, kind: InvaderKind) -> usize {
+    land.invaders.iter().filter(|i| i.kind == kind).count()
+}
+
+fn building_count(land: &LandState) -> usize {
+    land.invaders.iter().filter(|i| i.is_building()).count()
+}
+
+fn has_presence(land: &LandState) -> bool {
+    land.presence.0.iter().any(|p| *p != 0)
+}
+
+fn is_sacred_site(land: &LandState) -> bool {
+    land.presence.0.iter().any(|p| *p >= 2)
+}
+
+fn is_explorer(invader: &Invader) -> bool {
+    invader.kind == InvaderKind::Explorer
+}
+
+fn is_explorer_or_town(invader: &Invader) -> bool {
+    invader.kind != InvaderKind::City
+}
+
+fn has_explorer(land: &LandState) -> bool {
+    land.invaders.iter().any(is_explorer)
+}
+
+fn has_explorer_or_town(land: &LandState) -> bool {
+    land.invaders.iter().any(is_explorer_or_town)
+}
+
+fn health_worth(invaders: &[Invader]) -> u16 {
+    invaders.iter().map(|i| i.health_max as u16).sum()
+}
+
+fn skip(land_index: u8, action: InvaderActionKind) -> Box<dyn Effect> {
+    Box::new(SkipInvaderActionEffect{land_index, action, buildings: InvaderMap::new(true)})
+}
+
+fn each_spirit_chooses(filter: fn(&GameState, &LandState) -> bool, different: bool, effect: LandEffect) -> Box<dyn Effect> {
+    Box::new(EachSpiritChoosesLandDecision{filter, different, effect, chosen: Vec::new()})
+}
+
+fn remove_invaders(game: &mut GameState, land_index: u8, allowed: fn(&LandState, &[Invader]) -> bool) -> Result<(), StepFailure> {
+    game.do_effect(RemoveInvadersDecision{land_index, allowed, may: false, downgrade: false})
+}
+
+fn destroy_invaders(game: &mut GameState, land_index: u8, kind: InvaderKind, count: usize) -> Result<(), StepFailure> {
+    for _ in 0..count {
+        let invader_index = game.get_land(land_index)?.invaders.iter()
+            .position(|i| i.kind == kind)
+            .ok_or(StepFailure::InternalError("no invader to destroy".to_string()))?;
+        game.do_effect(RemoveInvaderEffect{land_index, invader_index, destroyed: true})?;
+    }
+    game.do_effect(AvengeDestroyedEffect{land_index})
+}
+
+fn defend_coastal(game: &mut GameState, defense: u16) -> Result<(), StepFailure> {
+    let lands: Vec<u8> = game.table.lands.iter()
+        .filter(|l| l.is_in_play && l.desc.is_coastal)
+        .map(|l| l.desc.index_on_table)
+        .collect();
+    for land_index in lands {
+        game.do_effect(PersistDefenseEffect{land_index, defense})?;
+    }
+    Ok(())
+}
+
+// Each town destroys an explorer and each city `per_city` explorers in their land.
+fn scapegoats(game: &mut GameState, per_city: usize) -> Result<(), StepFailure> {
+    for land_index in 0..game.table.lands.len() as u8 {
+        let land = game.get_land(land_index)?;
+        let count = min(count_of(land, InvaderKind::Explorer), count_of(land, InvaderKind::Town) + per_city * count_of(land, InvaderKind::City));
+        destroy_invaders(game, land_index, InvaderKind::Explorer, count)?;
+    }
+    Ok(())
+}
+
+// The land next to `land_index` explorers are pushed to for Seek Safety, the one with the most
+// buildings as long as it has more than where they come from.
+fn safer_land(game: &GameState, land_index: u8) -> Option<u8> {
+    let buildings = building_count(game.get_land(land_index).ok()?);
+    game.get_adjacent_lands(land_index).ok()?.into_iter()
+        .filter(|l| l.is_in_play && building_count(l) > buildings)
+        .max_by_key(|l| (building_count(l), std::cmp::Reverse(l.desc.index_on_table)))
+        .map(|l| l.desc.index_on_table)
+}
+
+fn remove_two_explorers_or_a_town(land: &LandState, removed: &[Invader]) -> bool {
+    (removed.len() == min(2, count_of(land, InvaderKind::Explorer)) && removed.iter().all(is_explorer))
+        || (removed.len() == 1 && removed[0].kind == InvaderKind::Town)
+}
+
+
 pub fn make_fear_cards() -> Vec<FearCardDescription> {
     vec![
         FearCardDescription {
             name: "Avoid the Dahan",
-            effect_1: Box::new(NotImplementedEffect { what: "Avoid the Dahan 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Avoid the Dahan 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Avoid the Dahan 3" }),
+            effect_1: Box::new(ForAllLandsDoEffect{
+                filter: |l| l.dahan.len() >= 2,
+                effect: |l| skip(l.desc.index_on_table, InvaderActionKind::Explore),
+            }),
+            effect_2: Box::new(ForAllLandsDoEffect{
+                filter: |l| l.dahan.len() > building_count(l),
+                effect: |l| skip(l.desc.index_on_table, InvaderActionKind::Build),
+            }),
+            effect_3: Box::new(ForAllLandsDoEffect{
+                filter: |l| !l.dahan.is_empty(),
+                effect: |l| skip(l.desc.index_on_table, InvaderActionKind::Build),
+            }),
         },
         FearCardDescription {
             name: "Belief Takes Root",
             effect_1: Box::new(ForAllLandsDoEffect{
-                filter: |l| l.presence.0.iter().any(|p| *p != 0),
+                filter: has_presence,
                 effect: |l| Box::new(PersistDefenseEffect{land_index: l.desc.index_on_table, defense: 2}),
             }),
-            effect_2: Box::new(NotImplementedEffect { what: "Belief Takes Root 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Belief Takes Root 3" }),
+            effect_2: Box::new(DoSubEffect{effect: |game| {
+                game.do_effect(ForAllLandsDoEffect{
+                    filter: has_presence,
+                    effect: |l| Box::new(PersistDefenseEffect{land_index: l.desc.index_on_table, defense: 2}),
+                })?;
+
+                // 1 energy per sacred site in lands with invaders
+                for spirit_index in 0..game.spirits.len() as u8 {
+                    let energy = game.table.lands.iter()
+                        .filter(|l| l.presence[spirit_index] >= 2 && !l.invaders.is_empty())
+                        .count() as u8;
+                    if energy != 0 {
+                        game.do_effect(GenerateEnergyEffect{spirit_index, energy})?;
+                    }
+                }
+                Ok(())
+            }}),
+            // up to 2 health of invaders per presence (of any spirit, as if it was the player's)
+            effect_3: each_spirit_chooses(|_, l| has_presence(l) && !l.invaders.is_empty(), true, |game, land_index| {
+                game.do_effect(RemoveInvadersDecision{
+                    land_index,
+                    allowed: |land, removed| health_worth(removed) <= 2 * land.presence.0.iter().map(|p| *p as u16).sum::<u16>(),
+                    may: true,
+                    downgrade: false,
+                })
+            }),
         },
+        // the lands chosen are the ones with invaders, the others would be wasted
         FearCardDescription {
             name: "Dahan Enheartened",
-            effect_1: Box::new(NotImplementedEffect { what: "Dahan Enheartened 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Dahan Enheartened 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Dahan Enheartened 3" }),
+            effect_1: each_spirit_chooses(|_, l| !l.invaders.is_empty(), false, |game, land_index| {
+                game.do_effect(ChooseLandEffectDecision{land_index, choices: vec![
+                    |game, land_index| game.do_effect(PushDecision{land_index, count: 1, may: true, kinds: vec![PieceKind::Dahan]}),
+                    |game, land_index| game.do_effect(GatherDecision{land_index, count: 1, may: true, kinds: vec![PieceKind::Dahan]}),
+                ]})
+            }),
+            effect_2: each_spirit_chooses(|_, l| !l.invaders.is_empty(), true, |game, land_index| {
+                game.do_effect(GatherDecision{land_index, count: 2, may: true, kinds: vec![PieceKind::Dahan]})?;
+                if !game.get_land(land_index)?.dahan.is_empty() {
+                    game.do_effect(DoDamageToInvadersDecision{land_index, damage: 1})?;
+                }
+                Ok(())
+            }),
+            effect_3: each_spirit_chooses(|_, l| !l.invaders.is_empty(), true, |game, land_index| {
+                game.do_effect(GatherDecision{land_index, count: 2, may: true, kinds: vec![PieceKind::Dahan]})?;
+                let damage = game.get_land(land_index)?.dahan.len() as u16;
+                game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+            }),
         },
         FearCardDescription {
             name: "Dahan Raid",
-            effect_1: Box::new(NotImplementedEffect { what: "Dahan Raid 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Dahan Raid 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Dahan Raid 3" }),
+            effect_1: each_spirit_chooses(|_, l| !l.dahan.is_empty() && !l.invaders.is_empty(), true, |game, land_index| {
+                game.do_effect(DoDamageToInvadersDecision{land_index, damage: 1})
+            }),
+            effect_2: each_spirit_chooses(|_, l| !l.dahan.is_empty() && !l.invaders.is_empty(), true, |game, land_index| {
+                let damage = game.get_land(land_index)?.dahan.len() as u16;
+                game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+            }),
+            effect_3: each_spirit_chooses(|_, l| !l.dahan.is_empty() && !l.invaders.is_empty(), true, |game, land_index| {
+                let damage = 2 * game.get_land(land_index)?.dahan.len() as u16;
+                game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+            }),
         },
         FearCardDescription {
             name: "Dahan on their Guard",
             effect_1: Box::new(ForAllLandsDoEffect{
-                filter: |l| l.dahan.len() != 0, // optimization...
+                filter: |l| !l.dahan.is_empty(), // optimization...
                 effect: |l| Box::new(PersistDefenseEffect{land_index: l.desc.index_on_table, defense: l.dahan.len() as u16}),
             }),
             effect_2: Box::new(ForAllLandsDoEffect{
-                filter: |l| l.dahan.len() != 0, // optimization...
+                filter: |l| !l.dahan.is_empty(), // optimization...
                 effect: |l| Box::new(PersistDefenseEffect{land_index: l.desc.index_on_table, defense: 1 + l.dahan.len() as u16}),
             }),
             effect_3: Box::new(ForAllLandsDoEffect{
-                filter: |l| l.dahan.len() != 0, // optimization...
+                filter: |l| !l.dahan.is_empty(), // optimization...
                 effect: |l| Box::new(PersistDefenseEffect{land_index: l.desc.index_on_table, defense: 2 * l.dahan.len() as u16}),
             }),
         },
         FearCardDescription {
             name: "Emigration Accelerates",
-            effect_1: Box::new(NotImplementedEffect { what: "Emigration Accelerates 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Emigration Accelerates 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Emigration Accelerates 3" }),
+            effect_1: each_spirit_chooses(|_, l| l.desc.is_coastal && has_explorer(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer(&r[0]))
+            }),
+            effect_2: each_spirit_chooses(|_, l| l.desc.is_coastal && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
+            effect_3: each_spirit_chooses(|_, l| has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
         },
         FearCardDescription {
             name: "Fear of the Unseen",
-            effect_1: Box::new(NotImplementedEffect { what: "Fear of the Unseen 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Fear of the Unseen 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Fear of the Unseen 3" }),
+            effect_1: each_spirit_chooses(|_, l| is_sacred_site(l) && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
+            effect_2: each_spirit_chooses(|_, l| has_presence(l) && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
+            effect_3: each_spirit_chooses(|_, l| has_presence(l) && !l.invaders.is_empty(), false, |game, land_index| {
+                remove_invaders(game, land_index, |l, r| r.len() == 1 && (is_explorer_or_town(&r[0]) || is_sacred_site(l)))
+            }),
         },
         FearCardDescription {
             name: "Isolation",
-            effect_1: Box::new(NotImplementedEffect { what: "Isolation 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Isolation 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Isolation 3" }),
+            effect_1: each_spirit_chooses(|_, l| l.invaders.len() == 1 && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
+            effect_2: each_spirit_chooses(|_, l| l.invaders.len() <= 2 && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
+            effect_3: each_spirit_chooses(|_, l| l.invaders.len() <= 2 && !l.invaders.is_empty(), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1)
+            }),
         },
         FearCardDescription {
             name: "Overseas Trade Seems Safer",
@@ -74,44 +254,136 @@ pub fn make_fear_cards() -> Vec<FearCardDescription> {
                 filter: |l| l.desc.is_coastal,
                 effect: |l| Box::new(PersistDefenseEffect{land_index: l.desc.index_on_table, defense: 3}),
             }),
-            effect_2: Box::new(NotImplementedEffect { what: "Overseas Trade Seems Safer 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Overseas Trade Seems Safer 3" }),
+            effect_2: Box::new(DoSubEffect{effect: |game| {
+                defend_coastal(game, 6)?;
+                game.do_effect(ForAllLandsDoEffect{
+                    filter: |l| l.is_in_play && l.desc.is_coastal,
+                    effect: |l| Box::new(SkipInvaderActionEffect{
+                        land_index: l.desc.index_on_table,
+                        action: InvaderActionKind::Build,
+                        buildings: InvaderMap::new(false).map(InvaderKind::City, true),
+                    }),
+                })
+            }}),
+            effect_3: Box::new(DoSubEffect{effect: |game| {
+                defend_coastal(game, 9)?;
+                game.do_effect(ForAllLandsDoEffect{
+                    filter: |l| l.is_in_play && l.desc.is_coastal,
+                    effect: |l| skip(l.desc.index_on_table, InvaderActionKind::Build),
+                })
+            }}),
         },
         FearCardDescription {
             name: "Retreat!",
-            effect_1: Box::new(NotImplementedEffect { what: "Retreat! 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Retreat! 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Retreat! 3" }),
+            effect_1: each_spirit_chooses(|_, l| !l.desc.is_coastal && has_explorer(l), false, |game, land_index| {
+                game.do_effect(PushDecision{land_index, count: 2, may: true,
+                    kinds: vec![PieceKind::Invader(InvaderKind::Explorer)]})
+            }),
+            effect_2: each_spirit_chooses(|_, l| !l.desc.is_coastal && has_explorer_or_town(l), false, |game, land_index| {
+                game.do_effect(PushDecision{land_index, count: 3, may: true,
+                    kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+            }),
+            effect_3: each_spirit_chooses(|_, l| has_explorer_or_town(l), false, |game, land_index| {
+                let count = game.get_land(land_index)?.invaders.iter().filter(|i| is_explorer_or_town(i)).count() as u8;
+                game.do_effect(PushDecision{land_index, count, may: true,
+                    kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+            }),
         },
         FearCardDescription {
             name: "Scapegoats",
-            effect_1: Box::new(NotImplementedEffect { what: "Scapegoats 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Scapegoats 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Scapegoats 3" }),
+            effect_1: Box::new(DoSubEffect{effect: |game| scapegoats(game, 0)}),
+            effect_2: Box::new(DoSubEffect{effect: |game| scapegoats(game, 2)}),
+            effect_3: Box::new(DoSubEffect{effect: |game| {
+                for land_index in 0..game.table.lands.len() as u8 {
+                    let land = game.get_land(land_index)?;
+                    if building_count(land) == 0 {
+                        continue;
+                    }
+
+                    let explorers = count_of(land, InvaderKind::Explorer);
+                    let towns = min(count_of(land, InvaderKind::Town), count_of(land, InvaderKind::City));
+                    destroy_invaders(game, land_index, InvaderKind::Explorer, explorers)?;
+                    destroy_invaders(game, land_index, InvaderKind::Town, towns)?;
+                }
+                Ok(())
+            }}),
         },
         FearCardDescription {
             name: "Seek Safety",
-            effect_1: Box::new(NotImplementedEffect { what: "Seek Safety 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Seek Safety 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Seek Safety 3" }),
+            // the explorer goes to the safest land next to it, rather than any safer one
+            effect_1: each_spirit_chooses(|game, l| has_explorer(l) && safer_land(game, l.desc.index_on_table).is_some(), false, |game, land_index| {
+                let to_land_index = safer_land(game, land_index)
+                    .ok_or(StepFailure::InternalError("no safer land".to_string()))?;
+                game.do_effect(MovePieceEffect{
+                    from_land_index: land_index,
+                    to_land_index,
+                    kind: PieceKind::Invader(InvaderKind::Explorer),
+                    index: 0,
+                })
+            }),
+            effect_2: each_spirit_chooses(|game, l| {
+                    let gathers_towns = count_of(l, InvaderKind::City) != 0;
+                    building_count(l) != 0 && game.get_adjacent_lands(l.desc.index_on_table).ok().unwrap().iter()
+                        .any(|a| a.is_in_play && a.invaders.iter().any(|i| is_explorer(i) || (gathers_towns && i.kind == InvaderKind::Town)))
+                }, false, |game, land_index| {
+                let kinds = if count_of(game.get_land(land_index)?, InvaderKind::City) != 0 {
+                    vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]
+                } else {
+                    vec![PieceKind::Invader(InvaderKind::Explorer)]
+                };
+                game.do_effect(GatherDecision{land_index, count: 1, may: true, kinds})
+            }),
+            effect_3: each_spirit_chooses(|_, l| count_of(l, InvaderKind::City) == 0 && !l.invaders.is_empty(), false, |game, land_index| {
+                game.do_effect(RemoveInvadersDecision{land_index, allowed: |_, r| health_worth(r) <= 3, may: true, downgrade: false})
+            }),
         },
         FearCardDescription {
             name: "Tall Tales of Savagery",
-            effect_1: Box::new(NotImplementedEffect { what: "Tall Tales of Savagery 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Tall Tales of Savagery 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Tall Tales of Savagery 3" }),
+            effect_1: each_spirit_chooses(|_, l| !l.dahan.is_empty() && has_explorer(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer(&r[0]))
+            }),
+            effect_2: each_spirit_chooses(|_, l| !l.dahan.is_empty() && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, remove_two_explorers_or_a_town)
+            }),
+            effect_3: Box::new(DoSubEffect{effect: |game| {
+                game.do_effect(ForAllLandsDoEffect{
+                    filter: |l| !l.dahan.is_empty() && has_explorer_or_town(l),
+                    effect: |l| Box::new(RemoveInvadersDecision{land_index: l.desc.index_on_table,
+                        allowed: remove_two_explorers_or_a_town, may: false, downgrade: false}),
+                })?;
+                game.do_effect(ForAllLandsDoEffect{
+                    filter: |l| l.dahan.len() >= 2 && count_of(l, InvaderKind::City) != 0,
+                    effect: |l| Box::new(RemoveInvadersDecision{land_index: l.desc.index_on_table,
+                        allowed: |_, r| r.len() == 1 && r[0].kind == InvaderKind::City, may: false, downgrade: false}),
+                })
+            }}),
         },
         FearCardDescription {
             name: "Trade Suffers",
-            effect_1: Box::new(NotImplementedEffect { what: "Trade Suffers 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Trade Suffers 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Trade Suffers 3" }),
+            effect_1: Box::new(ForAllLandsDoEffect{
+                filter: |l| count_of(l, InvaderKind::City) != 0,
+                effect: |l| skip(l.desc.index_on_table, InvaderActionKind::Build),
+            }),
+            effect_2: each_spirit_chooses(|_, l| l.desc.is_coastal && count_of(l, InvaderKind::Town) != 0, false, |game, land_index| {
+                game.do_effect(RemoveInvadersDecision{land_index, may: true, downgrade: true,
+                    allowed: |_, r| r.len() <= 1 && r.iter().all(|i| i.kind == InvaderKind::Town)})
+            }),
+            effect_3: each_spirit_chooses(|_, l| l.desc.is_coastal && building_count(l) != 0, false, |game, land_index| {
+                game.do_effect(RemoveInvadersDecision{land_index, may: true, downgrade: true,
+                    allowed: |_, r| r.len() <= 1 && r.iter().all(|i| i.is_building())})
+            }),
         },
         FearCardDescription {
             name: "Wary of the Interior",
-            effect_1: Box::new(NotImplementedEffect { what: "Wary of the Interior 1" }),
-            effect_2: Box::new(NotImplementedEffect { what: "Wary of the Interior 2" }),
-            effect_3: Box::new(NotImplementedEffect { what: "Wary of the Interior 3" }),
+            effect_1: each_spirit_chooses(|_, l| !l.desc.is_coastal && has_explorer(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer(&r[0]))
+            }),
+            effect_2: each_spirit_chooses(|_, l| !l.desc.is_coastal && has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
+            effect_3: each_spirit_chooses(|_, l| has_explorer_or_town(l), false, |game, land_index| {
+                remove_invaders(game, land_index, |_, r| r.len() == 1 && is_explorer_or_town(&r[0]))
+            }),
         },
     ]
 }
@@ -1,6 +1,261 @@
+use std::{
+    cmp::{min},
+};
+
+use crate::base::{
+    GameState, StepFailure, ElementMap,
+    LandState, InvaderKind, PieceKind,
+    effect::*, decision::*,
+};
 
 mod minor;
 mod major;
 
 pub use minor::make_minor_power_cards;
-pub use major::make_major_power_cards;
\ No newline at end of file
+pub use major::make_major_power_cards;
+
+
+// Whether the spirit using the power has the elements of a threshold ("If you have ...").
+fn has_elements(game: &GameState, threshold: ElementMap<u8>) -> Result<bool, StepFailure> {
+    let spirit_index = game.get_power_usage()?.using_spirit_index;
+    Ok(game.get_spirit(spirit_index)?.elements.meets(&threshold))
+}
+
+fn count_of(land: &LandState, kind: InvaderKind) -> usize {
+    land.invaders.iter().filter(|i| i.kind == kind).count()
+}
+
+fn destroy_invaders(game: &mut GameState, land_index: u8, kind: InvaderKind, count: usize) -> Result<(), StepFailure> {
+    let count = min(count, count_of(game.get_land(land_index)?, kind));
+    for _ in 0..count {
+        let invader_index = game.get_land(land_index)?.invaders.iter()
+            .rposition(|i| i.kind == kind)
+            .ok_or(StepFailure::InternalError("no invader to destroy".to_string()))?;
+        game.do_effect(RemoveInvaderEffect{land_index, invader_index, destroyed: true})?;
+    }
+    game.do_effect(AvengeDestroyedEffect{land_index})
+}
+
+fn destroy_dahan(game: &mut GameState, land_index: u8, count: usize) -> Result<(), StepFailure> {
+    let count = min(count, game.get_land(land_index)?.dahan.len());
+    for _ in 0..count {
+        let dahan_index = game.get_land(land_index)?.dahan.len() - 1;
+        game.do_effect(RemoveDahanEffect{land_index, dahan_index, destroyed: true})?;
+    }
+    game.do_effect(AvengeDestroyedEffect{land_index})
+}
+
+// Pushes up to `count` dahan out of a land, giving the lands they went to with how many went there.
+fn push_dahan(game: &mut GameState, land_index: u8, count: u8) -> Result<Vec<(u8, u16)>, StepFailure> {
+    let before: Vec<(u8, usize)> = game.get_adjacent_lands(land_index)?.iter()
+        .map(|l| (l.desc.index_on_table, l.dahan.len()))
+        .collect();
+
+    game.do_effect(PushDecision{land_index, count, may: true, kinds: vec![PieceKind::Dahan]})?;
+
+    let mut res = Vec::new();
+    for (adjacent_index, dahan) in before {
+        let now = game.get_land(adjacent_index)?.dahan.len();
+        if now > dahan {
+            res.push((adjacent_index, (now - dahan) as u16));
+        }
+    }
+    Ok(res)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{DecisionChoice, PowerTarget, PowerUsage, PresenceState, Element, Invader, Dahan, LandKind, TokenKind},
+        testing::set_up_game,
+    };
+
+    // Uses a core power card as spirit 0, with the given choices.
+    fn use_power(game: &mut GameState, name: &str, target: PowerTarget, choices: Vec<DecisionChoice>) {
+        let card = make_major_power_cards().into_iter().chain(make_minor_power_cards())
+            .find(|c| c.name == name)
+            .unwrap();
+        game.choices.extend(choices);
+        game.power_usages.push(PowerUsage{target, using_spirit_index: 0, src_land_index: None});
+        game.do_effect_box(card.box_clone()).unwrap();
+        game.power_usages.pop();
+        assert!(game.choices.is_empty());
+    }
+
+    // An inland land of the game and one of the inland lands next to it.
+    fn inland_pair(game: &GameState) -> (u8, u8) {
+        let inland = |l: &u8| game.table.lands[*l as usize].is_in_play && !game.desc.table.lands[*l as usize].is_coastal
+            && game.desc.table.lands[*l as usize].kind != LandKind::Ocean;
+        let land = (0..game.table.lands.len() as u8).find(|l| inland(l)).unwrap();
+        let adjacent = *game.desc.table.lands[land as usize].adjacent.iter().find(|l| inland(l)).unwrap();
+        (land, adjacent)
+    }
+
+    #[test]
+    fn entwined_power_shares_the_drafted_cards() {
+        let mut game = set_up_game(&["river", "lightning"], &["A", "B"]);
+        game.spirits[0].elements = ElementMap::new(0).map(Element::Water, 2).map(Element::Plant, 4);
+        let (hand, other_hand) = (game.spirits[0].deck.hand.len(), game.spirits[1].deck.hand.len());
+        let (energy, other_energy) = (game.spirits[0].energy, game.spirits[1].energy);
+
+        // minor, they draft, you draft, you gift nothing, they gift their first card
+        use_power(&mut game, "Entwined Power", PowerTarget::Spirit(1), vec![
+            DecisionChoice::Choice(0), DecisionChoice::Choice(0), DecisionChoice::Choice(0),
+            DecisionChoice::Choice(0), DecisionChoice::Choice(1)]);
+        assert!(game.spirits[0].entwined_with[1] && game.spirits[1].entwined_with[0]);
+        assert_eq!(game.spirits[0].deck.hand.len(), hand + 2);
+        assert_eq!(game.spirits[1].deck.hand.len(), other_hand);
+        assert_eq!(game.spirits[0].energy, energy + 3);
+        assert_eq!(game.spirits[1].energy, other_energy + 3);
+        assert_eq!(game.minor_powers.pending.len(), 0);
+
+        game.spirits[0].time_passes();
+        assert!(!game.spirits[0].entwined_with[1]);
+    }
+
+    #[test]
+    fn infinite_vitality_protects_dahan_in_the_target_spirits_lands() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let land = game.table.lands.iter().position(|l| l.presence[0] > 0).unwrap() as u8;
+        for l in game.table.lands.iter_mut() {
+            l.tokens[TokenKind::Blight] = 0;
+        }
+        game.table.lands[land as usize].tokens[TokenKind::Blight] = 1;
+        game.table.lands[land as usize].dahan = vec![Dahan::new()];
+
+        use_power(&mut game, "Infinite Vitality", PowerTarget::Spirit(0), vec![]);
+        assert_eq!(game.table.lands[land as usize].tokens[TokenKind::Blight], 0);
+
+        // 6 health
+        game.do_effect(DoDamageToDahanDecision{land_index: land, damage: 5, efficent: true}).unwrap();
+        assert_eq!(game.table.lands[land as usize].dahan.len(), 1);
+        assert_eq!(game.table.lands[land as usize].dahan[0].health_cur, 1);
+
+        game.spirits[0].elements = ElementMap::new(0).map(Element::Earth, 4);
+        use_power(&mut game, "Infinite Vitality", PowerTarget::Spirit(0), vec![]);
+        game.do_effect(DoDamageToDahanDecision{land_index: land, damage: 5, efficent: true}).unwrap();
+        destroy_dahan(&mut game, land, 1).unwrap();
+        assert_eq!(game.table.lands[land as usize].dahan.len(), 1);
+    }
+
+    #[test]
+    fn vengeance_of_the_dead_damages_after_each_destroy() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let (land, _) = inland_pair(&game);
+        game.table.lands[land as usize].invaders = vec![Invader::new(InvaderKind::Town), Invader::new(InvaderKind::Explorer)];
+
+        use_power(&mut game, "Vengeance of the Dead", PowerTarget::Land(land), vec![]);
+        assert_eq!(game.table.lands[land as usize].vengeance, 1);
+
+        // the town, then the explorer by its vengeance
+        game.choices.extend([DecisionChoice::Damage(vec![2, 0]), DecisionChoice::Damage(vec![1])]);
+        game.do_effect(DoDamageToInvadersDecision{land_index: land, damage: 2}).unwrap();
+        assert!(game.choices.is_empty());
+        assert!(game.table.lands[land as usize].invaders.is_empty());
+        assert_eq!(game.table.lands[land as usize].vengeance_owed, 0);
+    }
+
+    #[test]
+    fn vengeance_of_the_dead_spreads_to_adjacent_lands() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let (land, to) = inland_pair(&game);
+        game.spirits[0].elements = ElementMap::new(0).map(Element::Animal, 3);
+        for adjacent in game.desc.table.lands[land as usize].adjacent.clone() {
+            game.table.lands[adjacent as usize].invaders = Vec::new();
+        }
+        game.table.lands[land as usize].invaders = vec![Invader::new(InvaderKind::Town)];
+        game.table.lands[to as usize].invaders = vec![Invader::new(InvaderKind::Explorer)];
+
+        use_power(&mut game, "Vengeance of the Dead", PowerTarget::Land(land), vec![]);
+        game.choices.extend([DecisionChoice::Damage(vec![2]), DecisionChoice::Damage(vec![1])]);
+        game.do_effect(DoDamageToInvadersDecision{land_index: land, damage: 2}).unwrap();
+        assert!(game.choices.is_empty());
+        assert!(game.table.lands[to as usize].invaders.is_empty());
+    }
+
+    #[test]
+    fn veil_the_nights_hunt_damages_different_invaders() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let (land, _) = inland_pair(&game);
+        game.table.lands[land as usize].dahan = vec![Dahan::new(); 2];
+        game.table.lands[land as usize].invaders = vec![
+            Invader::new(InvaderKind::Explorer), Invader::new(InvaderKind::Explorer), Invader::new(InvaderKind::Town)];
+
+        let decision = DoDamageToDifferentInvadersDecision{land_index: land, count: 2};
+        let choices = decision.valid_choices(&game);
+        assert_eq!(choices.len(), 2);
+        assert!(choices.iter().any(|c| matches!(c, DecisionChoice::Damage(d) if *d == [1, 0, 1])));
+
+        use_power(&mut game, "Veil the Night's Hunt", PowerTarget::Land(land),
+            vec![DecisionChoice::Choice(0), DecisionChoice::Damage(vec![1, 0, 1])]);
+        let invaders = &game.table.lands[land as usize].invaders;
+        assert_eq!(invaders.len(), 2);
+        assert!(invaders[1].kind == InvaderKind::Town);
+        assert_eq!(invaders[1].health_cur, 1);
+    }
+
+    #[test]
+    fn reaching_grasp_extends_range_for_the_turn() {
+        let mut game = set_up_game(&["river"], &["A"]);
+
+        use_power(&mut game, "Reaching Grasp", PowerTarget::Spirit(0), vec![]);
+        assert_eq!(game.spirits[0].extra_range, 2);
+
+        game.spirits[0].time_passes();
+        assert_eq!(game.spirits[0].extra_range, 0);
+    }
+
+    #[test]
+    fn powerstorm_lets_the_target_repeat_powers() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let energy = game.spirits[0].energy;
+
+        use_power(&mut game, "Powerstorm", PowerTarget::Spirit(0), vec![]);
+        assert_eq!(game.spirits[0].energy, energy + 3);
+        assert_eq!(game.spirits[0].may_repeat_powers, 1);
+    }
+
+    #[test]
+    fn wrap_in_wings_of_sunlight_may_move_fewer() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let (land, to) = inland_pair(&game);
+        game.table.lands[land as usize].dahan = vec![Dahan::new(); 3];
+        let dahan = game.table.lands[to as usize].dahan.len();
+
+        use_power(&mut game, "Wrap in Wings of Sunlight", PowerTarget::Land(land),
+            vec![DecisionChoice::TargetLand{target_land: to, source_land: to}, DecisionChoice::Choice(1)]);
+        assert_eq!(game.table.lands[land as usize].dahan.len(), 2);
+        assert_eq!(game.table.lands[to as usize].dahan.len(), dahan + 1);
+    }
+
+    #[test]
+    fn vigor_of_the_breaking_dawn_damages_where_dahan_are_pushed() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let (land, to) = inland_pair(&game);
+        game.spirits[0].elements = ElementMap::new(0).map(Element::Sun, 3).map(Element::Animal, 2);
+        game.table.lands[land as usize].dahan = vec![Dahan::new()];
+        game.table.lands[land as usize].invaders = Vec::new();
+        game.table.lands[to as usize].invaders = vec![Invader::new(InvaderKind::Town)];
+
+        use_power(&mut game, "Vigor of the Breaking Dawn", PowerTarget::Land(land),
+            vec![DecisionChoice::AreaPieceSequence(vec![(to, PieceKind::Dahan, 0)]), DecisionChoice::Damage(vec![2])]);
+        assert!(game.table.lands[land as usize].dahan.is_empty());
+        assert!(game.table.lands[to as usize].invaders.is_empty());
+    }
+
+    #[test]
+    fn indomitable_claim_adds_presence_to_the_target() {
+        let mut game = set_up_game(&["river"], &["A"]);
+        let (land, _) = inland_pair(&game);
+        let presence = game.table.lands[land as usize].presence[0];
+        let source_presence = game.spirits[0].presence.iter()
+            .position(|p| matches!(p, PresenceState::OnTrack(_)))
+            .unwrap() as u8;
+
+        use_power(&mut game, "Indomitable Claim", PowerTarget::Land(land),
+            vec![DecisionChoice::PlacePresence{spirit: 0, target_land: land, source_presence}]);
+        assert_eq!(game.table.lands[land as usize].presence[0], presence + 1);
+    }
+}
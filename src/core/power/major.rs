@@ -2,40 +2,524 @@
 
 use crate::base::{
     GameState, StepFailure,
-    PowerCardDescription,
-    PowerCardKind, PowerSpeed, PowerTargetFilter, PowerTarget, Element, ElementMap,
-    LandKind, PieceKind, InvaderKind,
+    PowerCardDescription, sacred_site,
+    PowerCardKind, PowerSpeed, PowerTargetFilter, Element, ElementMap,
+    LandKind, PieceKind, InvaderKind, InvaderMap, InvaderActionKind, TokenKind,
     effect::*, decision::*,
 };
 
+use super::{has_elements, count_of, destroy_invaders, destroy_dahan, push_dahan};
+
+
+fn skip_all_invader_actions(game: &mut GameState, land_index: u8) -> Result<(), StepFailure> {
+    for action in [InvaderActionKind::Ravage, InvaderActionKind::Build, InvaderActionKind::Explore] {
+        game.do_effect(SkipInvaderActionEffect{land_index, action, buildings: InvaderMap::new(true)})?;
+    }
+    Ok(())
+}
+
 fn card_accelerated_rot (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Accelerated Rot" })
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(land_index)})?;
+
+    // +5 damage, remove 1 blight
+    let mut damage = 4;
+    let threshold = has_elements(game, ElementMap::new(0).map(Element::Sun, 3).map(Element::Water, 2).map(Element::Plant, 3))?;
+    if threshold {
+        damage += 5;
+    }
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})?;
+
+    if threshold {
+        game.do_effect(RemoveBlightEffect{land_index, count: 1})?;
+    }
+
+    Ok(())
 }
 
-// lss progression 3
-fn card_powerstorm (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Powerstorm" })
+fn card_cleansing_floods (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +10 damage
+    let mut damage = 4;
+    if has_elements(game, ElementMap::new(0).map(Element::Water, 4))? {
+        damage += 10;
+    }
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})?;
+
+    game.do_effect(RemoveBlightEffect{land_index, count: 1})
+}
+
+fn card_entwined_power (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = *game.get_power_usage()?;
+    let spirit_index = usage.target_spirit()?;
+    let using_spirit_index = usage.using_spirit_index;
+    let other = spirit_index != using_spirit_index;
+
+    if other {
+        game.do_effect(EntwinePresenceEffect{spirit_index: using_spirit_index, other_spirit_index: spirit_index})?;
+    }
+
+    // you gain one of the cards they did not keep
+    game.do_effect(GainPowerCardDecision{spirit_index,
+        shared_with: if other { Some(using_spirit_index) } else { None }})?;
+
+    // each gain 3 energy and may gift the other a power card
+    if has_elements(game, ElementMap::new(0).map(Element::Water, 2).map(Element::Plant, 4))? {
+        game.do_effect(GenerateEnergyEffect{spirit_index: using_spirit_index, energy: 3})?;
+        if other {
+            game.do_effect(GenerateEnergyEffect{spirit_index, energy: 3})?;
+            game.do_effect(GiftPowerCardDecision{spirit_index: using_spirit_index, other_spirit_index: spirit_index})?;
+            game.do_effect(GiftPowerCardDecision{spirit_index, other_spirit_index: using_spirit_index})?;
+        }
+    }
+
+    Ok(())
+}
+
+fn card_indomitable_claim (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = *game.get_power_usage()?;
+    let land_index = usage.target_land()?;
+
+    game.do_effect(AddPresenceToLandDecision{spirit_index: usage.using_spirit_index, land_index})?;
+    game.do_effect(PersistDefenseEffect{land_index, defense: 20})?;
+
+    // 3 fear if invaders are present, they skip all actions
+    if has_elements(game, ElementMap::new(0).map(Element::Sun, 2).map(Element::Earth, 3))? {
+        if !game.get_land(land_index)?.invaders.is_empty() {
+            game.do_effect(GenerateFearEffect{fear: 3, land_index: Some(land_index)})?;
+        }
+        skip_all_invader_actions(game, land_index)?;
+    }
+
+    Ok(())
+}
+
+fn card_infinite_vitality (game: &mut GameState) -> Result<(), StepFailure> {
+    let spirit_index = game.get_power_usage()?.target_spirit()?;
+
+    // dahan ignore damage and destruction, remove another blight
+    let threshold = has_elements(game, ElementMap::new(0).map(Element::Earth, 4))?;
+    game.do_effect(ProtectDahanEffect{spirit_index, health: 4, ignore_damage: threshold})?;
+
+    for _ in 0..(if threshold { 2 } else { 1 }) {
+        let lands: Vec<u8> = game.table.lands.iter()
+            .filter(|l| l.presence[spirit_index] > 0 && l.tokens[TokenKind::Blight] != 0)
+            .map(|l| l.desc.index_on_table)
+            .collect();
+        game.do_effect(ChooseLandDecision{lands,
+            effect: |game, land_index| game.do_effect(RemoveBlightEffect{land_index, count: 1})})?;
+    }
+
+    Ok(())
+}
+
+fn card_jungle_hungers (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(land_index)})?;
+
+    let land = game.get_land(land_index)?;
+    let (explorers, towns) = (count_of(land, InvaderKind::Explorer), count_of(land, InvaderKind::Town));
+    destroy_invaders(game, land_index, InvaderKind::Explorer, explorers)?;
+    destroy_invaders(game, land_index, InvaderKind::Town, towns)?;
+
+    // destroy 1 city, no dahan
+    if has_elements(game, ElementMap::new(0).map(Element::Moon, 2).map(Element::Plant, 3))? {
+        destroy_invaders(game, land_index, InvaderKind::City, 1)
+    } else {
+        let dahan = game.get_land(land_index)?.dahan.len();
+        destroy_dahan(game, land_index, dahan)
+    }
+}
+
+fn card_paralyzing_fright (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +4 fear
+    let mut fear = 4;
+    if has_elements(game, ElementMap::new(0).map(Element::Air, 2).map(Element::Earth, 3))? {
+        fear += 4;
+    }
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})?;
+
+    skip_all_invader_actions(game, land_index)
 }
 
 // lss progression 5
 fn card_pillar_of_living_flame (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Pillar of Living Flame" })
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +2 fear, +5 damage
+    let (fear, damage) = if has_elements(game, ElementMap::new(0).map(Element::Fire, 4))? { (5, 10) } else { (3, 5) };
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})?;
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})?;
+
+    let land = game.get_land_desc(land_index)?;
+    if land.kind == LandKind::Jungle || land.kind == LandKind::Wetlands {
+        game.do_effect(AddBlightEffect{land_index})?;
+    }
+
+    Ok(())
+}
+
+fn card_poisoned_land (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +1 fear, +4 damage
+    let (fear, damage) = if has_elements(game, ElementMap::new(0).map(Element::Earth, 3).map(Element::Plant, 2).map(Element::Animal, 2))? { (2, 11) } else { (1, 7) };
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})?;
+    game.do_effect(AddBlightEffect{land_index})?;
+
+    let dahan = game.get_land(land_index)?.dahan.len();
+    destroy_dahan(game, land_index, dahan)?;
+
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+}
+
+// lss progression 3
+fn card_powerstorm (game: &mut GameState) -> Result<(), StepFailure> {
+    let spirit_index = game.get_power_usage()?.target_spirit()?;
+
+    game.do_effect(GenerateEnergyEffect{spirit_index, energy: 3})?;
+
+    // repeat up to 2 more times
+    let mut amount = 1;
+    if has_elements(game, ElementMap::new(0).map(Element::Sun, 2).map(Element::Fire, 2).map(Element::Air, 3))? {
+        amount += 2;
+    }
+    game.do_effect(MayRepeatPowersEffect{spirit_index, amount})
+}
+
+fn card_talons_of_lightning (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GenerateFearEffect{fear: 3, land_index: Some(land_index)})?;
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage: 5})?;
+
+    // destroy 1 town in each adjacent land
+    if has_elements(game, ElementMap::new(0).map(Element::Fire, 3).map(Element::Air, 3))? {
+        let adjacent: Vec<u8> = game.get_adjacent_lands(land_index)?.into_iter()
+            .filter(|l| l.is_in_play)
+            .map(|l| l.desc.index_on_table)
+            .collect();
+        for adjacent_index in adjacent {
+            destroy_invaders(game, adjacent_index, InvaderKind::Town, 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn card_the_trees_and_stones_speak_of_war (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // first push up to 2 dahan, they do the same where they go
+    let mut lands = Vec::new();
+    if has_elements(game, ElementMap::new(0).map(Element::Sun, 2).map(Element::Earth, 2).map(Element::Plant, 2))? {
+        lands = push_dahan(game, land_index, 2)?;
+    }
+    lands.insert(0, (land_index, game.get_land(land_index)?.dahan.len() as u16));
+
+    // per dahan 1 damage, defend 2
+    for (land_index, dahan) in lands {
+        game.do_effect(DoDamageToInvadersDecision{land_index, damage: dahan})?;
+        game.do_effect(PersistDefenseEffect{land_index, defense: 2 * dahan})?;
+    }
+
+    Ok(())
+}
+
+fn card_tsunami (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(land_index)})?;
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage: 8})?;
+    destroy_dahan(game, land_index, 2)?;
+
+    // the other coastal lands of the board
+    if has_elements(game, ElementMap::new(0).map(Element::Water, 3).map(Element::Earth, 2))? {
+        let board = game.get_land_desc(land_index)?.parent_board_index;
+        let lands: Vec<u8> = game.table.lands.iter()
+            .filter(|l| l.is_in_play && l.desc.is_coastal && l.desc.parent_board_index == board
+                && l.desc.index_on_table != land_index)
+            .map(|l| l.desc.index_on_table)
+            .collect();
+        for land_index in lands {
+            game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(land_index)})?;
+            game.do_effect(DoDamageToInvadersDecision{land_index, damage: 4})?;
+            destroy_dahan(game, land_index, 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn card_terrifying_nightmares (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +4 fear
+    let mut fear = 4;
+    if has_elements(game, ElementMap::new(0).map(Element::Moon, 4))? {
+        fear += 4;
+    }
+
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})
+}
+
+fn card_vengeance_of_the_dead (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GenerateFearEffect{fear: 3, land_index: Some(land_index)})?;
+
+    // the damage may go to adjacent lands
+    let spreads = has_elements(game, ElementMap::new(0).map(Element::Animal, 3))?;
+    game.do_effect(PersistVengeanceEffect{land_index, spreads})
+}
+
+fn card_vigor_of_the_breaking_dawn (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // 2 damage per dahan
+    let damage = 2 * game.get_land(land_index)?.dahan.len() as u16;
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})?;
+
+    if has_elements(game, ElementMap::new(0).map(Element::Sun, 3).map(Element::Animal, 2))? {
+        // the pushed dahan deal 2 damage each where they go
+        for (land_index, dahan) in push_dahan(game, land_index, 2)? {
+            game.do_effect(DoDamageToInvadersDecision{land_index, damage: 2 * dahan})?;
+        }
+    }
+
+    Ok(())
+}
+
+fn card_winds_of_rust_and_atrophy (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // repeat this power
+    let times = if has_elements(game, ElementMap::new(0).map(Element::Air, 3).map(Element::Water, 3).map(Element::Animal, 2))? { 2 } else { 1 };
+    for _ in 0..times {
+        game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(land_index)})?;
+        game.do_effect(PersistDefenseEffect{land_index, defense: 6})?;
+        // a city by a town or a town by an explorer
+        game.do_effect(RemoveInvadersDecision{land_index, may: false, downgrade: true,
+            allowed: |_, removed| removed.len() == 1 && removed[0].is_building()})?;
+    }
+
+    Ok(())
+}
+
+fn card_wrap_in_wings_of_sunlight (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // first gather up to 3 dahan
+    if has_elements(game, ElementMap::new(0).map(Element::Sun, 2).map(Element::Air, 2).map(Element::Animal, 2))? {
+        game.do_effect(GatherDecision{land_index, count: 3, may: true,
+            kinds: vec![PieceKind::Dahan]})?;
+    }
+
+    // move up to 5 dahan to any land and defend 5 there
+    let lands: Vec<u8> = game.table.lands.iter()
+        .filter(|l| l.is_in_play && l.desc.index_on_table != land_index)
+        .map(|l| l.desc.index_on_table)
+        .collect();
+    game.do_effect(ChooseLandDecision{lands, effect: |game, to_land_index| {
+        let from_land_index = game.get_power_usage()?.target_land()?;
+        let moves: [LandEffect; 6] = [
+            |game, land_index| wrap_in_wings_of_sunlight_move(game, land_index, 0),
+            |game, land_index| wrap_in_wings_of_sunlight_move(game, land_index, 1),
+            |game, land_index| wrap_in_wings_of_sunlight_move(game, land_index, 2),
+            |game, land_index| wrap_in_wings_of_sunlight_move(game, land_index, 3),
+            |game, land_index| wrap_in_wings_of_sunlight_move(game, land_index, 4),
+            |game, land_index| wrap_in_wings_of_sunlight_move(game, land_index, 5),
+        ];
+        let most = game.get_land(from_land_index)?.dahan.len().min(5);
+        game.do_effect(ChooseLandEffectDecision{land_index: to_land_index, choices: moves[..=most].to_vec()})
+    }})
+}
+
+fn wrap_in_wings_of_sunlight_move (game: &mut GameState, to_land_index: u8, count: usize) -> Result<(), StepFailure> {
+    let from_land_index = game.get_power_usage()?.target_land()?;
+    for _ in 0..count {
+        let index = game.get_land(from_land_index)?.dahan.len() - 1;
+        game.do_effect(MovePieceEffect{from_land_index, to_land_index, kind: PieceKind::Dahan, index})?;
+    }
+    game.do_effect(PersistDefenseEffect{land_index: to_land_index, defense: 5})
 }
 
 pub fn make_major_power_cards() -> Vec<PowerCardDescription> {
     vec![
         PowerCardDescription {
             name: "Accelerated Rot",
-            kind: PowerCardKind::Minor,
+            kind: PowerCardKind::Major,
             elements: ElementMap::from_slice(&[Element::Sun, Element::Water, Element::Plant]),
             cost: 4, speed: PowerSpeed::Slow,
             target_filter: PowerTargetFilter::Land{
                 range: 2,
-                src: |_| true,
+                src: |_, _| true,
                 dst: |l| l.desc.kind == LandKind::Jungle || l.desc.kind == LandKind::Wetlands
             },
 
             effect: card_accelerated_rot
         },
+        PowerCardDescription {
+            name: "Cleansing Floods",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Water]),
+            cost: 5, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_cleansing_floods,
+        },
+        PowerCardDescription {
+            name: "Entwined Power",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Water, Element::Plant]),
+            cost: 2, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Spirit(|_| true),
+
+            effect: card_entwined_power,
+        },
+        PowerCardDescription {
+            name: "Indomitable Claim",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Earth]),
+            cost: 4, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_indomitable_claim,
+        },
+        PowerCardDescription {
+            name: "Infinite Vitality",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Earth, Element::Plant, Element::Animal]),
+            cost: 3, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Spirit(|_| true),
+
+            effect: card_infinite_vitality,
+        },
+        PowerCardDescription {
+            name: "Jungle Hungers",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Plant]),
+            cost: 3, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |l| l.desc.kind == LandKind::Jungle},
+
+            effect: card_jungle_hungers,
+        },
+        PowerCardDescription {
+            name: "Paralyzing Fright",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Air, Element::Earth]),
+            cost: 4, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_paralyzing_fright,
+        },
+        PowerCardDescription {
+            name: "Pillar of Living Flame",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Fire]),
+            cost: 5, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 2, src: sacred_site, dst: |_| true},
+
+            effect: card_pillar_of_living_flame,
+        },
+        PowerCardDescription {
+            name: "Poisoned Land",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Earth, Element::Plant, Element::Animal]),
+            cost: 3, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_poisoned_land,
+        },
+        PowerCardDescription {
+            name: "Powerstorm",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Fire, Element::Air]),
+            cost: 3, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Spirit(|_| true),
+
+            effect: card_powerstorm,
+        },
+        PowerCardDescription {
+            name: "Talons of Lightning",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Fire, Element::Air]),
+            cost: 6, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{
+                range: 1,
+                src: |_, _| true,
+                dst: |l| l.desc.kind == LandKind::Mountain || l.desc.kind == LandKind::Wetlands
+            },
+
+            effect: card_talons_of_lightning,
+        },
+        PowerCardDescription {
+            name: "Terrifying Nightmares",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Air]),
+            cost: 4, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 2, src: |_, _| true, dst: |_| true},
+
+            effect: card_terrifying_nightmares,
+        },
+        PowerCardDescription {
+            name: "The Trees and Stones Speak of War",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Earth, Element::Plant]),
+            cost: 2, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| !l.dahan.is_empty()},
+
+            effect: card_the_trees_and_stones_speak_of_war,
+        },
+        PowerCardDescription {
+            name: "Tsunami",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Water, Element::Earth]),
+            cost: 6, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 2, src: |_, _| true, dst: |l| l.desc.is_coastal},
+
+            effect: card_tsunami,
+        },
+        PowerCardDescription {
+            name: "Vengeance of the Dead",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Fire, Element::Animal]),
+            cost: 3, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 3, src: |_, _| true, dst: |_| true},
+
+            effect: card_vengeance_of_the_dead,
+        },
+        PowerCardDescription {
+            name: "Vigor of the Breaking Dawn",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Animal]),
+            cost: 3, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 2, src: |_, _| true, dst: |l| !l.dahan.is_empty()},
+
+            effect: card_vigor_of_the_breaking_dawn,
+        },
+        PowerCardDescription {
+            name: "Winds of Rust and Atrophy",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Air, Element::Water, Element::Animal]),
+            cost: 3, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_winds_of_rust_and_atrophy,
+        },
+        PowerCardDescription {
+            name: "Wrap in Wings of Sunlight",
+            kind: PowerCardKind::Major,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Air, Element::Animal]),
+            cost: 3, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true},
+
+            effect: card_wrap_in_wings_of_sunlight,
+        },
     ]
 }
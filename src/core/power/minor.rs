@@ -2,15 +2,46 @@
 
 use crate::base::{
     GameState, StepFailure,
-    PowerCardDescription,
+    PowerCardDescription, sacred_site,
     PowerCardKind, PowerSpeed, PowerTargetFilter, PowerTarget, Element, ElementMap,
-    LandKind, PieceKind, InvaderKind,
+    LandKind, PieceKind, InvaderKind, InvaderMap, TokenKind,
     effect::*, decision::*,
 };
 
+use super::{has_elements, count_of, destroy_invaders, destroy_dahan};
+
+
+// Replaces the last invader of a kind with a dahan.
+fn replace_with_dahan(game: &mut GameState, land_index: u8, kind: InvaderKind) -> Result<(), StepFailure> {
+    let invader_index = match game.get_land(land_index)?.invaders.iter().rposition(|i| i.kind == kind) {
+        Some(invader_index) => invader_index,
+        None => return Ok(()),
+    };
+    game.do_effect(RemoveInvaderEffect{land_index, invader_index, destroyed: false})?;
+    game.do_effect(AddDahanEffect{land_index, count: 1})
+}
 
 fn card_call_of_the_dahan_ways (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Call of The Dahan Ways" })
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // may instead replace a town
+    if has_elements(game, ElementMap::new(0).map(Element::Moon, 2))?
+        && count_of(game.get_land(land_index)?, InvaderKind::Town) != 0 {
+        return game.do_effect(ChooseEffectDecision{
+            choices: vec![
+                |game| {
+                    let land_index = game.get_power_usage()?.target_land()?;
+                    replace_with_dahan(game, land_index, InvaderKind::Explorer)
+                },
+                |game| {
+                    let land_index = game.get_power_usage()?.target_land()?;
+                    replace_with_dahan(game, land_index, InvaderKind::Town)
+                },
+            ]
+        });
+    }
+
+    replace_with_dahan(game, land_index, InvaderKind::Explorer)
 }
 
 // lss progression 2
@@ -35,7 +66,23 @@ fn card_call_to_bloodshed (game: &mut GameState) -> Result<(), StepFailure> {
 
 // lss progression 7
 fn card_call_to_isolation (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Call to Isolation" })
+    game.do_effect(ChooseEffectDecision{
+        choices: vec![
+            |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                // push 1 explorer/town per dahan
+                let count = game.get_land(land_index)?.dahan.len() as u8;
+                game.do_effect(PushDecision{land_index, count, may: false,
+                    kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+            },
+            |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                // push 1 dahan
+                game.do_effect(PushDecision{land_index, count: 1, may: false,
+                    kinds: vec![PieceKind::Dahan]})
+            }
+        ]
+    })
 }
 
 fn card_call_to_migrate (game: &mut GameState) -> Result<(), StepFailure> {
@@ -114,7 +161,7 @@ fn card_devouring_ants (game: &mut GameState) -> Result<(), StepFailure> {
     }
     game.do_effect(DoDamageToInvadersDecision{land_index, damage})?;
 
-    game.do_effect(NotImplementedEffect { what: "Devouring Ants Destroy Dahan" })
+    destroy_dahan(game, land_index, 1)
 }
 
 fn card_drift_down_into_slumber (game: &mut GameState) -> Result<(), StepFailure> {
@@ -132,25 +179,328 @@ fn card_drift_down_into_slumber (game: &mut GameState) -> Result<(), StepFailure
 }
 
 fn card_drought (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Drought" })
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    destroy_invaders(game, land_index, InvaderKind::Town, 3)?;
+    game.do_effect(DoDamageToEachInvaderEffect{land_index, damage: 1,
+        kinds: InvaderMap::new(false).map(InvaderKind::Town, true).map(InvaderKind::City, true)})?;
+    game.do_effect(AddBlightEffect{land_index})?;
+
+    if has_elements(game, ElementMap::new(0).map(Element::Sun, 3))? {
+        destroy_invaders(game, land_index, InvaderKind::City, 1)?;
+    }
+
+    Ok(())
 }
 
 fn card_elemental_boon (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Elemental Boon" })
+    let usage = *game.get_power_usage()?;
+    let spirit_index = usage.target_spirit()?;
+
+    // you gain them too when targeting another spirit
+    let also = if spirit_index != usage.using_spirit_index { Some(usage.using_spirit_index) } else { None };
+    game.do_effect(GainElementsDecision{spirit_index, count: 3, also})
 }
 
 fn card_encompassing_ward (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Encompassing ward" })
+    let spirit_index = game.get_power_usage()?.target_spirit()?;
+
+    let lands: Vec<u8> = game.table.lands.iter()
+        .filter(|l| l.presence[spirit_index] > 0)
+        .map(|l| l.desc.index_on_table)
+        .collect();
+    for land_index in lands {
+        game.do_effect(PersistDefenseEffect{land_index, defense: 2})?;
+    }
+
+    Ok(())
+}
+
+fn card_enticing_splendor (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GatherDecision{land_index, count: 1, may: false,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})?;
+    game.do_effect(GatherDecision{land_index, count: 2, may: true,
+        kinds: vec![PieceKind::Dahan]})
 }
 
 // lss progression 6
 fn card_entrancing_apparitions (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Entrancing Apparitions" })
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(PersistDefenseEffect{land_index, defense: 2})?;
+
+    if game.get_land(land_index)?.invaders.is_empty() {
+        game.do_effect(GatherDecision{land_index, count: 2, may: true,
+            kinds: vec![PieceKind::Invader(InvaderKind::Explorer)]})?;
+    }
+
+    Ok(())
+}
+
+fn card_gift_of_living_energy (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = *game.get_power_usage()?;
+    let spirit_index = usage.target_spirit()?;
+
+    // another spirit gains 1 more per sacred site
+    let mut energy = 1;
+    if spirit_index != usage.using_spirit_index {
+        energy += game.table.lands.iter().filter(|l| l.presence[spirit_index] >= 2).count() as u8;
+    }
+
+    game.do_effect(GenerateEnergyEffect{spirit_index, energy})
+}
+
+fn card_gift_of_power (game: &mut GameState) -> Result<(), StepFailure> {
+    let spirit_index = game.get_power_usage()?.target_spirit()?;
+
+    game.do_effect(GainMinorPowerCardDecision{spirit_index, draw_count: 4, shared_with: None})
+}
+
+fn card_gnawing_rootbiters (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(PushDecision{land_index, count: 2, may: true,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+}
+
+fn card_land_of_haunts_and_embers (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +2 fear if blighted
+    let mut fear = 2;
+    if game.get_land(land_index)?.tokens[TokenKind::Blight] != 0 {
+        fear += 2;
+    }
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})?;
+
+    game.do_effect(PushDecision{land_index, count: 2, may: true,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})?;
+    game.do_effect(AddBlightEffect{land_index})
+}
+
+fn card_lure_of_the_unknown (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GatherDecision{land_index, count: 1, may: false,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+}
+
+fn card_natures_resilience (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // may instead remove 1 blight
+    if has_elements(game, ElementMap::new(0).map(Element::Water, 2))?
+        && game.get_land(land_index)?.tokens[TokenKind::Blight] != 0 {
+        return game.do_effect(ChooseEffectDecision{
+            choices: vec![
+                |game| {
+                    let land_index = game.get_power_usage()?.target_land()?;
+                    game.do_effect(PersistDefenseEffect{land_index, defense: 6})
+                },
+                |game| {
+                    let land_index = game.get_power_usage()?.target_land()?;
+                    game.do_effect(RemoveBlightEffect{land_index, count: 1})
+                },
+            ]
+        });
+    }
+
+    game.do_effect(PersistDefenseEffect{land_index, defense: 6})
+}
+
+fn card_pull_beneath_the_hungry_earth (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = *game.get_power_usage()?;
+    let land_index = usage.target_land()?;
+
+    let mut damage = 0;
+    if game.get_land(land_index)?.presence[usage.using_spirit_index] > 0 {
+        game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(land_index)})?;
+        damage += 1;
+    }
+    let land = game.get_land_desc(land_index)?;
+    if land.kind == LandKind::Sands || land.kind == LandKind::Wetlands {
+        damage += 1;
+    }
+
+    if damage != 0 {
+        game.do_effect(DoDamageToInvadersDecision{land_index, damage})?;
+    }
+
+    Ok(())
 }
 
 // lss progression 4
 fn card_purifying_flame (game: &mut GameState) -> Result<(), StepFailure> {
-    game.do_effect(NotImplementedEffect { what: "Purifying Flame" })
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // may instead remove 1 blight
+    let land = game.get_land_desc(land_index)?;
+    if land.kind == LandKind::Mountain || land.kind == LandKind::Sands {
+        return game.do_effect(ChooseEffectDecision{
+            choices: vec![
+                |game| {
+                    let land_index = game.get_power_usage()?.target_land()?;
+                    let damage = game.get_land(land_index)?.tokens[TokenKind::Blight] as u16;
+                    game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+                },
+                |game| {
+                    let land_index = game.get_power_usage()?.target_land()?;
+                    game.do_effect(RemoveBlightEffect{land_index, count: 1})
+                },
+            ]
+        });
+    }
+
+    // 1 damage per blight
+    let damage = game.get_land(land_index)?.tokens[TokenKind::Blight] as u16;
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+}
+
+fn card_quicken_the_earths_struggles (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(DoDamageToEachInvaderEffect{land_index, damage: 1,
+        kinds: InvaderMap::new(false).map(InvaderKind::Town, true).map(InvaderKind::City, true)})?;
+    game.do_effect(PersistDefenseEffect{land_index, defense: 10})
+}
+
+fn card_rain_of_blood (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // +1 fear for 2 towns/cities
+    let mut fear = 2;
+    if game.get_land(land_index)?.invaders.iter().filter(|i| i.is_building()).count() >= 2 {
+        fear += 1;
+    }
+
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})
+}
+
+fn card_reaching_grasp (game: &mut GameState) -> Result<(), StepFailure> {
+    let spirit_index = game.get_power_usage()?.target_spirit()?;
+
+    game.do_effect(ExtraRangeEffect{spirit_index, range: 2})
+}
+
+fn card_savage_mawbeasts (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    let (fear, damage) = if has_elements(game, ElementMap::new(0).map(Element::Animal, 3))? { (2, 2) } else { (1, 1) };
+
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})?;
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage})
+}
+
+fn card_shadows_of_the_burning_forest (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(land_index)})?;
+
+    let land = game.get_land_desc(land_index)?;
+    if land.kind == LandKind::Mountain || land.kind == LandKind::Jungle {
+        game.do_effect(PushDecision{land_index, count: 1, may: false,
+            kinds: vec![PieceKind::Invader(InvaderKind::Explorer)]})?;
+        game.do_effect(PushDecision{land_index, count: 1, may: false,
+            kinds: vec![PieceKind::Invader(InvaderKind::Town)]})?;
+    }
+
+    Ok(())
+}
+
+fn card_song_of_sanctity (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // push all explorers, otherwise remove 1 blight
+    let explorers = count_of(game.get_land(land_index)?, InvaderKind::Explorer) as u8;
+    if explorers != 0 {
+        game.do_effect(PushDecision{land_index, count: explorers, may: false,
+            kinds: vec![PieceKind::Invader(InvaderKind::Explorer)]})
+    } else {
+        game.do_effect(RemoveBlightEffect{land_index, count: 1})
+    }
+}
+
+fn card_steam_vents (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    // may instead destroy 1 town
+    let kinds = if has_elements(game, ElementMap::new(0).map(Element::Earth, 3))? {
+        InvaderMap::new(false).map(InvaderKind::Explorer, true).map(InvaderKind::Town, true)
+    } else {
+        InvaderMap::new(false).map(InvaderKind::Explorer, true)
+    };
+
+    game.do_effect(DestroyInvadersDecision{land_index, count: 1, kinds})
+}
+
+fn card_uncanny_melting (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    if !game.get_land(land_index)?.invaders.is_empty() {
+        game.do_effect(GenerateFearEffect{fear: 1, land_index: Some(land_index)})?;
+    }
+
+    let land = game.get_land_desc(land_index)?;
+    if land.kind == LandKind::Sands || land.kind == LandKind::Wetlands {
+        game.do_effect(RemoveBlightEffect{land_index, count: 1})?;
+    }
+
+    Ok(())
+}
+
+fn card_veil_the_nights_hunt (game: &mut GameState) -> Result<(), StepFailure> {
+    game.do_effect(ChooseEffectDecision{
+        choices: vec![
+            |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                // each dahan 1 damage, each to a different invader
+                let count = game.get_land(land_index)?.dahan.len() as u8;
+                game.do_effect(DoDamageToDifferentInvadersDecision{land_index, count})
+            },
+            |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                // push up to 3 dahan
+                game.do_effect(PushDecision{land_index, count: 3, may: true,
+                    kinds: vec![PieceKind::Dahan]})
+            }
+        ]
+    })
+}
+
+fn card_visions_of_fiery_doom (game: &mut GameState) -> Result<(), StepFailure> {
+    let land_index = game.get_power_usage()?.target_land()?;
+
+    let mut fear = 1;
+    if has_elements(game, ElementMap::new(0).map(Element::Fire, 2))? {
+        fear += 1;
+    }
+    game.do_effect(GenerateFearEffect{fear, land_index: Some(land_index)})?;
+
+    game.do_effect(PushDecision{land_index, count: 1, may: false,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+}
+
+fn card_voice_of_thunder (game: &mut GameState) -> Result<(), StepFailure> {
+    game.do_effect(ChooseEffectDecision{
+        choices: vec![
+            |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                // push up to 4 dahan
+                game.do_effect(PushDecision{land_index, count: 4, may: true,
+                    kinds: vec![PieceKind::Dahan]})
+            },
+            |game| {
+                let land_index = game.get_power_usage()?.target_land()?;
+                // 2 fear if invaders are present
+                if game.get_land(land_index)?.invaders.is_empty() {
+                    return Ok(());
+                }
+                game.do_effect(GenerateFearEffect{fear: 2, land_index: Some(land_index)})
+            }
+        ]
+    })
 }
 
 pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
@@ -160,7 +510,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Moon, Element::Water, Element::Animal]),
             cost: 1, speed: PowerSpeed::Slow,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |l| l.dahan.len() != 0},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| l.dahan.len() != 0},
 
             effect: card_call_of_the_dahan_ways
         },
@@ -169,7 +519,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Sun, Element::Fire, Element::Animal]),
             cost: 1, speed: PowerSpeed::Slow,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |l| l.dahan.len() != 0},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| l.dahan.len() != 0},
 
             effect: card_call_to_bloodshed
         },
@@ -178,7 +528,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Sun, Element::Air, Element::Animal]),
             cost: 0, speed: PowerSpeed::Fast,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |l| l.dahan.len() != 0},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| l.dahan.len() != 0},
 
             effect: card_call_to_isolation
         },
@@ -187,7 +537,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Fire, Element::Air, Element::Animal]),
             cost: 1, speed: PowerSpeed::Slow,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
             effect: card_call_to_migrate
         },
@@ -196,7 +546,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Water, Element::Plant, Element::Animal]),
             cost: 1, speed: PowerSpeed::Slow,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |l| l.dahan.len() != 0},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| l.dahan.len() != 0},
 
             effect: card_call_to_tend
         },
@@ -205,7 +555,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Moon, Element::Earth, Element::Plant]),
             cost: 1, speed: PowerSpeed::Fast,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
             effect: card_dark_and_tangled_woods
         },
@@ -214,7 +564,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Sun, Element::Moon, Element::Air]),
             cost: 1, speed: PowerSpeed::Fast,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
             effect: card_delusions_of_danger
         },
@@ -223,16 +573,16 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Sun, Element::Earth, Element::Animal]),
             cost: 1, speed: PowerSpeed::Slow,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
             effect: card_devouring_ants,
         },
         PowerCardDescription {
-            name: "Dirft Down into Slumber",
+            name: "Drift Down into Slumber",
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Air, Element::Earth, Element::Plant]),
             cost: 0, speed: PowerSpeed::Fast,
-            target_filter: PowerTargetFilter::Land{range: 2, src: |_| true, dst: |_| true},
+            target_filter: PowerTargetFilter::Land{range: 2, src: |_, _| true, dst: |_| true},
 
             effect: card_drift_down_into_slumber,
         },
@@ -241,7 +591,7 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
             kind: PowerCardKind::Minor,
             elements: ElementMap::from_slice(&[Element::Sun, Element::Fire, Element::Earth]),
             cost: 1, speed: PowerSpeed::Slow,
-            target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
             effect: card_drought,
         },
@@ -263,5 +613,194 @@ pub fn make_minor_power_cards() -> Vec<PowerCardDescription> {
 
             effect: card_encompassing_ward,
         },
+        PowerCardDescription {
+            name: "Enticing Splendor",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Air, Element::Plant]),
+            cost: 0, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |l| l.tokens[TokenKind::Blight] == 0},
+
+            effect: card_enticing_splendor,
+        },
+        PowerCardDescription {
+            name: "Entrancing Apparitions",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Air, Element::Water]),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_entrancing_apparitions,
+        },
+        PowerCardDescription {
+            name: "Gift of Living Energy",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Fire, Element::Plant]),
+            cost: 0, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Spirit(|_| true),
+
+            effect: card_gift_of_living_energy,
+        },
+        PowerCardDescription {
+            name: "Gift of Power",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Water, Element::Earth, Element::Plant]),
+            cost: 0, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Spirit(|_| true),
+
+            effect: card_gift_of_power,
+        },
+        PowerCardDescription {
+            name: "Gnawing Rootbiters",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Earth, Element::Animal]),
+            cost: 0, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_gnawing_rootbiters,
+        },
+        PowerCardDescription {
+            name: "Land of Haunts and Embers",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Fire, Element::Air]),
+            cost: 0, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 2, src: |_, _| true, dst: |_| true},
+
+            effect: card_land_of_haunts_and_embers,
+        },
+        PowerCardDescription {
+            name: "Lure of the Unknown",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Fire, Element::Air, Element::Plant]),
+            cost: 0, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 2, src: |_, _| true, dst: |l| l.invaders.is_empty()},
+
+            effect: card_lure_of_the_unknown,
+        },
+        PowerCardDescription {
+            name: "Nature's Resilience",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Earth, Element::Plant, Element::Animal]),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_natures_resilience,
+        },
+        PowerCardDescription {
+            name: "Pull Beneath the Hungry Earth",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Water, Element::Earth]),
+            cost: 1, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_pull_beneath_the_hungry_earth,
+        },
+        PowerCardDescription {
+            name: "Purifying Flame",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Fire, Element::Air, Element::Plant]),
+            cost: 1, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: sacred_site, dst: |_| true},
+
+            effect: card_purifying_flame,
+        },
+        PowerCardDescription {
+            name: "Quicken the Earth's Struggles",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Fire, Element::Earth, Element::Animal]),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true},
+
+            effect: card_quicken_the_earths_struggles,
+        },
+        PowerCardDescription {
+            name: "Rain of Blood",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Air, Element::Water, Element::Animal]),
+            cost: 1, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: sacred_site, dst: |l| !l.invaders.is_empty()},
+
+            effect: card_rain_of_blood,
+        },
+        PowerCardDescription {
+            name: "Reaching Grasp",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Air, Element::Water]),
+            cost: 0, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Spirit(|_| true),
+
+            effect: card_reaching_grasp,
+        },
+        PowerCardDescription {
+            name: "Savage Mawbeasts",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Fire, Element::Animal]),
+            cost: 0, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: sacred_site, dst: |_| true},
+
+            effect: card_savage_mawbeasts,
+        },
+        PowerCardDescription {
+            name: "Shadows of the Burning Forest",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Fire, Element::Plant]),
+            cost: 0, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true},
+
+            effect: card_shadows_of_the_burning_forest,
+        },
+        PowerCardDescription {
+            name: "Song of Sanctity",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Water, Element::Plant]),
+            cost: 1, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| l.desc.kind == LandKind::Mountain || l.desc.kind == LandKind::Jungle},
+
+            effect: card_song_of_sanctity,
+        },
+        PowerCardDescription {
+            name: "Steam Vents",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Fire, Element::Air, Element::Water, Element::Earth]),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true},
+
+            effect: card_steam_vents,
+        },
+        PowerCardDescription {
+            name: "Uncanny Melting",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Moon, Element::Water]),
+            cost: 1, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: sacred_site, dst: |_| true},
+
+            effect: card_uncanny_melting,
+        },
+        PowerCardDescription {
+            name: "Veil the Night's Hunt",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Air, Element::Animal]),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |l| !l.dahan.is_empty()},
+
+            effect: card_veil_the_nights_hunt,
+        },
+        PowerCardDescription {
+            name: "Visions of Fiery Doom",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Moon, Element::Fire]),
+            cost: 1, speed: PowerSpeed::Fast,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_visions_of_fiery_doom,
+        },
+        PowerCardDescription {
+            name: "Voice of Thunder",
+            kind: PowerCardKind::Minor,
+            elements: ElementMap::from_slice(&[Element::Sun, Element::Air]),
+            cost: 0, speed: PowerSpeed::Slow,
+            target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
+
+            effect: card_voice_of_thunder,
+        },
     ]
 }
@@ -1,13 +1,9 @@
 // This file contains copyrighted assets owned by Greater Than Games.
 
-use std::{
-    cmp::{min}
-};
-
 use crate::base::{
-    GameState, StepFailure, SpiritDescription, PresenceState,
-    PowerCardDescription,
-    PowerCardKind, PowerSpeed, PowerTargetFilter, PowerTarget, Element, ElementMap,
+    GameState, StepFailure, SpiritDescription, PresenceState, PresenceTrack, TrackSlot,
+    PowerCardDescription, InnatePowerDescription, InnatePowerLevel,
+    PowerCardKind, PowerSpeed, PowerTargetFilter, sacred_site, PowerTarget, Element, ElementMap,
     LandKind, PieceKind, InvaderKind, InvaderMap,
     effect::*, decision::*,
 };
@@ -57,8 +53,13 @@ fn card_shatter_homesteads (game: &mut GameState) -> Result<(), StepFailure> {
     Ok(())
 }
 
-const _TOP_TRACK_START : u8 = 2;
-const _BOT_TRACK_START : u8 = 9;
+fn innate_thundering_destruction (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = *game.get_power_usage()?;
+    let land_index = usage.target_land()?;
+
+    game.do_effect(DestroyInvadersDecision{ land_index, count: 1,
+        kinds: InvaderMap::new(false).map(InvaderKind::Town, true).map(InvaderKind::City, true) })
+}
 
 impl SpiritDescription for SpiritDescriptionLightning {
     fn name(&self) -> &'static str { "Lightning's Swift Strike" }
@@ -71,7 +72,7 @@ impl SpiritDescription for SpiritDescriptionLightning {
                 kind: PowerCardKind::Spirit(spirit_index),
                 elements: ElementMap::from_slice(&[Element::Fire, Element::Air]),
                 cost: 0, speed: PowerSpeed::Slow,
-                target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+                target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
                 effect: card_harbringers_of_the_lightning
             },
@@ -89,7 +90,7 @@ impl SpiritDescription for SpiritDescriptionLightning {
                 kind: PowerCardKind::Spirit(spirit_index),
                 elements: ElementMap::from_slice(&[Element::Fire, Element::Air, Element::Water]),
                 cost: 3, speed: PowerSpeed::Slow,
-                target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+                target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
                 effect: card_raging_storm,
             },
@@ -98,7 +99,7 @@ impl SpiritDescription for SpiritDescriptionLightning {
                 kind: PowerCardKind::Spirit(spirit_index),
                 elements: ElementMap::from_slice(&[Element::Fire, Element::Air]),
                 cost: 1, speed: PowerSpeed::Slow,
-                target_filter: PowerTargetFilter::Land{range: 2, src: |_| true /* sacred site */, dst: |_| true},
+                target_filter: PowerTargetFilter::Land{range: 2, src: sacred_site, dst: |_| true},
 
                 effect: card_shatter_homesteads,
             },
@@ -109,12 +110,41 @@ impl SpiritDescription for SpiritDescriptionLightning {
             "Delusions of Danger",
             "Call to Bloodshed",
             "Powerstorm",
-            "Purfying Flame",
+            "Purifying Flame",
             "Pillar of Living Flame",
             "Entrancing Apparitions",
             "Call to Isolation",
         ]
     }
+    fn get_innate_powers(&self) -> Vec<InnatePowerDescription> {
+        // every level destroys one more town or city
+        vec![
+            InnatePowerDescription {
+                name: "Thundering Destruction",
+                speed: PowerSpeed::Slow,
+                target_filter: PowerTargetFilter::Land{range: 1, src: sacred_site, dst: |_| true},
+
+                levels: vec![
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Fire, 3).map(Element::Air, 2),
+                        instead: false, effect: innate_thundering_destruction,
+                    },
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Fire, 4).map(Element::Air, 3),
+                        instead: false, effect: innate_thundering_destruction,
+                    },
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Fire, 5).map(Element::Air, 4).map(Element::Water, 1),
+                        instead: false, effect: innate_thundering_destruction,
+                    },
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Fire, 5).map(Element::Air, 5).map(Element::Water, 2),
+                        instead: false, effect: innate_thundering_destruction,
+                    },
+                ],
+            },
+        ]
+    }
 
     fn do_setup(&self, game: &mut GameState, si: usize) -> Result<(), StepFailure> {
         // Lightning puts 2 in the highest sands
@@ -137,27 +167,17 @@ impl SpiritDescription for SpiritDescriptionLightning {
         Ok(())
     }
 
-    fn may_place_presence(&self, state: &[PresenceState; 13], presence_index: usize) -> Result<bool, StepFailure> {
-        match state[presence_index] {
-            PresenceState::OnTrack(track_loc) => {
-                if track_loc < _BOT_TRACK_START {
-                    // Top Track
-                    if track_loc == _TOP_TRACK_START {
-                        Ok(true)
-                    } else {
-                        Ok(state[(track_loc - 1) as usize] != PresenceState::OnTrack(track_loc - 1))
-                    }
-                } else {
-                    // Bottom Track
-                    if track_loc == _BOT_TRACK_START {
-                        Ok(true)
-                    } else {
-                        Ok(state[(track_loc - 1) as usize] != PresenceState::OnTrack(track_loc - 1))
-                    }
-                }
-            },
-            _ => Ok(true)
-        }
+    fn get_presence_tracks(&self) -> Vec<PresenceTrack> {
+        vec![
+            PresenceTrack{ first: 2, slots: vec![
+                TrackSlot::Energy(1), TrackSlot::Energy(1), TrackSlot::Energy(2), TrackSlot::Energy(2),
+                TrackSlot::Energy(3), TrackSlot::Energy(4), TrackSlot::Energy(4), TrackSlot::Energy(5),
+            ]},
+            PresenceTrack{ first: 9, slots: vec![
+                TrackSlot::Plays(2), TrackSlot::Plays(3), TrackSlot::Plays(4), TrackSlot::Plays(5),
+                TrackSlot::Plays(6),
+            ]},
+        ]
     }
 
     fn do_growth(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure> {
@@ -168,7 +188,7 @@ impl SpiritDescription for SpiritDescriptionLightning {
                 |game, spirit_index| {
                     // Growth A
                     game.do_effect(ReclaimAllEffect{ spirit_index })?;
-                    game.do_effect(GainPowerCardDecision{ spirit_index })?;
+                    game.do_effect(GainPowerCardDecision{ spirit_index, shared_with: None })?;
                     game.do_effect(GenerateEnergyEffect{ spirit_index, energy: 1 })?;
 
                     Ok(())
@@ -190,61 +210,6 @@ impl SpiritDescription for SpiritDescriptionLightning {
             ]
         })
     }
-
-    fn do_income(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure> {
-        let spirit = game.get_spirit_mut(spirit_index as u8)?;
-        
-        let mut top_track_min = 15;
-        let mut bot_track_min = 15;
-
-        for presence in spirit.presence.iter() {
-            match *presence {
-                PresenceState::OnTrack(track_loc) => {
-                    if track_loc >= _BOT_TRACK_START {
-                        bot_track_min = min(bot_track_min, track_loc);
-                    } else {
-                        top_track_min = min(top_track_min, track_loc);
-                    }
-                },
-                _ => {}
-            }
-        }
-
-        // The above loop fings the minimum track spot with presence still on it. To find the
-        // minimum open spot we should have to subtract 1, to get normative numbering of the tracks
-        // (where 0 is the free space and 1 is the first open space) we need to add 1. These cancel
-        // out. Make sure to change this code if these assumptions change.
-        // TODO: use a struct for all this shit. 
-
-        top_track_min -= _TOP_TRACK_START;
-        bot_track_min -= _BOT_TRACK_START;
-
-        let card_plays;
-        let energy;
-
-        match top_track_min {
-            1 => energy = 1,
-            2 | 3 => energy = 2,
-            4 => energy = 3,
-            5 | 6 => energy = 4,
-            7 => energy = 5,
-            _ => energy = 1,
-        }
-
-        match bot_track_min {
-            1 => card_plays = 3,
-            2 => card_plays = 4,
-            3 => card_plays = 5,
-            4 => card_plays = 6,
-            _ => card_plays = 2,
-        }
-
-        spirit.plays = card_plays; // TODO: effect maybe?
-        
-        game.do_effect(GenerateEnergyEffect{ spirit_index: spirit_index as u8, energy })?;
-
-        Ok(())
-    }
 }
 
 impl SpiritDescriptionLightning {
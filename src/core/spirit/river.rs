@@ -1,14 +1,10 @@
 // This file contains copyrighted assets owned by Greater Than Games.
 
-use std::{
-    cmp::{min}
-};
-
 use crate::base::{
-    GameState, StepFailure, SpiritDescription, PresenceState,
-    PowerCardDescription,
-    PowerCardKind, PowerSpeed, PowerTargetFilter, Element, ElementMap,
-    LandKind, PieceKind, InvaderKind,
+    GameState, StepFailure, SpiritDescription, PresenceState, PresenceTrack, TrackSlot,
+    PowerCardDescription, InnatePowerDescription, InnatePowerLevel,
+    PowerCardKind, PowerSpeed, PowerTargetFilter, sacred_site, Element, ElementMap,
+    LandKind, PieceKind, InvaderKind, InvaderMap,
     effect::*, decision::*,
 };
 
@@ -77,8 +73,29 @@ fn card_wash_away (game: &mut GameState) -> Result<(), StepFailure> {
     Ok(())
 }
 
-const _TOP_TRACK_START : u8 = 1;
-const _BOT_TRACK_START : u8 = 7;
+fn innate_massive_flooding_1 (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = game.get_power_usage()?;
+    let land_index = usage.target_land()?;
+
+    game.do_effect(PushDecision{land_index, count: 1, may: false,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+}
+
+fn innate_massive_flooding_2 (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = game.get_power_usage()?;
+    let land_index = usage.target_land()?;
+
+    game.do_effect(DoDamageToInvadersDecision{land_index, damage: 2})?;
+    game.do_effect(PushDecision{land_index, count: 3, may: true,
+        kinds: vec![PieceKind::Invader(InvaderKind::Explorer), PieceKind::Invader(InvaderKind::Town)]})
+}
+
+fn innate_massive_flooding_3 (game: &mut GameState) -> Result<(), StepFailure> {
+    let usage = game.get_power_usage()?;
+    let land_index = usage.target_land()?;
+
+    game.do_effect(DoDamageToEachInvaderEffect{land_index, damage: 2, kinds: InvaderMap::new(true)})
+}
 
 impl SpiritDescription for SpiritDescriptionRiver {
     fn name(&self) -> &'static str { "River Surges in Sunlight" }
@@ -100,7 +117,7 @@ impl SpiritDescription for SpiritDescriptionRiver {
                 kind: PowerCardKind::Spirit(spirit_index),
                 elements: ElementMap::from_slice(&[Element::Sun, Element::Water]),
                 cost: 1, speed: PowerSpeed::Fast,
-                target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+                target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
                 effect: card_flash_floods,
             },
@@ -109,7 +126,7 @@ impl SpiritDescription for SpiritDescriptionRiver {
                 kind: PowerCardKind::Spirit(spirit_index),
                 elements: ElementMap::from_slice(&[Element::Sun, Element::Water, Element::Animal]),
                 cost: 0, speed: PowerSpeed::Slow,
-                target_filter: PowerTargetFilter::Land{range: 0, src: |_| true, dst: |_| true},
+                target_filter: PowerTargetFilter::Land{range: 0, src: |_, _| true, dst: |_| true},
 
                 effect: card_rivers_bounty,
             },
@@ -118,7 +135,7 @@ impl SpiritDescription for SpiritDescriptionRiver {
                 kind: PowerCardKind::Spirit(spirit_index),
                 elements: ElementMap::from_slice(&[Element::Water, Element::Earth]),
                 cost: 1, speed: PowerSpeed::Slow,
-                target_filter: PowerTargetFilter::Land{range: 1, src: |_| true, dst: |_| true},
+                target_filter: PowerTargetFilter::Land{range: 1, src: |_, _| true, dst: |_| true},
 
                 effect: card_wash_away,
             },
@@ -127,14 +144,38 @@ impl SpiritDescription for SpiritDescriptionRiver {
     fn get_power_progression(&self) -> Vec<&'static str> {
         vec![
             "Uncanny Melting",
-            "Nature's Resilence",
+            "Nature's Resilience",
             "Pull Beneath the Hungry Earth",
-            "Acelerated Rot",
+            "Accelerated Rot",
             "Song of Sanctity",
             "Tsunami",
             "Encompassing Ward"
         ]
     }
+    fn get_innate_powers(&self) -> Vec<InnatePowerDescription> {
+        vec![
+            InnatePowerDescription {
+                name: "Massive Flooding",
+                speed: PowerSpeed::Slow,
+                target_filter: PowerTargetFilter::Land{range: 1, src: sacred_site, dst: |_| true},
+
+                levels: vec![
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Sun, 1).map(Element::Water, 2),
+                        instead: false, effect: innate_massive_flooding_1,
+                    },
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Sun, 2).map(Element::Water, 3),
+                        instead: true, effect: innate_massive_flooding_2,
+                    },
+                    InnatePowerLevel {
+                        elements: ElementMap::new(0).map(Element::Sun, 3).map(Element::Water, 4).map(Element::Earth, 1),
+                        instead: true, effect: innate_massive_flooding_3,
+                    },
+                ],
+            },
+        ]
+    }
 
     fn do_setup(&self, game: &mut GameState, si: usize) -> Result<(), StepFailure> {
         // River puts 1 in the highest wetland
@@ -156,27 +197,17 @@ impl SpiritDescription for SpiritDescriptionRiver {
         Ok(())
     }
 
-    fn may_place_presence(&self, state: &[PresenceState; 13], presence_index: usize) -> Result<bool, StepFailure> {
-        match state[presence_index] {
-            PresenceState::OnTrack(track_loc) => {
-                if track_loc < _BOT_TRACK_START {
-                    // Top Track
-                    if track_loc == _TOP_TRACK_START {
-                        Ok(true)
-                    } else {
-                        Ok(state[(track_loc - 1) as usize] != PresenceState::OnTrack(track_loc - 1))
-                    }
-                } else {
-                    // Bottom Track
-                    if track_loc == _BOT_TRACK_START {
-                        Ok(true)
-                    } else {
-                        Ok(state[(track_loc - 1) as usize] != PresenceState::OnTrack(track_loc - 1))
-                    }
-                }
-            },
-            _ => Ok(true)
-        }
+    fn get_presence_tracks(&self) -> Vec<PresenceTrack> {
+        vec![
+            PresenceTrack{ first: 1, slots: vec![
+                TrackSlot::Energy(1), TrackSlot::Energy(2), TrackSlot::Energy(2), TrackSlot::Energy(3),
+                TrackSlot::Energy(4), TrackSlot::Energy(4), TrackSlot::Energy(5),
+            ]},
+            PresenceTrack{ first: 7, slots: vec![
+                TrackSlot::Plays(1), TrackSlot::Plays(2), TrackSlot::Plays(2), TrackSlot::Plays(3),
+                TrackSlot::ReclaimOne, TrackSlot::Plays(4), TrackSlot::Plays(5),
+            ]},
+        ]
     }
 
     fn do_growth(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure> {
@@ -187,7 +218,7 @@ impl SpiritDescription for SpiritDescriptionRiver {
                 |game, spirit_index| {
                     // Growth A
                     game.do_effect(ReclaimAllEffect{ spirit_index })?;
-                    game.do_effect(GainPowerCardDecision{ spirit_index })?;
+                    game.do_effect(GainPowerCardDecision{ spirit_index, shared_with: None })?;
                     game.do_effect(GenerateEnergyEffect{ spirit_index, energy: 1 })?;
 
                     Ok(())
@@ -202,73 +233,13 @@ impl SpiritDescription for SpiritDescriptionRiver {
                 |game, spirit_index| {
                     // Growth C
                     game.do_effect(AddPresenceDecision{ spirit_index, distance: 2 })?;
-                    game.do_effect(GainPowerCardDecision{ spirit_index })?;
+                    game.do_effect(GainPowerCardDecision{ spirit_index, shared_with: None })?;
                     
                     Ok(())
                 },
             ]
         })
     }
-
-    fn do_income(&self, game: &mut GameState, spirit_index: usize) -> Result<(), StepFailure> {
-        let spirit = game.get_spirit_mut(spirit_index as u8)?;
-        
-        let mut top_track_min = 15;
-        let mut bot_track_min = 15;
-
-        for presence in spirit.presence.iter() {
-            match *presence {
-                PresenceState::OnTrack(track_loc) => {
-                    if track_loc >= _BOT_TRACK_START {
-                        bot_track_min = min(bot_track_min, track_loc);
-                    } else {
-                        top_track_min = min(top_track_min, track_loc);
-                    }
-                },
-                _ => {}
-            }
-        }
-
-        // The above loop fings the minimum track spot with presence still on it. To find the
-        // minimum open spot we should have to subtract 1, to get normative numbering of the tracks
-        // (where 0 is the free space and 1 is the first open space) we need to add 1. These cancel
-        // out. Make sure to change this code if these assumptions change.
-        // TODO: use a struct for all this shit. 
-
-        top_track_min -= _TOP_TRACK_START;
-        bot_track_min -= _BOT_TRACK_START;
-
-        let card_plays;
-        let energy;
-        let mut reclaim_one = false;
-
-        match top_track_min {
-            1 | 2 => energy = 2,
-            3 => energy = 3,
-            4 | 5 => energy = 4,
-            6 => energy = 5,
-            _ => energy = 1,
-        }
-
-        match bot_track_min {
-            1 | 2 => card_plays = 2,
-            3 => card_plays = 3,
-            4 => { card_plays = 3; reclaim_one = true },
-            5 => { card_plays = 4; reclaim_one = true },
-            6 => { card_plays = 5; reclaim_one = true },
-            _ => card_plays = 1,
-        }
-
-        spirit.plays = card_plays; // TODO: effect maybe?
-        
-        game.do_effect(GenerateEnergyEffect{ spirit_index: spirit_index as u8, energy })?;
-        if reclaim_one {
-            // TODO: reclaim one decision
-            game.do_effect(NotImplementedEffect { what: "Reclaim One" })?;
-        }
-
-        Ok(())
-    }
 }
 
 impl SpiritDescriptionRiver {
@@ -5,20 +5,21 @@ extern crate simple_error;
 extern crate crypto;
 extern crate crossbeam;
 extern crate clap;
+extern crate yaml_rust;
 
 use std::error::Error;
-use std::sync::{Arc};
-use rand::prelude::*;
-use rand_chacha::{ChaChaRng};
-use self::crypto::digest::Digest;
-use self::crypto::sha1::Sha1;
-use clap::{Arg, App};
+use clap::{Arg, App, SubCommand};
 
 mod base;
 mod core;
 mod solve;
-
-use crate::core::{CoreContent};
+mod config;
+mod batch;
+mod record;
+mod snapshot;
+mod advise;
+#[cfg(test)]
+mod testing;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = App::new("Spirit Island Solver")
@@ -28,9 +29,23 @@ fn main() -> Result<(), Box<dyn Error>> {
         .arg(Arg::with_name("spirit")
             .short("s")
             .long("spirit")
-            .help("Selects a spirit to solve the game with.")
+            .help("Selects a spirit to solve the game with, repeat it for games with several spirits.")
+            .takes_value(true)
+            .multiple(true))
+        .arg(Arg::with_name("board")
+            .long("board")
+            .help("Selects the board of each spirit, in the order of the spirits (boards A, B, C and D in order by default).")
             .takes_value(true)
             .multiple(true))
+        .arg(Arg::with_name("adversary")
+            .long("adversary")
+            .help("Selects the adversary (brandenburg-prussia, england or sweden), none by default.")
+            .takes_value(true))
+        .arg(Arg::with_name("level")
+            .long("level")
+            .help("The level of the adversary, from 0 to 6.")
+            .takes_value(true)
+            .requires("adversary"))
         .arg(Arg::with_name("seed")
             .long("seed")
             .help("Sets the seet of the random system for reproducible results..")
@@ -45,65 +60,304 @@ fn main() -> Result<(), Box<dyn Error>> {
             .help("The type of solver to use.")
             .possible_value("rng")
             .possible_value("simple")
+            .possible_value("mcts")
             .takes_value(true))
         .arg(Arg::with_name("solver-take")
             .long("solver-take")
             .help("For solvers that only order decisions, how many to take. Use 0 to take all.")
             .takes_value(true))
+        .arg(Arg::with_name("iterations")
+            .long("iterations")
+            .help("For the mcts solver, how many games to play out (defaults to 1000 without --seconds).")
+            .takes_value(true))
+        .arg(Arg::with_name("seconds")
+            .long("seconds")
+            .help("For the mcts solver, how long to search for.")
+            .takes_value(true))
+        .arg(Arg::with_name("rollout-turns")
+            .long("rollout-turns")
+            .help("For the mcts solver, stops play outs this many turns ahead and scores the position with the evaluator.")
+            .takes_value(true))
+        .arg(Arg::with_name("horizon-turns")
+            .long("horizon-turns")
+            .help("For the tree solvers, searches only this many turns ahead, scoring the positions there with the evaluator and ranking the choices of the first decision.")
+            .takes_value(true))
+        .arg(Arg::with_name("horizon-decisions")
+            .long("horizon-decisions")
+            .help("For the tree solvers, searches only this many decisions ahead (like --horizon-turns).")
+            .takes_value(true))
+        .arg(Arg::with_name("transpositions")
+            .long("transpositions")
+            .help("How many positions the solver remembers to reuse when reached again, 0 to turn it off.")
+            .takes_value(true))
+        .arg(Arg::with_name("fate")
+            .long("fate")
+            .help("Fate choosing: the solver also decides the invader, fear and power card draws (branching over --solver-take of them), reporting whether the game can be won at all."))
         .arg(Arg::with_name("print-best")
             .long("print-best")
             .help("Attempted to print the best game sequence."))
+        .arg(Arg::with_name("config")
+            .long("config")
+            .help("Loads the run (content, game and solver) from a configuration file instead of the arguments above.")
+            .takes_value(true)
+            .conflicts_with_all(&["spirit", "board", "adversary", "level", "seed", "solver", "solver-take", "iterations", "seconds", "rollout-turns", "horizon-turns", "horizon-decisions", "transpositions", "fate"]))
+        .arg(Arg::with_name("batch")
+            .long("batch")
+            .help("Runs every combination of the `all` values in the configuration file, reporting a row for each.")
+            .requires("config"))
+        .arg(Arg::with_name("output")
+            .long("output")
+            .help("Writes the batch results as csv to this file.")
+            .takes_value(true)
+            .requires("batch"))
+        .arg(Arg::with_name("record")
+            .long("record")
+            .help("Writes the first best game to this file as a game record (see `replay`).")
+            .takes_value(true)
+            .conflicts_with("batch"))
+        .arg(Arg::with_name("resume")
+            .long("resume")
+            .help("Starts solving from a snapshot of a game in progress (see `replay --snapshot`).")
+            .takes_value(true)
+            .conflicts_with_all(&["config", "spirit", "adversary", "seed", "record"]))
+        .subcommand(SubCommand::with_name("replay")
+            .about("Replays a game record, logging every step.")
+            .arg(Arg::with_name("record")
+                .help("The game record file to replay.")
+                .required(true))
+            .arg(Arg::with_name("snapshot")
+                .long("snapshot")
+                .help("Stops the replay at the step given by --at, writing a snapshot of the game to this file.")
+                .takes_value(true)
+                .requires("at"))
+            .arg(Arg::with_name("at")
+                .long("at")
+                .help("The step to stop at, for example `turn 3 growth`.")
+                .takes_value(true)
+                .requires("snapshot")))
+        .subcommand(SubCommand::with_name("advise")
+            .about("Ranks the choices of the next decision in a position, a snapshot of a real game where the draw piles may be hidden.")
+            .arg(Arg::with_name("position")
+                .help("The position file to advise on.")
+                .required(true))
+            .arg(Arg::with_name("samples")
+                .long("samples")
+                .help("How many times to deal out the hidden cards.")
+                .takes_value(true))
+            .arg(Arg::with_name("playouts")
+                .long("playouts")
+                .help("How many random games to play out per choice per sample.")
+                .takes_value(true)))
         .get_matches();
 
+    let threads = args.value_of("threads").unwrap_or("4").parse::<usize>().unwrap();
 
-    let mut seed: [u8; 32] = [0; 32];
-    let mut hasher = Sha1::new();
-    hasher.input_str(args.value_of("seed").unwrap_or("default"));
-    hasher.result(&mut seed);
-    let rng = Box::new(base::DeterministicChaCha::new(ChaChaRng::from_seed(seed)));
+    if let Some(replay_args) = args.subcommand_matches("replay") {
+        let record = record::GameRecord::load(replay_args.value_of("record").unwrap())?;
+        let state = record.config.make_state()?;
 
-    let mut content: Vec<Box<dyn base::ContentPack>> = Vec::new();
-    content.push(Box::new(CoreContent::new()));
+        if let Some(snapshot_path) = replay_args.value_of("snapshot") {
+            let at = snapshot::parse_step(replay_args.value_of("at").unwrap())?;
 
-    let mut spirits = Vec::new();
-    if let Some(arg_spirits) = args.values_of("spirit") {
-        for spirit in arg_spirits {
-            match base::search_for_spirit(&content, spirit) {
-                Some(spirit) => spirits.push(spirit),
-                None => bail!("Spirit `{}` not found.", spirit),
+            let final_state = solve::resimulate_game(&state, record.choices, Some(at))?;
+            if final_state.step != at {
+                bail!("The game ended before reaching `{}`.", replay_args.value_of("at").unwrap());
             }
-            
+
+            snapshot::Snapshot::capture(&record.config, &final_state)?.save(snapshot_path)?;
+            println!("Wrote a snapshot at `{}` to `{}`.", final_state.step, snapshot_path);
+
+            return Ok(());
         }
+
+        let final_state = solve::resimulate_game(&state, record.choices, None)?;
+        match record.score {
+            Some(score) if score != final_state.score_game() =>
+                bail!("Replay ended with score {} but the record has {}.", final_state.score_game(), score),
+            _ => println!("  score: {}", final_state.score_game()),
+        }
+
+        return Ok(());
     }
 
-    let threads = args.value_of("threads").unwrap_or("4").parse::<usize>().unwrap();
+    if let Some(advise_args) = args.subcommand_matches("advise") {
+        let position = snapshot::Snapshot::load(advise_args.value_of("position").unwrap())?;
+        let samples = advise_args.value_of("samples").unwrap_or("16").parse::<usize>()?;
+        let playouts = advise_args.value_of("playouts").unwrap_or("16").parse::<usize>()?;
 
+        advise::advise(&position, samples, playouts, threads)?;
 
+        return Ok(());
+    }
 
-    let adversary: Box<dyn base::AdversaryDescription> = Box::new(base::DefaultAdversaryDescription::new());
+    let resume = match args.value_of("resume") {
+        Some(snapshot_path) => Some(snapshot::Snapshot::load(snapshot_path)?),
+        None => None,
+    };
 
-    let map = Box::new(base::make_map(&content, vec!["A"]));
+    let solver = args.value_of("solver").unwrap_or("simple");
+    // The mcts solver samples the choices itself, it only needs limiting for huge decisions.
+    let solver_take = args.value_of("solver-take").unwrap_or(if solver == "mcts" { "0" } else { "2" }).parse::<u8>().unwrap();
+    let order = match solver {
+        "rng" => config::PlayOrder::Rng,
+        _ => config::PlayOrder::Simple,
+    };
+    let cli_engine = match solver {
+        "mcts" => {
+            let iterations = match args.value_of("iterations") {
+                Some(iterations) => Some(iterations.parse::<usize>()?),
+                None => None,
+            };
+            let seconds = match args.value_of("seconds") {
+                Some(seconds) => Some(seconds.parse::<f64>()?),
+                None => None,
+            };
+            let rollout_turns = match args.value_of("rollout-turns") {
+                Some(turns) => Some(turns.parse::<u8>()?),
+                None => None,
+            };
 
-    let description = Arc::new(base::GameDescription::new(content, adversary, spirits, map));
-    let state = base::GameState::new(description, rng);
+            config::SolverEngine::Mcts(solve::MctsOptions {
+                iterations: if iterations.is_none() && seconds.is_none() { Some(1000) } else { iterations },
+                seconds,
+                exploration: std::f64::consts::SQRT_2,
+                rollout_turns,
+            })
+        },
+        _ => {
+            let horizon_turns = match args.value_of("horizon-turns") {
+                Some(turns) => Some(turns.parse::<u8>()?),
+                None => None,
+            };
+            let horizon_decisions = match args.value_of("horizon-decisions") {
+                Some(decisions) => Some(decisions.parse::<usize>()?),
+                None => None,
+            };
+            if horizon_turns == Some(0) || horizon_decisions == Some(0) {
+                bail!("A horizon of 0 doesn't search anything.");
+            }
+
+            config::SolverEngine::Tree(solve::TreeOptions {
+                horizon_turns,
+                horizon_decisions,
+            })
+        },
+    };
+    let cli_transpositions = match args.value_of("transpositions") {
+        Some(transpositions) => transpositions.parse::<usize>()?,
+        None => config::DEFAULT_TRANSPOSITIONS,
+    };
+
+    let mut cli_strategy = vec![config::StrategyLayer::PlayBranch { take: solver_take, order }];
+    if args.is_present("fate") {
+        cli_strategy.push(config::StrategyLayer::SetupBranch {
+            take: solver_take,
+            fear: true,
+            invader: true,
+            blight: false,
+            minor: true,
+            major: true,
+            events: false,
+        });
+    }
+
+    let config = match (args.value_of("config"), &resume) {
+        (Some(config_path), _) => config::RunConfig::load(config_path)?,
+        (None, Some(snapshot)) => {
+            let mut config = snapshot.config.clone();
+            // The solver arguments override whatever the snapshot asks for.
+            if args.is_present("transpositions") {
+                config.solver.transpositions = cli_transpositions;
+            }
+            if config.solver.strategy.is_empty() || args.is_present("solver") || args.is_present("solver-take") || args.is_present("fate")
+                || args.is_present("horizon-turns") || args.is_present("horizon-decisions") {
+                config.solver.engine = cli_engine;
+                config.solver.strategy = cli_strategy;
+            }
+            config
+        },
+        (None, None) => {
+            let mut spirits = Vec::new();
+            if let Some(arg_spirits) = args.values_of("spirit") {
+                for spirit in arg_spirits {
+                    spirits.push(config::Choice::One(spirit.to_string()));
+                }
+            }
+            let boards = match args.values_of("board") {
+                Some(arg_boards) => arg_boards.map(|board| config::Choice::One(board.to_string())).collect(),
+                None => ["A", "B", "C", "D"].iter().take(spirits.len()).map(|board| config::Choice::One(board.to_string())).collect(),
+            };
+
+            config::RunConfig {
+                seed: args.value_of("seed").unwrap_or("default").to_string(),
+                content: vec!["core".to_string()],
+                game: config::GameConfig {
+                    spirits,
+                    boards,
+                    adversary: config::AdversaryConfig {
+                        name: config::Choice::One(args.value_of("adversary").unwrap_or("none").to_string()),
+                        difficulty: config::Choice::One(args.value_of("level").unwrap_or("0").parse::<u8>()?),
+                    },
+                    events: false,
+                },
+                solver: config::SolverConfig {
+                    engine: cli_engine,
+                    transpositions: cli_transpositions,
+                    evaluator: solve::EvaluationWeights::new(),
+                    strategy: cli_strategy,
+                },
+            }
+        },
+    };
+    config.validate()?;
+
+    if args.is_present("batch") {
+        batch::run_batch(&config, threads, args.value_of("output"))?;
 
-    let solver_name = args.value_of("solver").unwrap_or("simple");
-    let solver_strategy =
-        if solver_name == "simple" {
-            let solver_take = args.value_of("solver-take").unwrap_or("2").parse::<u8>().unwrap();
-            Ok(solve::SimpleDecisionMaker::new(solver_take) as Box<dyn solve::SolveStrategy>)
-        } else if solver_name == "rng" {
-            let solver_take = args.value_of("solver-take").unwrap_or("2").parse::<u8>().unwrap();
-            let solver_rng = Box::new(base::DeterministicChaCha::new(ChaChaRng::from_seed(seed)));
-            Ok(solve::StochasticDecisionMaker::new(solver_rng, solver_take) as Box<dyn solve::SolveStrategy>)
+        return Ok(());
+    }
+
+    let state = match &resume {
+        Some(snapshot) => snapshot.make_state()?,
+        None => config.make_state()?,
+    };
+    let solver_strategy = config.make_strategy()?;
+
+    let (victories, max_score, best_game) = match &config.solver.engine {
+        config::SolverEngine::Tree(options) => {
+            let mut solver = solve::SolveEngine::new(&state, solver_strategy, config.make_evaluator(), options.clone(), config.solver.transpositions);
+            solver.print_first_best_game = args.is_present("print-best");
+
+            let stats = solver.main(threads)?;
+            (stats.victories, stats.max_score, stats.first_best_game)
+        },
+        config::SolverEngine::Mcts(options) => {
+            let solver = solve::MctsEngine::new(&state, solver_strategy, config.make_evaluator(), options.clone(), &config.seed);
+
+            let result = solver.main(threads)?;
+            if args.is_present("print-best") {
+                solve::resimulate_game(&state, result.best_game.clone(), None)?;
+            }
+            (result.rows.iter().map(|row| row.victories).sum(), result.max_score, result.best_game)
+        },
+    };
+
+    if state.fate.any() {
+        if victories > 0 {
+            println!("  winnable: yes, {} of the branched fates end in victory", victories);
         } else {
-            Err("Unknown solver.")
-        }.unwrap();
+            println!("  winnable: no victory among the branched fates");
+        }
+    }
 
-    let mut solver = solve::SolveEngine::new(&state, solver_strategy);
-    solver.print_first_best_game = args.is_present("print-best");
-    
-    solver.main(threads)?;
+    if let Some(record_path) = args.value_of("record") {
+        if best_game.is_empty() {
+            bail!("No game was played to the end, there is nothing to record.");
+        }
+        let record = record::GameRecord::new(&config, Some(max_score), best_game);
+        record.save(record_path)?;
+        println!("Recorded the first best game to `{}`.", record_path);
+    }
 
     Ok(())
 }
@@ -0,0 +1,123 @@
+use std::{
+    error::Error,
+    fs,
+    collections::{VecDeque},
+};
+
+use yaml_rust::{Yaml, YamlLoader, YamlEmitter};
+
+use crate::base::{DecisionChoice};
+use crate::config::{RunConfig, check_keys};
+
+
+/*
+    Game records store a single game on disk so it can be archived, shared and replayed.
+
+    The header (`seed`, `content`, `game` and in fate or power progression mode `solver`) is the
+    same as a run configuration's, it is everything needed to recreate the initial game state.
+    After it:
+
+    * `score`: (optional) the score the game ended with, for reference only.
+    * `choices`: the decision edges taken, in order. Each edge is the list of choices (in the text
+      form of `DecisionChoice`) consumed between two solver branches.
+
+    Replaying only works against the same version of the content, the choices are indices into
+    whatever the game offered at that point.
+*/
+
+
+pub struct GameRecord {
+    pub config: RunConfig,
+    pub score: Option<i16>,
+    pub choices: Vec<VecDeque<DecisionChoice>>,
+}
+
+impl GameRecord {
+    pub fn new(config: &RunConfig, score: Option<i16>, choices: Vec<VecDeque<DecisionChoice>>) -> GameRecord {
+        GameRecord {
+            config: config.clone(),
+            score,
+            choices,
+        }
+    }
+
+    pub fn load(path: &str) -> Result<GameRecord, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| Box::<dyn Error>::from(format!("Could not read `{}`: {}", path, e)))?;
+
+        GameRecord::parse(&text)
+            .map_err(|e| Box::<dyn Error>::from(format!("In `{}`: {}", path, e)))
+    }
+
+    pub fn parse(text: &str) -> Result<GameRecord, Box<dyn Error>> {
+        let docs = YamlLoader::load_from_str(text)?;
+        let doc = match docs.first() {
+            Some(doc) => doc,
+            None => bail!("Game record is empty."),
+        };
+
+        check_keys(doc, "<root>", &["seed", "content", "game", "solver", "score", "choices"])?;
+
+        let config = RunConfig::from_yaml(doc)?;
+
+        let score = match &doc["score"] {
+            Yaml::Integer(i) => Some(*i as i16),
+            Yaml::BadValue | Yaml::Null => None,
+            _ => bail!("`score` must be a number."),
+        };
+
+        let edges = match &doc["choices"] {
+            Yaml::Array(edges) => edges,
+            Yaml::BadValue | Yaml::Null => bail!("`choices` is required."),
+            _ => bail!("`choices` must be a list."),
+        };
+
+        let mut choices = Vec::new();
+        for (i, edge) in edges.iter().enumerate() {
+            let edge = match edge {
+                Yaml::Array(edge) => edge,
+                _ => bail!("`choices` entry {} must be a list.", i),
+            };
+
+            let mut parsed = VecDeque::new();
+            for choice in edge.iter() {
+                match choice.as_str() {
+                    Some(text) => parsed.push_back(text.parse::<DecisionChoice>()
+                        .map_err(|e| format!("`choices` entry {}: {}", i, e))?),
+                    None => bail!("`choices` entry {} must be a list of strings.", i),
+                }
+            }
+            choices.push(parsed);
+        }
+
+        Ok(GameRecord {
+            config,
+            score,
+            choices,
+        })
+    }
+
+    pub fn to_yaml_string(&self) -> Result<String, Box<dyn Error>> {
+        let mut doc = self.config.to_header_yaml();
+
+        if let Some(score) = self.score {
+            doc.insert(Yaml::String("score".to_string()), Yaml::Integer(score as i64));
+        }
+
+        let edges = self.choices.iter()
+            .map(|edge| Yaml::Array(edge.iter().map(|c| Yaml::String(c.to_string())).collect()))
+            .collect();
+        doc.insert(Yaml::String("choices".to_string()), Yaml::Array(edges));
+
+        let mut text = String::new();
+        YamlEmitter::new(&mut text).dump(&Yaml::Hash(doc))?;
+        text.push('\n');
+
+        Ok(text)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_yaml_string()?)
+            .map_err(|e| Box::<dyn Error>::from(format!("Could not write `{}`: {}", path, e)))
+    }
+}
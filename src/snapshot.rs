@@ -0,0 +1,963 @@
+use std::{
+    cmp::min,
+    convert::TryFrom,
+    error::Error,
+    fs,
+    sync::{Arc},
+    collections::{VecDeque},
+};
+
+use rand::prelude::*;
+use rand_chacha::{ChaChaRng};
+use yaml_rust::{Yaml, YamlLoader, YamlEmitter, yaml};
+
+use crate::base::*;
+use crate::config::{RunConfig, check_keys, as_string, as_switch, as_number};
+
+
+/*
+    Snapshots store a game in progress so a solve can start from a mid-game position.
+
+    The header (`seed`, `content`, `game` and optionally `solver`) is a run configuration, after it
+    `state` holds everything in the game state that changes during play. Descriptions are never
+    stored, cards are referenced by name and spirits by their position in `game.spirits`.
+
+    Positions are snapshots of real games, where draw piles are hidden (see `Pile`) and there is no
+    rng position, they can only be used by `advise` which deals the hidden cards itself.
+
+    Snapshots can only be taken between steps, when no effect is being resolved and no choices are
+    pending. Steps are written as `init`, `setup-spirit`, `setup-explore`, `victory`, `defeat` or
+    `turn <n> <step>` where `<step>` is one of: `growth`, `income`, `play`, `fast`,
+    `blighted-island`, `event <card> <part>`, `fear <card>`, `invader <action> <card>`, `advance`,
+    `slow`, `time-passes`. Turns count from 1, like the log.
+*/
+
+
+const TOKEN_KINDS: [TokenKind; 6] = [
+    TokenKind::Blight, TokenKind::Beast, TokenKind::Wilds,
+    TokenKind::Disease, TokenKind::Strife, TokenKind::Badlands,
+];
+
+const ELEMENTS: [(Element, &str); 8] = [
+    (Element::Sun, "sun"), (Element::Moon, "moon"), (Element::Fire, "fire"), (Element::Air, "air"),
+    (Element::Water, "water"), (Element::Earth, "earth"), (Element::Plant, "plant"), (Element::Animal, "animal"),
+];
+
+
+fn key(s: &str) -> Yaml {
+    Yaml::String(s.to_string())
+}
+
+fn number<T: Into<i64>>(v: T) -> Yaml {
+    Yaml::Integer(v.into())
+}
+
+fn list_of<T, F: Fn(&T) -> Yaml>(items: &[T], f: F) -> Yaml {
+    Yaml::Array(items.iter().map(f).collect())
+}
+
+fn as_list<'a>(yaml: &'a Yaml, what: &str) -> Result<&'a Vec<Yaml>, Box<dyn Error>> {
+    match yaml {
+        Yaml::Array(list) => Ok(list),
+        _ => bail!("`{}` must be a list.", what),
+    }
+}
+
+fn as_number_or<T: TryFrom<i64>>(yaml: &Yaml, what: &str, default: T) -> Result<T, Box<dyn Error>> {
+    match yaml {
+        Yaml::BadValue | Yaml::Null => Ok(default),
+        _ => as_number(yaml, what),
+    }
+}
+
+// Empty lists are optional.
+fn as_optional_list<'a>(yaml: &'a Yaml, what: &str) -> Result<&'a [Yaml], Box<dyn Error>> {
+    match yaml {
+        Yaml::BadValue | Yaml::Null => Ok(&[]),
+        _ => Ok(as_list(yaml, what)?.as_slice()),
+    }
+}
+
+
+fn step_to_string(step: &GameStep) -> String {
+    match step {
+        GameStep::Init => "init".to_string(),
+        GameStep::SetupSpirit => "setup-spirit".to_string(),
+        GameStep::SetupExplore => "setup-explore".to_string(),
+        GameStep::Victory => "victory".to_string(),
+        GameStep::Defeat => "defeat".to_string(),
+        GameStep::Turn(turn, turn_step) => {
+            let step = match turn_step {
+                TurnStep::Spirit(SpiritStep::Growth) => "growth".to_string(),
+                TurnStep::Spirit(SpiritStep::Income) => "income".to_string(),
+                TurnStep::Spirit(SpiritStep::Play) => "play".to_string(),
+                TurnStep::FastPower => "fast".to_string(),
+                TurnStep::Invader(InvaderStep::BlightedIsland) => "blighted-island".to_string(),
+                TurnStep::Invader(InvaderStep::Event(card, part)) => format!("event {} {}", card, part),
+                TurnStep::Invader(InvaderStep::FearEffect(card)) => format!("fear {}", card),
+                TurnStep::Invader(InvaderStep::InvaderAction(action, card)) => format!("invader {} {}", action, card),
+                TurnStep::Invader(InvaderStep::InvaderAdvance) => "advance".to_string(),
+                TurnStep::SlowPower => "slow".to_string(),
+                TurnStep::TimePasses => "time-passes".to_string(),
+            };
+            format!("turn {} {}", turn + 1, step)
+        },
+    }
+}
+
+pub fn parse_step(text: &str) -> Result<GameStep, Box<dyn Error>> {
+    fn n(s: &str) -> Result<u8, Box<dyn Error>> {
+        s.parse::<u8>().map_err(|_| Box::<dyn Error>::from(format!("expected a number but found `{}`", s)))
+    }
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    Ok(match words.as_slice() {
+        ["init"] => GameStep::Init,
+        ["setup-spirit"] => GameStep::SetupSpirit,
+        ["setup-explore"] => GameStep::SetupExplore,
+        ["victory"] => GameStep::Victory,
+        ["defeat"] => GameStep::Defeat,
+        ["turn", turn, rest @ ..] => {
+            let turn = n(turn)?;
+            if turn == 0 {
+                bail!("Turns count from 1.");
+            }
+
+            let turn_step = match rest {
+                ["growth"] => TurnStep::Spirit(SpiritStep::Growth),
+                ["income"] => TurnStep::Spirit(SpiritStep::Income),
+                ["play"] => TurnStep::Spirit(SpiritStep::Play),
+                ["fast"] => TurnStep::FastPower,
+                ["blighted-island"] => TurnStep::Invader(InvaderStep::BlightedIsland),
+                ["event", card, part] => TurnStep::Invader(InvaderStep::Event(n(card)?, n(part)?)),
+                ["fear", card] => TurnStep::Invader(InvaderStep::FearEffect(n(card)?)),
+                ["invader", action, card] => TurnStep::Invader(InvaderStep::InvaderAction(n(action)?, n(card)?)),
+                ["advance"] => TurnStep::Invader(InvaderStep::InvaderAdvance),
+                ["slow"] => TurnStep::SlowPower,
+                ["time-passes"] => TurnStep::TimePasses,
+                _ => bail!("Unknown step `{}`.", text),
+            };
+            GameStep::Turn(turn - 1, turn_step)
+        },
+        _ => bail!("Unknown step `{}`.", text),
+    })
+}
+
+fn presence_to_string(presence: &PresenceState) -> String {
+    match presence {
+        PresenceState::OnBoard(land) => format!("board {}", land),
+        PresenceState::OnTrack(track) => format!("track {}", track),
+        PresenceState::Destroyed => "destroyed".to_string(),
+        PresenceState::RemovedFromGame => "removed".to_string(),
+    }
+}
+
+fn parse_presence(text: &str) -> Result<PresenceState, Box<dyn Error>> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    Ok(match words.as_slice() {
+        ["board", land] => PresenceState::OnBoard(land.parse()?),
+        ["track", track] => PresenceState::OnTrack(track.parse()?),
+        ["destroyed"] => PresenceState::Destroyed,
+        ["removed"] => PresenceState::RemovedFromGame,
+        _ => bail!("Unknown presence `{}`.", text),
+    })
+}
+
+
+// Cards keep their index in the list they were dealt from (see `GameState::step`), all of a
+// kind for power cards.
+fn find_power_card(desc: &GameDescription, name: &str) -> Result<PowerCard, Box<dyn Error>> {
+    let card = match desc.powers.iter().find(|pcd| pcd.name == name) {
+        Some(card) => card,
+        None => bail!("Power card `{}` not found.", name),
+    };
+
+    let index = desc.powers.iter()
+        .filter(|pcd| pcd.kind == card.kind)
+        .position(|pcd| Arc::ptr_eq(pcd, card))
+        .unwrap();
+
+    Ok(PowerCard { desc: Arc::clone(card), index })
+}
+
+fn find_fear_card(desc: &GameDescription, name: &str) -> Result<FearCard, Box<dyn Error>> {
+    match desc.fear.iter().position(|fcd| fcd.name == name) {
+        Some(index) => Ok(FearCard { desc: Arc::clone(&desc.fear[index]), index }),
+        None => bail!("Fear card `{}` not found.", name),
+    }
+}
+
+fn find_blight_card(desc: &GameDescription, name: &str) -> Result<BlightCard, Box<dyn Error>> {
+    match desc.blight.iter().position(|bcd| bcd.name == name) {
+        Some(index) => Ok(BlightCard { desc: Arc::clone(&desc.blight[index]), index }),
+        None => bail!("Blight card `{}` not found.", name),
+    }
+}
+
+fn find_event_card(desc: &GameDescription, name: &str) -> Result<EventCard, Box<dyn Error>> {
+    match desc.events.iter().position(|ecd| ecd.name == name) {
+        Some(index) => Ok(EventCard { desc: Arc::clone(&desc.events[index]), index }),
+        None => bail!("Event card `{}` not found (is the events variant on?).", name),
+    }
+}
+
+fn power_cards_to_yaml(cards: &[PowerCard]) -> Yaml {
+    list_of(cards, |c| key(c.desc.name))
+}
+
+fn parse_power_cards(desc: &GameDescription, yaml: &Yaml, what: &str) -> Result<Vec<PowerCard>, Box<dyn Error>> {
+    as_optional_list(yaml, what)?.iter()
+        .map(|c| find_power_card(desc, &as_string(c, what)?))
+        .collect()
+}
+
+fn fear_cards_to_yaml(cards: &[FearCard]) -> Yaml {
+    list_of(cards, |c| key(c.desc.name))
+}
+
+fn parse_fear_cards(desc: &GameDescription, yaml: &Yaml, what: &str) -> Result<Vec<FearCard>, Box<dyn Error>> {
+    as_optional_list(yaml, what)?.iter()
+        .map(|c| find_fear_card(desc, &as_string(c, what)?))
+        .collect()
+}
+
+fn blight_cards_to_yaml(cards: &[BlightCard]) -> Yaml {
+    list_of(cards, |c| key(c.desc.name))
+}
+
+fn parse_blight_cards(desc: &GameDescription, yaml: &Yaml, what: &str) -> Result<Vec<BlightCard>, Box<dyn Error>> {
+    as_optional_list(yaml, what)?.iter()
+        .map(|c| find_blight_card(desc, &as_string(c, what)?))
+        .collect()
+}
+
+fn event_cards_to_yaml(cards: &[EventCard]) -> Yaml {
+    list_of(cards, |c| key(c.desc.name))
+}
+
+fn parse_event_cards(desc: &GameDescription, yaml: &Yaml, what: &str) -> Result<Vec<EventCard>, Box<dyn Error>> {
+    as_optional_list(yaml, what)?.iter()
+        .map(|c| find_event_card(desc, &as_string(c, what)?))
+        .collect()
+}
+
+fn invader_cards_to_yaml(cards: &[InvaderCard]) -> Yaml {
+    list_of(cards, |c| key(&c.to_string()))
+}
+
+fn parse_invader_cards(yaml: &Yaml, what: &str) -> Result<Vec<InvaderCard>, Box<dyn Error>> {
+    as_optional_list(yaml, what)?.iter()
+        .map(|c| Ok(as_string(c, what)?.parse::<InvaderCard>()?))
+        .collect()
+}
+
+fn power_deck_to_yaml(deck: &PowerDeck) -> Yaml {
+    let mut hash = yaml::Hash::new();
+    hash.insert(key("draw"), power_cards_to_yaml(&deck.draw));
+    hash.insert(key("pending"), power_cards_to_yaml(&deck.pending));
+    hash.insert(key("discard"), power_cards_to_yaml(&deck.discard));
+    Yaml::Hash(hash)
+}
+
+// The draw pile is returned separately, it may be hidden.
+fn parse_power_deck(desc: &GameDescription, yaml: &Yaml, what: &str) -> Result<(PowerDeck, Pile<PowerCard>), Box<dyn Error>> {
+    check_keys(yaml, what, &["draw", "pending", "discard"])?;
+
+    let mut deck = PowerDeck::new();
+    let draw = parse_pile(&yaml["draw"], what, |yaml, what| parse_power_cards(desc, yaml, what))?;
+    deck.pending = parse_power_cards(desc, &yaml["pending"], what)?;
+    deck.discard = parse_power_cards(desc, &yaml["discard"], what)?;
+    Ok((deck, draw))
+}
+
+
+// Positions (see `advise`) describe a real game, where the draw piles can't be seen. Instead of a
+// list of cards their draw piles are given as a count, or `unknown` for every card not seen
+// elsewhere.
+enum Pile<T> {
+    Known(Vec<T>),
+    Hidden(Option<usize>),
+}
+
+fn parse_pile<T, F>(yaml: &Yaml, what: &str, parse: F) -> Result<Pile<T>, Box<dyn Error>>
+    where F: Fn(&Yaml, &str) -> Result<Vec<T>, Box<dyn Error>>
+{
+    match yaml {
+        Yaml::Integer(_) => Ok(Pile::Hidden(Some(as_number(yaml, what)?))),
+        Yaml::String(s) if s == "unknown" => Ok(Pile::Hidden(None)),
+        _ => Ok(Pile::Known(parse(yaml, what)?)),
+    }
+}
+
+// Deals `count` (or all) of `unseen`, which have already been shuffled.
+fn deal<T>(mut unseen: Vec<T>, count: Option<usize>, what: &str) -> Result<Vec<T>, Box<dyn Error>> {
+    match count {
+        Some(count) if count > unseen.len() => bail!("`{}` has {} cards but only {} have not been seen elsewhere.", what, count, unseen.len()),
+        Some(count) => {
+            unseen.truncate(count);
+            Ok(unseen)
+        },
+        None => Ok(unseen),
+    }
+}
+
+// The invader deck is stacked by stage (see `AdversaryDescription::invader_deck_stages`), so the
+// cards not seen yet fill the stages left, shuffled within each stage.
+fn deal_invader_draw(stages: &[u8], seen: &[InvaderCard], count: Option<usize>, rng: &mut dyn RngCore) -> Result<Vec<InvaderCard>, Box<dyn Error>> {
+    let mut unseen = generate_invader_deck();
+    for card in seen.iter() {
+        match unseen.iter().position(|c| c == card) {
+            Some(i) => { unseen.remove(i); },
+            None => bail!("Invader card `{}` is seen more times than it is in the deck.", card),
+        }
+    }
+
+    let remaining = stages.get(seen.len()..).unwrap_or(&[]);
+    if let Some(count) = count {
+        if count != remaining.len() {
+            bail!("`state.invader.draw` has {} cards but the deck has {} left.", count, remaining.len());
+        }
+    }
+
+    Ok(stack_invader_deck(remaining, &unseen, Some(rng))?)
+}
+
+
+// An invader as it is added, with the changes of the adversary.
+fn fresh_invader(desc: &GameDescription, kind: InvaderKind) -> Invader {
+    let mut invader = Invader::new(kind);
+    desc.adversary.modify_invader(&mut invader);
+    invader
+}
+
+// Pieces are written by name unless they have been changed this turn (damaged, or had their
+// attack modified), then in full.
+fn invader_to_yaml(desc: &GameDescription, invader: &Invader) -> Yaml {
+    let fresh = fresh_invader(desc, invader.kind);
+    if invader.health_cur == fresh.health_cur && invader.health_max == fresh.health_max && invader.attack == fresh.attack {
+        return key(&invader.kind.to_string());
+    }
+
+    let mut hash = yaml::Hash::new();
+    hash.insert(key("kind"), key(&invader.kind.to_string()));
+    hash.insert(key("health"), number(invader.health_cur));
+    hash.insert(key("max-health"), number(invader.health_max));
+    hash.insert(key("attack"), number(invader.attack));
+    Yaml::Hash(hash)
+}
+
+fn parse_invader(desc: &GameDescription, yaml: &Yaml) -> Result<Invader, Box<dyn Error>> {
+    let kind_of = |text: &str| match text.parse::<PieceKind>()? {
+        PieceKind::Invader(kind) => Ok(kind),
+        _ => Err(Box::<dyn Error>::from(format!("`{}` is not an invader.", text))),
+    };
+
+    match yaml {
+        Yaml::Hash(_) => {
+            check_keys(yaml, "invaders", &["kind", "health", "max-health", "attack"])?;
+            let mut invader = fresh_invader(desc, kind_of(&as_string(&yaml["kind"], "invaders.kind")?)?);
+            invader.health_cur = as_number(&yaml["health"], "invaders.health")?;
+            invader.health_max = as_number(&yaml["max-health"], "invaders.max-health")?;
+            invader.attack = as_number(&yaml["attack"], "invaders.attack")?;
+            Ok(invader)
+        },
+        _ => Ok(fresh_invader(desc, kind_of(&as_string(yaml, "invaders")?)?)),
+    }
+}
+
+fn dahan_to_yaml(dahan: &Dahan) -> Yaml {
+    let fresh = Dahan::new();
+    if dahan.health_cur == fresh.health_cur && dahan.health_max == fresh.health_max && dahan.attack == fresh.attack {
+        return key("Dahan");
+    }
+
+    let mut hash = yaml::Hash::new();
+    hash.insert(key("health"), number(dahan.health_cur));
+    hash.insert(key("max-health"), number(dahan.health_max));
+    hash.insert(key("attack"), number(dahan.attack));
+    Yaml::Hash(hash)
+}
+
+fn parse_dahan(yaml: &Yaml) -> Result<Dahan, Box<dyn Error>> {
+    let mut dahan = Dahan::new();
+    match yaml {
+        Yaml::Hash(_) => {
+            check_keys(yaml, "dahan", &["health", "max-health", "attack"])?;
+            dahan.health_cur = as_number(&yaml["health"], "dahan.health")?;
+            dahan.health_max = as_number(&yaml["max-health"], "dahan.max-health")?;
+            dahan.attack = as_number(&yaml["attack"], "dahan.attack")?;
+        },
+        _ => {
+            let name = as_string(yaml, "dahan")?;
+            if name != "Dahan" {
+                bail!("`{}` is not a dahan.", name);
+            }
+        },
+    }
+    Ok(dahan)
+}
+
+fn land_to_yaml(desc: &GameDescription, land: &LandState) -> Yaml {
+    let mut tokens = yaml::Hash::new();
+    for kind in TOKEN_KINDS.iter() {
+        if land.tokens[*kind] != 0 {
+            tokens.insert(key(&kind.to_string()), number(land.tokens[*kind]));
+        }
+    }
+
+    let mut hash = yaml::Hash::new();
+    hash.insert(key("in-play"), Yaml::Boolean(land.is_in_play));
+    hash.insert(key("tokens"), Yaml::Hash(tokens));
+    hash.insert(key("presence"), Yaml::Array((0..desc.spirits.len()).map(|i| number(land.presence[i as u8])).collect()));
+    hash.insert(key("invaders"), list_of(&land.invaders, |i| invader_to_yaml(desc, i)));
+    hash.insert(key("dahan"), list_of(&land.dahan, dahan_to_yaml));
+    hash.insert(key("defense"), number(land.defense));
+    hash.insert(key("fear-generated"), number(land.fear_generated_here_this_round));
+
+    let mut skip = Vec::new();
+    if land.skip_explore { skip.push("Explore"); }
+    match (land.skip_build[InvaderKind::Town], land.skip_build[InvaderKind::City]) {
+        (true, true) => skip.push("Build"),
+        (true, false) => skip.push("Build Town"),
+        (false, true) => skip.push("Build City"),
+        (false, false) => {},
+    }
+    if land.skip_ravage { skip.push("Ravage"); }
+    if !skip.is_empty() {
+        hash.insert(key("skip"), Yaml::Array(skip.into_iter().map(key).collect()));
+    }
+    if land.vengeance != 0 {
+        hash.insert(key("vengeance"), number(land.vengeance));
+        hash.insert(key("vengeance-spreads"), Yaml::Boolean(land.vengeance_spreads));
+    }
+    Yaml::Hash(hash)
+}
+
+fn parse_land(desc: &GameDescription, land: &mut LandState, yaml: &Yaml, what: &str) -> Result<(), Box<dyn Error>> {
+    check_keys(yaml, what, &["in-play", "tokens", "presence", "invaders", "dahan", "defense", "fear-generated", "skip",
+        "vengeance", "vengeance-spreads"])?;
+
+    land.is_in_play = as_switch(&yaml["in-play"], "in-play", land.is_in_play)?;
+
+    land.tokens = TokenMap::new(|| 0);
+    if let Yaml::Hash(tokens) = &yaml["tokens"] {
+        for (kind, count) in tokens.iter() {
+            let kind = as_string(kind, "tokens")?;
+            match kind.parse::<PieceKind>()? {
+                PieceKind::Token(token) => land.tokens[token] = as_number(count, "tokens")?,
+                _ => bail!("`{}` is not a token.", kind),
+            }
+        }
+    }
+
+    land.presence = SpiritMap::new(|| 0);
+    let presence = as_optional_list(&yaml["presence"], "presence")?;
+    if presence.len() > land.presence.0.len() {
+        bail!("`{}.presence` lists more spirits than a game can have.", what);
+    }
+    for (i, count) in presence.iter().enumerate() {
+        land.presence[i as u8] = as_number(count, "presence")?;
+    }
+
+    land.invaders = as_optional_list(&yaml["invaders"], "invaders")?.iter()
+        .map(|i| parse_invader(desc, i))
+        .collect::<Result<_, _>>()?;
+    land.dahan = as_optional_list(&yaml["dahan"], "dahan")?.iter()
+        .map(parse_dahan)
+        .collect::<Result<_, _>>()?;
+
+    land.defense = as_number_or(&yaml["defense"], "defense", 0)?;
+    land.fear_generated_here_this_round = as_number_or(&yaml["fear-generated"], "fear-generated", 0)?;
+
+    land.skip_explore = false;
+    land.skip_build = InvaderMap::new(false);
+    land.skip_ravage = false;
+    for action in as_optional_list(&yaml["skip"], "skip")?.iter() {
+        match as_string(action, "skip")?.as_str() {
+            "Explore" => land.skip_explore = true,
+            "Build" => land.skip_build = InvaderMap::new(true),
+            "Build Town" => land.skip_build[InvaderKind::Town] = true,
+            "Build City" => land.skip_build[InvaderKind::City] = true,
+            "Ravage" => land.skip_ravage = true,
+            other => bail!("`{}` is not an invader action to skip.", other),
+        }
+    }
+
+    land.vengeance = as_number_or(&yaml["vengeance"], "vengeance", 0)?;
+    land.vengeance_spreads = as_switch(&yaml["vengeance-spreads"], "vengeance-spreads", false)?;
+
+    Ok(())
+}
+
+fn spirit_to_yaml(spirit: &SpiritState) -> Yaml {
+    let mut elements = yaml::Hash::new();
+    for (element, name) in ELEMENTS.iter() {
+        if spirit.elements[*element] != 0 {
+            elements.insert(key(name), number(spirit.elements[*element]));
+        }
+    }
+
+    let mut hash = yaml::Hash::new();
+    hash.insert(key("presence"), list_of(&spirit.presence, |p| key(&presence_to_string(p))));
+    hash.insert(key("energy"), number(spirit.energy));
+    hash.insert(key("plays"), number(spirit.plays));
+    hash.insert(key("elements"), Yaml::Hash(elements));
+    hash.insert(key("may-play-slows-as-fasts"), number(spirit.may_play_slows_as_fasts));
+    hash.insert(key("extra-range"), number(spirit.extra_range));
+    hash.insert(key("may-repeat-powers"), number(spirit.may_repeat_powers));
+    hash.insert(key("entwined-with"), Yaml::Array((0..spirit.entwined_with.0.len() as u8)
+        .filter(|i| spirit.entwined_with[*i])
+        .map(number)
+        .collect()));
+    hash.insert(key("dahan-health-bonus"), number(spirit.dahan_health_bonus));
+    hash.insert(key("dahan-ignore-damage"), Yaml::Boolean(spirit.dahan_ignore_damage));
+    hash.insert(key("hand"), power_cards_to_yaml(&spirit.deck.hand));
+    hash.insert(key("pending"), power_cards_to_yaml(&spirit.deck.pending));
+    hash.insert(key("discard"), power_cards_to_yaml(&spirit.deck.discard));
+    hash.insert(key("forgotten"), power_cards_to_yaml(&spirit.deck.forgotten));
+    Yaml::Hash(hash)
+}
+
+fn parse_spirit(desc: &GameDescription, spirit: &mut SpiritState, yaml: &Yaml, what: &str) -> Result<(), Box<dyn Error>> {
+    check_keys(yaml, what, &["presence", "energy", "plays", "elements", "may-play-slows-as-fasts",
+        "extra-range", "may-repeat-powers", "entwined-with", "dahan-health-bonus", "dahan-ignore-damage",
+        "hand", "pending", "discard", "forgotten"])?;
+
+    let presence = as_list(&yaml["presence"], "presence")?;
+    if presence.len() != spirit.presence.len() {
+        bail!("`{}.presence` must list all {} presence.", what, spirit.presence.len());
+    }
+    for (slot, text) in spirit.presence.iter_mut().zip(presence.iter()) {
+        *slot = parse_presence(&as_string(text, "presence")?)?;
+    }
+
+    spirit.energy = as_number(&yaml["energy"], "energy")?;
+    spirit.plays = as_number_or(&yaml["plays"], "plays", 0)?;
+    spirit.may_play_slows_as_fasts = as_number_or(&yaml["may-play-slows-as-fasts"], "may-play-slows-as-fasts", 0)?;
+    spirit.extra_range = as_number_or(&yaml["extra-range"], "extra-range", 0)?;
+    spirit.may_repeat_powers = as_number_or(&yaml["may-repeat-powers"], "may-repeat-powers", 0)?;
+    spirit.entwined_with = SpiritMap::new(|| false);
+    for other in as_optional_list(&yaml["entwined-with"], "entwined-with")?.iter() {
+        let other: u8 = as_number(other, "entwined-with")?;
+        if other as usize >= desc.spirits.len() {
+            bail!("`{}.entwined-with` lists spirit {}, but there are {} spirits.", what, other, desc.spirits.len());
+        }
+        spirit.entwined_with[other] = true;
+    }
+    spirit.dahan_health_bonus = as_number_or(&yaml["dahan-health-bonus"], "dahan-health-bonus", 0)?;
+    spirit.dahan_ignore_damage = as_switch(&yaml["dahan-ignore-damage"], "dahan-ignore-damage", false)?;
+
+    spirit.elements.set_all(0);
+    if let Yaml::Hash(elements) = &yaml["elements"] {
+        for (name, count) in elements.iter() {
+            let name = as_string(name, "elements")?;
+            match ELEMENTS.iter().find(|(_, n)| *n == name) {
+                Some((element, _)) => spirit.elements[*element] = as_number(count, "elements")?,
+                None => bail!("Unknown element `{}`.", name),
+            }
+        }
+    }
+
+    spirit.deck.hand = parse_power_cards(desc, &yaml["hand"], "hand")?;
+    spirit.deck.pending = parse_power_cards(desc, &yaml["pending"], "pending")?;
+    spirit.deck.discard = parse_power_cards(desc, &yaml["discard"], "discard")?;
+    spirit.deck.forgotten = parse_power_cards(desc, &yaml["forgotten"], "forgotten")?;
+
+    Ok(())
+}
+
+
+pub struct Snapshot {
+    pub config: RunConfig,
+    pub state: Yaml,
+}
+
+impl Snapshot {
+    pub fn capture(config: &RunConfig, game: &GameState) -> Result<Snapshot, Box<dyn Error>> {
+        if !game.effect_stack.is_empty() || !game.choices.is_empty() || !game.power_usages.is_empty() {
+            bail!("Snapshots can only be taken between steps.");
+        }
+
+        let position = game.rng.get_position();
+        if position > i64::MAX as u128 {
+            bail!("The rng position is too large to store.");
+        }
+
+        let mut invader = yaml::Hash::new();
+        invader.insert(key("draw"), invader_cards_to_yaml(&game.invader.draw));
+        invader.insert(key("pending"), Yaml::Array(game.invader.pending.iter().map(|p| invader_cards_to_yaml(p)).collect()));
+        invader.insert(key("discard"), invader_cards_to_yaml(&game.invader.discard));
+        invader.insert(key("steps"), list_of(&game.invader.sequence, |k| key(&k.to_string())));
+
+        let (tier2, tier3) = game.fear.terror_thresholds();
+        let mut fear = yaml::Hash::new();
+        fear.insert(key("draw"), fear_cards_to_yaml(&game.fear.draw));
+        fear.insert(key("pending"), fear_cards_to_yaml(&game.fear.pending));
+        fear.insert(key("discard"), fear_cards_to_yaml(&game.fear.discard));
+        fear.insert(key("terror-thresholds"), Yaml::Array(vec![number(tier2 as i64), number(tier3 as i64)]));
+        fear.insert(key("resolved"), number(game.fear.resolved as i64));
+
+        let mut blight = yaml::Hash::new();
+        blight.insert(key("draw"), blight_cards_to_yaml(&game.blight.draw));
+        if let Some(card) = &game.blight.card {
+            blight.insert(key("card"), key(card.desc.name));
+        }
+        blight.insert(key("flipped"), Yaml::Boolean(game.blight.flipped));
+        blight.insert(key("discard"), blight_cards_to_yaml(&game.blight.discard));
+
+        let mut events = yaml::Hash::new();
+        events.insert(key("draw"), event_cards_to_yaml(&game.events.draw));
+        events.insert(key("pending"), event_cards_to_yaml(&game.events.pending));
+        events.insert(key("discard"), event_cards_to_yaml(&game.events.discard));
+
+        let mut state = yaml::Hash::new();
+        state.insert(key("step"), key(&step_to_string(&game.step)));
+        state.insert(key("choice-count"), number(game.choice_count));
+        if let Some(reason) = &game.game_over_reason {
+            state.insert(key("game-over"), key(reason));
+        }
+        state.insert(key("rng"), number(position as i64));
+        state.insert(key("fear-pool"), number(game.fear_pool));
+        state.insert(key("fear-generated"), number(game.fear_generated));
+        state.insert(key("fear-generated-total"), number(game.fear_generated_total));
+        state.insert(key("blight-remaining"), number(game.blight_remaining));
+        state.insert(key("invader"), Yaml::Hash(invader));
+        state.insert(key("fear"), Yaml::Hash(fear));
+        state.insert(key("blight"), Yaml::Hash(blight));
+        if game.events.is_in_play() {
+            state.insert(key("events"), Yaml::Hash(events));
+        }
+        state.insert(key("minor-powers"), power_deck_to_yaml(&game.minor_powers));
+        state.insert(key("major-powers"), power_deck_to_yaml(&game.major_powers));
+        state.insert(key("lands"), list_of(&game.table.lands, |l| land_to_yaml(&game.desc, l)));
+        state.insert(key("spirits"), list_of(&game.spirits, spirit_to_yaml));
+
+        Ok(Snapshot {
+            config: config.clone(),
+            state: Yaml::Hash(state),
+        })
+    }
+
+    pub fn load(path: &str) -> Result<Snapshot, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| Box::<dyn Error>::from(format!("Could not read `{}`: {}", path, e)))?;
+
+        Snapshot::parse(&text)
+            .map_err(|e| Box::<dyn Error>::from(format!("In `{}`: {}", path, e)))
+    }
+
+    pub fn parse(text: &str) -> Result<Snapshot, Box<dyn Error>> {
+        let docs = YamlLoader::load_from_str(text)?;
+        let doc = match docs.first() {
+            Some(doc) => doc,
+            None => bail!("Snapshot is empty."),
+        };
+
+        check_keys(doc, "<root>", &["seed", "content", "game", "solver", "state"])?;
+        check_keys(&doc["state"], "state", &["step", "choice-count", "game-over", "rng",
+            "fear-pool", "fear-generated", "fear-generated-total", "blight-remaining",
+            "invader", "fear", "blight", "events", "minor-powers", "major-powers", "lands", "spirits"])?;
+
+        Ok(Snapshot {
+            config: RunConfig::from_yaml(doc)?,
+            state: doc["state"].clone(),
+        })
+    }
+
+    pub fn to_yaml_string(&self) -> Result<String, Box<dyn Error>> {
+        let mut doc = self.config.to_header_yaml();
+        doc.insert(key("state"), self.state.clone());
+
+        let mut text = String::new();
+        YamlEmitter::new(&mut text).dump(&Yaml::Hash(doc))?;
+        text.push('\n');
+
+        Ok(text)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_yaml_string()?)
+            .map_err(|e| Box::<dyn Error>::from(format!("Could not write `{}`: {}", path, e)))
+    }
+
+    // Rebuilds the game state against a fresh description of the game in the header.
+    pub fn make_state(&self) -> Result<GameState, Box<dyn Error>> {
+        self.build_state(None)
+    }
+
+    // Like `make_state`, but hidden draw piles are dealt at random from the cards not seen
+    // elsewhere, and the game's rng is seeded from `sample_seed` (a real game has no rng position).
+    pub fn make_sampled_state(&self, sample_seed: &str) -> Result<GameState, Box<dyn Error>> {
+        self.build_state(Some(sample_seed))
+    }
+
+    fn build_state(&self, sample_seed: Option<&str>) -> Result<GameState, Box<dyn Error>> {
+        let state = &self.state;
+
+        let desc = Arc::new(self.config.make_description()?);
+
+        let rng = match sample_seed {
+            Some(sample_seed) => Box::new(base_rng(sample_seed)),
+            None => {
+                let mut rng = Box::new(base_rng(&self.config.seed));
+                rng.set_position(as_number::<i64>(&state["rng"], "state.rng")? as u128);
+                rng
+            },
+        };
+        let mut sampler = base_rng(&format!("{}-hidden", sample_seed.unwrap_or("")));
+
+        let mut game = GameState::new(Arc::clone(&desc), rng);
+        game.fate = self.config.make_fate();
+        game.power_progression = self.config.make_power_progression();
+
+        game.step = parse_step(&as_string(&state["step"], "state.step")?)?;
+        game.next_step = game.step;
+        game.choice_count = as_number_or(&state["choice-count"], "state.choice-count", 0)?;
+        game.game_over_reason = match &state["game-over"] {
+            Yaml::BadValue | Yaml::Null => None,
+            v => Some(as_string(v, "state.game-over")?),
+        };
+
+        game.fear_pool = as_number(&state["fear-pool"], "state.fear-pool")?;
+        game.fear_generated = as_number(&state["fear-generated"], "state.fear-generated")?;
+        game.fear_generated_total = as_number(&state["fear-generated-total"], "state.fear-generated-total")?;
+        game.blight_remaining = as_number(&state["blight-remaining"], "state.blight-remaining")?;
+
+        let lands = as_list(&state["lands"], "state.lands")?;
+        if lands.len() != game.table.lands.len() {
+            bail!("`state.lands` must list all {} lands of the table.", game.table.lands.len());
+        }
+        for (i, (land, yaml)) in game.table.lands.iter_mut().zip(lands.iter()).enumerate() {
+            parse_land(&desc, land, yaml, &format!("state.lands[{}]", i))?;
+        }
+
+        // Spirits are only added to the game by init.
+        let spirits = as_optional_list(&state["spirits"], "state.spirits")?;
+        if !spirits.is_empty() && spirits.len() != desc.spirits.len() {
+            bail!("`state.spirits` must list all {} spirits of the game.", desc.spirits.len());
+        }
+        for (i, (spirit_desc, yaml)) in desc.spirits.iter().zip(spirits.iter()).enumerate() {
+            let mut spirit = SpiritState::new(spirit_desc);
+            parse_spirit(&desc, &mut spirit, yaml, &format!("state.spirits[{}]", i))?;
+            game.spirits.push(spirit);
+        }
+
+        let hidden = |what: &str| -> Result<(), Box<dyn Error>> {
+            if sample_seed.is_none() {
+                bail!("`{}` is hidden, positions with hidden cards can only be used to ask for advice.", what);
+            }
+            Ok(())
+        };
+
+        // The invader deck
+        {
+            let yaml = &state["invader"];
+            check_keys(yaml, "state.invader", &["draw", "pending", "discard", "steps"])?;
+
+            let draw = parse_pile(&yaml["draw"], "state.invader.draw", parse_invader_cards)?;
+            let discard = parse_invader_cards(&yaml["discard"], "state.invader.discard")?;
+            // Before init the deck has no invader steps (and so no pending slots) yet, adversaries
+            // can change them during the game.
+            let sequence = match &yaml["steps"] {
+                Yaml::BadValue | Yaml::Null if game.step == GameStep::Init => Vec::new(),
+                Yaml::BadValue | Yaml::Null => desc.adversary.invader_steps(),
+                steps => as_list(steps, "state.invader.steps")?.iter()
+                    .map(|k| Ok(as_string(k, "state.invader.steps")?.parse::<InvaderActionKind>()?))
+                    .collect::<Result<_, Box<dyn Error>>>()?,
+            };
+            game.invader.set_state(Vec::new(), discard, sequence);
+
+            let pending = as_optional_list(&yaml["pending"], "state.invader.pending")?;
+            if pending.len() != game.invader.pending.len() {
+                bail!("`state.invader.pending` must have one list per invader step ({}).", game.invader.pending.len());
+            }
+            game.invader.pending = pending.iter()
+                .map(|p| parse_invader_cards(p, "state.invader.pending"))
+                .collect::<Result<VecDeque<_>, _>>()?;
+
+            game.invader.draw = match draw {
+                Pile::Known(draw) => draw,
+                Pile::Hidden(count) => {
+                    hidden("state.invader.draw")?;
+                    let seen: Vec<InvaderCard> = game.invader.pending.iter().flatten()
+                        .chain(game.invader.discard.iter())
+                        .cloned()
+                        .collect();
+                    deal_invader_draw(&desc.adversary.invader_deck_stages(), &seen, count, sampler.get_rng())?
+                },
+            };
+        }
+
+        // The fear deck
+        {
+            let yaml = &state["fear"];
+            check_keys(yaml, "state.fear", &["draw", "pending", "discard", "terror-thresholds", "resolved"])?;
+
+            let (t1, t2, t3) = desc.adversary.fear_cards();
+            let thresholds = match &yaml["terror-thresholds"] {
+                Yaml::BadValue | Yaml::Null => ((t2 + t3) as usize, t3 as usize),
+                v => match as_list(v, "state.fear.terror-thresholds")?.as_slice() {
+                    [tier2, tier3] => (as_number(tier2, "state.fear.terror-thresholds")?, as_number(tier3, "state.fear.terror-thresholds")?),
+                    _ => bail!("`state.fear.terror-thresholds` must be the two draw pile sizes at which terror level II and III begin."),
+                },
+            };
+
+            let pending = parse_fear_cards(&desc, &yaml["pending"], "state.fear.pending")?;
+            let discard = parse_fear_cards(&desc, &yaml["discard"], "state.fear.discard")?;
+            let draw = match parse_pile(&yaml["draw"], "state.fear.draw", |yaml, what| parse_fear_cards(&desc, yaml, what))? {
+                Pile::Known(draw) => draw,
+                Pile::Hidden(count) => {
+                    hidden("state.fear.draw")?;
+                    // Unlike the other decks only some of the fear cards are used.
+                    let count = count.unwrap_or((t1 + t2 + t3) as usize - min((t1 + t2 + t3) as usize, pending.len() + discard.len()));
+
+                    let mut unseen: Vec<FearCard> = desc.fear.iter().enumerate()
+                        .filter(|(_, d)| !pending.iter().chain(discard.iter()).any(|c| Arc::ptr_eq(&c.desc, d)))
+                        .map(|(index, d)| FearCard { desc: Arc::clone(d), index })
+                        .collect();
+                    unseen.shuffle(sampler.get_rng());
+                    deal(unseen, Some(count), "state.fear.draw")?
+                },
+            };
+
+            // Whatever isn't in the game was set aside during setup.
+            let unused = desc.fear.iter().enumerate()
+                .filter(|(_, d)| !draw.iter().chain(pending.iter()).chain(discard.iter()).any(|c| Arc::ptr_eq(&c.desc, d)))
+                .map(|(index, d)| FearCard { desc: Arc::clone(d), index })
+                .collect();
+
+            game.fear.set_state(draw, pending, discard, unused, thresholds);
+            game.fear.resolved = as_number_or(&yaml["resolved"], "state.fear.resolved", 0)?;
+        }
+
+        // The blight deck, snapshots taken before it existed have no blight card
+        if let Yaml::Hash(_) = &state["blight"] {
+            let yaml = &state["blight"];
+            check_keys(yaml, "state.blight", &["draw", "card", "flipped", "discard"])?;
+
+            game.blight.card = match &yaml["card"] {
+                Yaml::BadValue | Yaml::Null => None,
+                v => Some(find_blight_card(&desc, &as_string(v, "state.blight.card")?)?),
+            };
+            game.blight.flipped = as_switch(&yaml["flipped"], "state.blight.flipped", false)?;
+            game.blight.discard = parse_blight_cards(&desc, &yaml["discard"], "state.blight.discard")?;
+            game.blight.draw = match parse_pile(&yaml["draw"], "state.blight.draw", |yaml, what| parse_blight_cards(&desc, yaml, what))? {
+                Pile::Known(draw) => draw,
+                Pile::Hidden(count) => {
+                    hidden("state.blight.draw")?;
+                    let seen: Vec<&BlightCard> = game.blight.card.iter().chain(game.blight.discard.iter()).collect();
+                    let mut unseen: Vec<BlightCard> = desc.blight.iter().enumerate()
+                        .filter(|(_, d)| !seen.iter().any(|c| Arc::ptr_eq(&c.desc, d)))
+                        .map(|(index, d)| BlightCard { desc: Arc::clone(d), index })
+                        .collect();
+                    unseen.shuffle(sampler.get_rng());
+                    deal(unseen, count, "state.blight.draw")?
+                },
+            };
+        }
+
+        // The event deck, only with the events variant
+        if let Yaml::Hash(_) = &state["events"] {
+            let yaml = &state["events"];
+            check_keys(yaml, "state.events", &["draw", "pending", "discard"])?;
+
+            game.events.pending = parse_event_cards(&desc, &yaml["pending"], "state.events.pending")?;
+            game.events.discard = parse_event_cards(&desc, &yaml["discard"], "state.events.discard")?;
+            game.events.draw = match parse_pile(&yaml["draw"], "state.events.draw", |yaml, what| parse_event_cards(&desc, yaml, what))? {
+                Pile::Known(draw) => draw,
+                Pile::Hidden(count) => {
+                    hidden("state.events.draw")?;
+                    let seen: Vec<&EventCard> = game.events.pending.iter().chain(game.events.discard.iter()).collect();
+                    let mut unseen: Vec<EventCard> = desc.events.iter().enumerate()
+                        .filter(|(_, d)| !seen.iter().any(|c| Arc::ptr_eq(&c.desc, d)))
+                        .map(|(index, d)| EventCard { desc: Arc::clone(d), index })
+                        .collect();
+                    unseen.shuffle(sampler.get_rng());
+                    deal(unseen, count, "state.events.draw")?
+                },
+            };
+        }
+
+        // The power decks
+        let (mut minor_powers, minor_draw) = parse_power_deck(&desc, &state["minor-powers"], "state.minor-powers")?;
+        let (mut major_powers, major_draw) = parse_power_deck(&desc, &state["major-powers"], "state.major-powers")?;
+
+        let seen_powers: Vec<PowerCard> = game.spirits.iter()
+            .flat_map(|s| s.deck.hand.iter().chain(s.deck.pending.iter()).chain(s.deck.discard.iter()).chain(s.deck.forgotten.iter()))
+            .chain(minor_powers.pending.iter()).chain(minor_powers.discard.iter())
+            .chain(major_powers.pending.iter()).chain(major_powers.discard.iter())
+            .cloned()
+            .collect();
+        for (deck, draw, kind, what) in [
+                (&mut minor_powers, minor_draw, PowerCardKind::Minor, "state.minor-powers.draw"),
+                (&mut major_powers, major_draw, PowerCardKind::Major, "state.major-powers.draw")] {
+            deck.draw = match draw {
+                Pile::Known(draw) => draw,
+                Pile::Hidden(count) => {
+                    hidden(what)?;
+                    let mut unseen: Vec<PowerCard> = desc.powers.iter()
+                        .filter(|d| d.kind == kind)
+                        .enumerate()
+                        .filter(|(_, d)| !seen_powers.iter().any(|c| Arc::ptr_eq(&c.desc, d)))
+                        .map(|(index, d)| PowerCard { desc: Arc::clone(d), index })
+                        .collect();
+                    unseen.shuffle(sampler.get_rng());
+                    deal(unseen, count, what)?
+                },
+            };
+        }
+        game.minor_powers = minor_powers;
+        game.major_powers = major_powers;
+
+        Ok(game)
+    }
+}
+
+fn base_rng(seed: &str) -> DeterministicChaCha {
+    DeterministicChaCha::new(ChaChaRng::from_seed(make_seed(seed)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::play_steps;
+
+    #[test]
+    fn steps_round_trip_through_text() {
+        let steps = [
+            GameStep::Init,
+            GameStep::SetupExplore,
+            GameStep::Turn(0, TurnStep::Spirit(SpiritStep::Growth)),
+            GameStep::Turn(1, TurnStep::Invader(InvaderStep::Event(0, 2))),
+            GameStep::Turn(2, TurnStep::Invader(InvaderStep::FearEffect(1))),
+            GameStep::Turn(3, TurnStep::Invader(InvaderStep::InvaderAction(3, 1))),
+            GameStep::Turn(4, TurnStep::TimePasses),
+        ];
+        for step in steps.iter() {
+            assert!(parse_step(&step_to_string(step)).unwrap() == *step, "{}", step_to_string(step));
+        }
+
+        assert!(parse_step("turn 0 growth").is_err());
+        assert!(parse_step("turn 1 event 0").is_err());
+        assert!(parse_step("turn one growth").is_err());
+    }
+
+    #[test]
+    fn snapshot_resumes_where_it_was_taken() {
+        let config = RunConfig::parse("seed: test\ngame:\n  spirits: [river, lightning]\n  boards: [A, B]\n  adversary:\n    name: england\n    difficulty: 2\n").unwrap();
+        let mut game = config.make_state().unwrap();
+        play_steps(&mut game, 30);
+
+        let snapshot = Snapshot::capture(&config, &game).unwrap();
+        let mut resumed = Snapshot::parse(&snapshot.to_yaml_string().unwrap()).unwrap().make_state().unwrap();
+        assert!(resumed.step == game.step);
+        assert_eq!(resumed.canonical_hash(), game.canonical_hash());
+
+        // and plays on the same way
+        play_steps(&mut game, 30);
+        play_steps(&mut resumed, 30);
+        assert!(resumed.step == game.step);
+        assert_eq!(resumed.canonical_hash(), game.canonical_hash());
+    }
+}
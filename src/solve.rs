@@ -7,7 +7,7 @@ use std::{
         Arc, Weak, Mutex,
         atomic::{Ordering, AtomicUsize},
     },
-    collections::{VecDeque},
+    collections::{VecDeque, HashMap},
     time::Instant,
 };
 
@@ -15,7 +15,13 @@ use rand::prelude::*;
 
 use crossbeam::{unbounded, atomic::AtomicCell};
 
-use crate::base::{GameState, StepFailure, DecisionChoice};
+use crate::base::{GameState, GameStep, StepFailure, DecisionChoice};
+
+mod evaluate;
+mod mcts;
+
+pub use self::evaluate::*;
+pub use self::mcts::*;
 
 
 pub trait SolveStrategy : Send + Sync {
@@ -24,6 +30,29 @@ pub trait SolveStrategy : Send + Sync {
 }
 
 
+// Bounds the tree engine's search. Past the horizon positions aren't played out but scored by the
+// evaluator instead. Turns count from the turn the search starts in (from the first turn when
+// starting during setup), decisions are the decision steps taken from the root.
+#[derive(Clone)]
+pub struct TreeOptions {
+    pub horizon_turns: Option<u8>,
+    pub horizon_decisions: Option<usize>,
+}
+
+impl TreeOptions {
+    pub fn new() -> TreeOptions {
+        TreeOptions {
+            horizon_turns: None,
+            horizon_decisions: None,
+        }
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.horizon_turns.is_some() || self.horizon_decisions.is_some()
+    }
+}
+
+
 #[derive(Clone)]
 pub struct BasicStatistics {
     pub victories: usize,
@@ -33,6 +62,14 @@ pub struct BasicStatistics {
     pub min_score: i16,
     pub max_score: i16,
 
+    // Every end of the search: finished games by their score and positions at the horizon by their
+    // evaluation (`evaluated` of them).
+    pub ends: usize,
+    pub evaluated: usize,
+    pub min_value: f64,
+    pub max_value: f64,
+    pub total_value: f64,
+
     pub first_best_game: Vec<VecDeque<DecisionChoice>>,
 }
 
@@ -46,10 +83,27 @@ impl BasicStatistics {
             min_score: std::i16::MAX,
             max_score: std::i16::MIN,
 
+            ends: 0,
+            evaluated: 0,
+            min_value: f64::INFINITY,
+            max_value: f64::NEG_INFINITY,
+            total_value: 0.0,
+
             first_best_game: Vec::new(),
         }
     }
 
+    pub fn expected_value(&self) -> f64 {
+        self.total_value / self.ends as f64
+    }
+
+    fn add_value(&mut self, value: f64) {
+        self.ends += 1;
+        self.total_value += value;
+        self.min_value = self.min_value.min(value);
+        self.max_value = self.max_value.max(value);
+    }
+
     pub fn merge(&mut self, other: &BasicStatistics) {
         self.victories += other.victories;
         self.defeats += other.defeats;
@@ -61,6 +115,12 @@ impl BasicStatistics {
 
         self.min_score = min(self.min_score, other.min_score);
         self.max_score = max(self.max_score, other.max_score);
+
+        self.ends += other.ends;
+        self.evaluated += other.evaluated;
+        self.total_value += other.total_value;
+        self.min_value = self.min_value.min(other.min_value);
+        self.max_value = self.max_value.max(other.max_value);
     }
 
     fn collect_first_best_game(branch: &SolveBranch, choices: Option<VecDeque<DecisionChoice>>) -> Result<Vec<VecDeque<DecisionChoice>>, Box<dyn Error>> {
@@ -115,10 +175,17 @@ impl BasicStatistics {
 
             self.min_score = min(self.min_score, score);
             self.max_score = max(self.max_score, score);
+            self.add_value(score as f64);
         }
 
         Ok(())
     }
+
+    // A position at the horizon, scored by the evaluator.
+    pub fn consume_horizon(&mut self, value: f64) {
+        self.evaluated += 1;
+        self.add_value(value);
+    }
 }
 
 
@@ -148,6 +215,9 @@ struct SolveBranchInternal {
 pub struct SolveBranch {
     pub parent: Weak<SolveBranch>,
     pub decision_edge: VecDeque<DecisionChoice>,
+    pub canonical_hash: u64,
+    // how many decision steps were taken from the root
+    pub depth: usize,
 
     // Only store to this if you hold the below mutex!!
     state: AtomicCell<SolveBranchState>,
@@ -162,8 +232,10 @@ impl SolveBranch {
         decision_edge: VecDeque<DecisionChoice>,
     ) -> SolveBranch {
         SolveBranch {
+            depth: parent.upgrade().map_or(0, |p| p.depth + 1),
             parent: parent,
             decision_edge,
+            canonical_hash: game_state.canonical_hash(),
 
             state: AtomicCell::new(SolveBranchState::Inited),
 
@@ -182,9 +254,69 @@ impl SolveBranch {
     }
 }
 
+// The statistics of finalized branches by the canonical hash of their game state, so branches that
+// reach the same position through a different order of decisions reuse them instead of expanding
+// it again. Once full nothing more is stored.
+pub struct TranspositionTable {
+    shards: Vec<Mutex<HashMap<u64, BasicStatistics>>>,
+    capacity: usize,
+
+    pub stored: AtomicUsize,
+    pub hits: AtomicUsize,
+}
+
+impl TranspositionTable {
+    const SHARDS: usize = 64;
+
+    pub fn new(capacity: usize) -> TranspositionTable {
+        TranspositionTable {
+            shards: (0..TranspositionTable::SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+            capacity,
+
+            stored: AtomicUsize::new(0),
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    fn shard(&self, hash: u64) -> Result<std::sync::MutexGuard<'_, HashMap<u64, BasicStatistics>>, Box<dyn Error>> {
+        self.shards[(hash % TranspositionTable::SHARDS as u64) as usize].lock()
+            .or(Err(Box::<dyn std::error::Error>::from("Could not obtain transposition lock.")))
+    }
+
+    pub fn get(&self, hash: u64) -> Result<Option<BasicStatistics>, Box<dyn Error>> {
+        if self.capacity == 0 {
+            return Ok(None);
+        }
+
+        let found = self.shard(hash)?.get(&hash).cloned();
+        if found.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(found)
+    }
+
+    pub fn store(&self, hash: u64, stats: &BasicStatistics) -> Result<(), Box<dyn Error>> {
+        if self.stored.load(Ordering::Relaxed) >= self.capacity {
+            return Ok(());
+        }
+
+        if self.shard(hash)?.insert(hash, stats.clone()).is_none() {
+            self.stored.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
 struct SolveEngineShared {
     pub init_branch: Arc<SolveBranch>,
     pub strategy: Box<dyn SolveStrategy>,
+    pub evaluator: Box<dyn Evaluator>,
+    pub options: TreeOptions,
+    pub horizon_turn: Option<u8>,
+    pub transpositions: TranspositionTable,
+
+    // The statistics of each choice of the root decision, in the order they were first seen.
+    pub root_choices: Mutex<Vec<(String, BasicStatistics)>>,
 
     pub branches: AtomicUsize,
     pub steps: AtomicUsize,
@@ -194,9 +326,59 @@ struct SolveEngineShared {
 }
 
 impl SolveEngineShared {
-    
+
+    fn at_horizon(&self, branch: &SolveBranch, state: &GameState) -> bool {
+        if let (Some(horizon), GameStep::Turn(turn, _)) = (self.horizon_turn, state.step) {
+            if turn >= horizon {
+                return true;
+            }
+        }
+
+        self.options.horizon_decisions.is_some_and(|decisions| branch.depth >= decisions)
+    }
+
+    // With a decision horizon the same position reached at another depth has another budget left,
+    // so it is a different entry.
+    fn transposition_key(&self, hash: u64, depth: usize) -> u64 {
+        match self.options.horizon_decisions {
+            Some(_) => hash ^ (depth as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            None => hash,
+        }
+    }
+
+    fn is_root(&self, branch: &SolveBranch) -> bool {
+        std::ptr::eq(branch, Arc::as_ptr(&self.init_branch))
+    }
+
+    fn record_root_choice(&self, choices: &VecDeque<DecisionChoice>, stats: &BasicStatistics) -> Result<(), Box<dyn Error>> {
+        let key = match choices.front() {
+            Some(choice) => choice.to_string(),
+            None => return Ok(()),
+        };
+
+        let mut root_choices = self.root_choices.lock()
+            .or(Err(Box::<dyn std::error::Error>::from("Could not obtain root choices lock.")))?;
+        match root_choices.iter_mut().find(|(choice, _)| *choice == key) {
+            Some((_, row)) => row.merge(stats),
+            None => {
+                let mut row = BasicStatistics::new();
+                row.merge(stats);
+                root_choices.push((key, row));
+            },
+        }
+
+        Ok(())
+    }
+
     pub fn do_branch_execute(&self, branch: &SolveBranch, branch_internal: &mut SolveBranchInternal) -> Result<(), Box<dyn Error>> {
         loop {
+            if self.at_horizon(branch, &branch_internal.game_state) {
+                let value = self.evaluator.evaluate(&branch_internal.game_state);
+                branch_internal.stats.consume_horizon(value);
+                branch.state.store(SolveBranchState::Completed);
+                break;
+            }
+
             let mut working_state = branch_internal.game_state.clone();
             //working_state.enable_logging = true; // HACK
             let res = working_state.step();
@@ -246,6 +428,14 @@ impl SolveEngineShared {
                 Ok(_) => {
                     working_state.advance()?;
 
+                    if let Some(stats) = self.transpositions.get(self.transposition_key(working_state.canonical_hash(), branch.depth + 1))? {
+                        branch_internal.stats.merge(&stats);
+                        if self.is_root(branch) {
+                            self.record_root_choice(&choices, &stats)?;
+                        }
+                        continue;
+                    }
+
                     let new_branch = SolveBranch::new(Arc::downgrade(branch), working_state, choices);
 
                     // agressively prune these branches
@@ -257,6 +447,9 @@ impl SolveEngineShared {
         
                             if new_branch.state.load() == SolveBranchState::Completed {
                                 branch_internal.stats.merge(&new_branch_internal.stats);
+                                if self.is_root(branch) {
+                                    self.record_root_choice(&new_branch.decision_edge, &new_branch_internal.stats)?;
+                                }
 
                                 false
                             } else {
@@ -284,7 +477,14 @@ impl SolveEngineShared {
                     self.do_branch_expand(branch, branch_internal, choices)?;
                 },
                 Err(terminal) => {
-                    branch_internal.stats.consume(branch, Some(choices), &working_state, &terminal)?;
+                    if self.is_root(branch) {
+                        let mut stats = BasicStatistics::new();
+                        stats.consume(branch, Some(choices.clone()), &working_state, &terminal)?;
+                        branch_internal.stats.merge(&stats);
+                        self.record_root_choice(&choices, &stats)?;
+                    } else {
+                        branch_internal.stats.consume(branch, Some(choices), &working_state, &terminal)?;
+                    }
                 },
             };
         }
@@ -317,7 +517,11 @@ impl SolveEngineShared {
                 .or(Err(Box::<dyn std::error::Error>::from("Could not obtain branch lock.")))?;
 
             current_stats.merge(&sub_branch_internal.stats);
+            if self.is_root(branch) {
+                self.record_root_choice(&sub_branch.decision_edge, &sub_branch_internal.stats)?;
+            }
         }
+        self.transpositions.store(self.transposition_key(branch.canonical_hash, branch.depth), current_stats)?;
 
         branch_internal.branches.clear();
         let prev = self.branches_finalized.fetch_add(1, Ordering::Relaxed);
@@ -398,12 +602,31 @@ enum SolveWork {
 }
 
 impl SolveEngine {
-    pub fn new(init_state: &GameState, strategy: Box<dyn SolveStrategy>) -> SolveEngine {
+    // `transpositions` is how many positions to remember, 0 turns the transposition table off.
+    // The evaluator only scores positions at the horizon of `options`.
+    pub fn new(
+        init_state: &GameState,
+        strategy: Box<dyn SolveStrategy>,
+        evaluator: Box<dyn Evaluator>,
+        options: TreeOptions,
+        transpositions: usize,
+    ) -> SolveEngine {
+        let horizon_turn = match (options.horizon_turns, init_state.step) {
+            (Some(turns), GameStep::Turn(turn, _)) => Some(turn.saturating_add(turns)),
+            (Some(turns), _) => Some(turns),
+            (None, _) => None,
+        };
+
         SolveEngine {
             init_state: init_state.clone(),
 
             shared: Arc::new(SolveEngineShared {
                 strategy: strategy,
+                evaluator,
+                options,
+                horizon_turn,
+                transpositions: TranspositionTable::new(transpositions),
+                root_choices: Mutex::new(Vec::new()),
                 init_branch: Arc::new(SolveBranch::new(Weak::new(), init_state.clone(), VecDeque::new())),
 
                 steps: AtomicUsize::new(0),
@@ -439,49 +662,42 @@ impl SolveEngine {
         Ok(())
     }
 
-    pub fn resimulate_game(&self, mut choices: Vec<VecDeque<DecisionChoice>>) -> Result<(), Box<dyn Error>> {
-        choices.reverse();
-
-        let mut current_state = self.init_state.clone();
-        current_state.enable_logging = true;
+    // The values found within the horizon, and the choices of the root decision ranked by the best
+    // value reachable after them (the expected value breaking ties).
+    fn print_horizon(&self, stats: &BasicStatistics) -> Result<(), Box<dyn Error>> {
+        if stats.ends == 0 {
+            println!("    value: - (nothing was reached)");
+            return Ok(());
+        }
+        println!("    value: min {:.2},  expected {:.2},  max {:.2}  ({} ends, {} at the horizon)",
+            stats.min_value, stats.expected_value(), stats.max_value, stats.ends, stats.evaluated);
+
+        let mut rows = self.shared.root_choices.lock()
+            .or(Err(Box::<dyn std::error::Error>::from("Could not obtain root choices lock.")))?
+            .clone();
+        if rows.is_empty() {
+            return Ok(());
+        }
+        rows.sort_by(|(_, a), (_, b)| b.max_value.total_cmp(&a.max_value)
+            .then(b.expected_value().total_cmp(&a.expected_value())));
+
+        println!();
+        println!("  rank  choice                            max  expected     min   ends  victories");
+        for (rank, (choice, row)) in rows.iter().enumerate() {
+            println!("  {:>4}  {:<30} {:>6.2} {:>9.2} {:>7.2} {:>6} {:>10}",
+                rank + 1, choice, row.max_value, row.expected_value(), row.min_value, row.ends, row.victories);
+        }
 
-        let mut pull_decision = false;
-        loop {
-            let mut working_state = current_state.clone();
-            if pull_decision {
-                pull_decision = false;
-                // TODO fix my fucking errors
-                working_state.choices = choices.pop().unwrap();
-            }
+        Ok(())
+    }
 
-            let res = working_state.step();
+    pub fn resimulate_game(&self, choices: Vec<VecDeque<DecisionChoice>>) -> Result<(), Box<dyn Error>> {
+        resimulate_game(&self.init_state, choices, None)?;
 
-            match res {
-                Ok(_) => {
-                    current_state = working_state;
-                    current_state.advance()?;
-                    continue;
-                },
-                Err(StepFailure::DecisionRequired) => {
-                    pull_decision = true;
-                    continue;
-                },
-                Err(StepFailure::GameOverVictory) => {
-                    println!("Victory!    {}", working_state.game_over_reason.as_ref().unwrap());
-                    return Ok(());
-                }
-                Err(StepFailure::GameOverDefeat) => {
-                    println!("Defeat :(   {}", working_state.game_over_reason.as_ref().unwrap());
-                    return Ok(());
-                }
-                Err(fail) => {
-                    return Err(Box::<dyn std::error::Error>::from(fail));
-                }
-            }
-        };
+        Ok(())
     }
 
-    pub fn main(&mut self, threads: usize) -> Result<(), Box<dyn Error>> {
+    pub fn main(&mut self, threads: usize) -> Result<BasicStatistics, Box<dyn Error>> {
         // TODO use strtaegy to decide on execution order
         // TODO have parallel worker threads
 
@@ -566,16 +782,113 @@ impl SolveEngine {
             }
     
             println!("  v: {},  d: {},  e: {}", stats.victories, stats.defeats, stats.errors);
-            println!("    min: {},  max: {}  ", stats.min_score, stats.max_score);
+            println!("    transpositions: {} stored, {} reused",
+                self.shared.transpositions.stored.load(Ordering::Relaxed), self.shared.transpositions.hits.load(Ordering::Relaxed));
+            if stats.victories + stats.defeats != 0 {
+                println!("    min: {},  max: {}  ", stats.min_score, stats.max_score);
+            } else {
+                println!("    min: -,  max: -  (no game was played to the end)");
+            }
+            if self.shared.options.is_bounded() {
+                self.print_horizon(stats)?;
+            }
             println!("");
 
-            Ok(())
+            Ok(stats.clone())
         }
     }
 }
 
 
 
+// Replays a game (with logging) from its initial state and the decision edges taken, returning the
+// final state. With `stop_at` the replay stops (without running it) when that step is reached.
+pub fn resimulate_game(init_state: &GameState, mut choices: Vec<VecDeque<DecisionChoice>>, stop_at: Option<GameStep>) -> Result<GameState, Box<dyn Error>> {
+    choices.reverse();
+
+    let mut current_state = init_state.clone();
+    current_state.enable_logging = true;
+
+    let mut pull_decision = false;
+    loop {
+        if Some(current_state.step) == stop_at {
+            return Ok(current_state);
+        }
+
+        let mut working_state = current_state.clone();
+        if pull_decision {
+            pull_decision = false;
+            working_state.choices = match choices.pop() {
+                Some(edge) => edge,
+                None => bail!("Ran out of choices at step {} before the game ended.", working_state.step),
+            };
+        }
+
+        let res = working_state.step();
+
+        match res {
+            Ok(_) => {
+                current_state = working_state;
+                current_state.advance()?;
+                continue;
+            },
+            Err(StepFailure::DecisionRequired) => {
+                pull_decision = true;
+                continue;
+            },
+            Err(StepFailure::GameOverVictory) => {
+                println!("Victory!    {}", working_state.game_over_reason.as_ref().unwrap());
+                return Ok(working_state);
+            }
+            Err(StepFailure::GameOverDefeat) => {
+                println!("Defeat :(   {}", working_state.game_over_reason.as_ref().unwrap());
+                return Ok(working_state);
+            }
+            Err(fail) => {
+                return Err(Box::<dyn std::error::Error>::from(fail));
+            }
+        }
+    };
+}
+
+
+
+// Plays a game out to the end from `state`, making every decision (after `choices`) uniformly at
+// random. Returns the final state.
+pub fn playout(state: &GameState, mut choices: VecDeque<DecisionChoice>, rng: &mut dyn RngCore) -> Result<GameState, Box<dyn Error>> {
+    let mut current_state = state.clone();
+
+    loop {
+        let mut working_state = current_state.clone();
+        working_state.choices = choices.clone();
+
+        match working_state.step() {
+            Ok(_) => {
+                current_state = working_state;
+                current_state.advance()?;
+                choices.clear();
+            },
+            Err(StepFailure::DecisionRequired) => {
+                let decision = working_state.effect_stack.last()
+                    .and_then(|e| e.as_decision())
+                    .ok_or_else(|| Box::<dyn std::error::Error>::from("Decision required without a decision."))?;
+
+                match decision.valid_choices(&working_state).choose(rng) {
+                    Some(choice) => choices.push_back(choice.clone()),
+                    None => bail!("Decision at {} has no valid choices.", working_state.step),
+                }
+            },
+            Err(StepFailure::GameOverVictory) | Err(StepFailure::GameOverDefeat) => {
+                return Ok(working_state);
+            },
+            Err(fail) => {
+                return Err(Box::<dyn std::error::Error>::from(fail));
+            },
+        }
+    }
+}
+
+
 pub struct SimpleDecisionMaker {
     take_first: u8
 }
@@ -603,6 +916,37 @@ impl SolveStrategy for SimpleDecisionMaker {
 
 
 
+// Fate mode: chance decisions (the draws of fated decks) are branched over by one strategy, every
+// other decision by another.
+pub struct FateDecisionMaker {
+    fate: Box<dyn SolveStrategy>,
+    play: Box<dyn SolveStrategy>,
+}
+
+impl FateDecisionMaker {
+    pub fn new(fate: Box<dyn SolveStrategy>, play: Box<dyn SolveStrategy>) -> Box<FateDecisionMaker> {
+        Box::new(FateDecisionMaker {
+            fate,
+            play,
+        })
+    }
+}
+
+impl SolveStrategy for FateDecisionMaker {
+    fn decide(&self, state: &GameState) -> Vec<DecisionChoice> {
+        let undecided_decision = state.effect_stack.last().unwrap();
+        let decision = undecided_decision.as_decision().unwrap();
+
+        if decision.is_chance() {
+            self.fate.decide(state)
+        } else {
+            self.play.decide(state)
+        }
+    }
+}
+
+
+
 pub struct StochasticDecisionMaker {
     pub rng: Box<dyn DeterministicRng>,
     take_first: u8
@@ -639,3 +983,42 @@ impl SolveStrategy for StochasticDecisionMaker {
         decisions
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(victories: usize) -> BasicStatistics {
+        let mut stats = BasicStatistics::new();
+        stats.victories = victories;
+        stats
+    }
+
+    #[test]
+    fn transpositions_are_stored_up_to_capacity() {
+        let table = TranspositionTable::new(2);
+        assert!(table.get(1).unwrap().is_none());
+
+        table.store(1, &stats(1)).unwrap();
+        table.store(1, &stats(2)).unwrap();
+        table.store(65, &stats(65)).unwrap();
+        assert_eq!(table.get(1).unwrap().unwrap().victories, 2);
+        assert_eq!(table.get(65).unwrap().unwrap().victories, 65);
+        assert_eq!(table.stored.load(Ordering::Relaxed), 2);
+        assert_eq!(table.hits.load(Ordering::Relaxed), 2);
+
+        // full
+        table.store(3, &stats(3)).unwrap();
+        assert!(table.get(3).unwrap().is_none());
+        assert_eq!(table.stored.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn transpositions_are_off_without_capacity() {
+        let table = TranspositionTable::new(0);
+        table.store(1, &stats(1)).unwrap();
+        assert!(table.get(1).unwrap().is_none());
+        assert_eq!(table.stored.load(Ordering::Relaxed), 0);
+    }
+}
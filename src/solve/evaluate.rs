@@ -0,0 +1,111 @@
+use crate::base::{GameState, InvaderKind, PresenceState, TokenKind};
+
+
+/*
+    Evaluators score positions that aren't over yet, so searches can stop early and still rank
+    choices. Values are in the units of `GameState::score_game` (which finished games are scored
+    with), a position scores about what a game ending there would.
+
+    The weighted evaluator sums features of the position, each times its weight. Island features
+    (blight, invaders, dahan, presence, energy) are per player like in `score_game`.
+*/
+
+
+pub trait Evaluator : Send + Sync {
+    fn evaluate(&self, state: &GameState) -> f64;
+}
+
+
+#[derive(Clone)]
+pub struct EvaluationWeights {
+    // per blight on the island
+    pub blight: f64,
+    // per invader on the island, by kind
+    pub explorer: f64,
+    pub town: f64,
+    pub city: f64,
+    // per living dahan
+    pub dahan: f64,
+    // per fear card earned, the fear generated towards the next card counts as a fraction
+    pub fear: f64,
+    // per presence on the island
+    pub presence: f64,
+    // per energy a spirit has
+    pub energy: f64,
+    // per invader card no longer in the draw pile (the game has lasted that much longer)
+    pub invader_cards: f64,
+}
+
+impl EvaluationWeights {
+    pub fn new() -> EvaluationWeights {
+        EvaluationWeights {
+            blight: -1.0,
+            explorer: -0.2,
+            town: -0.5,
+            city: -1.0,
+            dahan: 1.0,
+            fear: 0.5,
+            presence: 0.25,
+            energy: 0.1,
+            invader_cards: 1.0,
+        }
+    }
+}
+
+
+pub struct WeightedEvaluator {
+    pub weights: EvaluationWeights,
+}
+
+impl WeightedEvaluator {
+    pub fn new(weights: EvaluationWeights) -> Box<WeightedEvaluator> {
+        Box::new(WeightedEvaluator {
+            weights,
+        })
+    }
+}
+
+impl Evaluator for WeightedEvaluator {
+    fn evaluate(&self, state: &GameState) -> f64 {
+        if state.is_over() {
+            return state.score_game() as f64;
+        }
+
+        let w = &self.weights;
+        let players = state.spirits.len().max(1) as f64;
+        let mut value = 0.0;
+
+        // 1. The island
+        for land in state.table.lands.iter() {
+            value += w.blight * land.tokens[TokenKind::Blight] as f64 / players;
+            value += w.dahan * land.dahan.len() as f64 / players;
+
+            for invader in land.invaders.iter() {
+                value += match invader.kind {
+                    InvaderKind::Explorer => w.explorer,
+                    InvaderKind::Town => w.town,
+                    InvaderKind::City => w.city,
+                } / players;
+            }
+        }
+
+        // 2. Fear
+        let cards_earned = state.fear.pending.len() + state.fear.discard.len();
+        let pool = (state.fear_generated + state.fear_pool) as f64;
+        let towards_next = if pool > 0.0 { state.fear_generated as f64 / pool } else { 0.0 };
+        value += w.fear * (cards_earned as f64 + towards_next);
+
+        // 3. The spirits
+        for spirit in state.spirits.iter() {
+            let on_island = spirit.presence.iter().filter(|p| matches!(p, PresenceState::OnBoard(_))).count();
+            value += w.presence * on_island as f64 / players;
+            value += w.energy * spirit.energy as f64 / players;
+        }
+
+        // 4. The invader deck
+        let invader_cards = state.invader.pending.iter().map(|p| p.len()).sum::<usize>() + state.invader.discard.len();
+        value += w.invader_cards * invader_cards as f64;
+
+        value
+    }
+}
@@ -0,0 +1,407 @@
+use std::{
+    error::Error,
+    thread,
+    sync::Arc,
+    collections::{VecDeque, HashMap},
+    time::{Duration, Instant},
+};
+
+use rand::prelude::*;
+use rand_chacha::{ChaChaRng};
+
+use crate::base::{self, GameState, GameStep, StepFailure, DecisionChoice};
+
+use super::{SolveStrategy, Evaluator};
+
+
+/*
+    Monte Carlo Tree Search, for games where the full tree explodes.
+
+    Each iteration walks down the tree from the root picking children by UCT, expands one untried
+    choice, plays the rest of the game out (rollout) and adds the final score to every node on the
+    way down. The children of a node (and the choices a rollout picks from at random) are whatever
+    the strategy decides, so `play-branch` (and `setup-branch` in fate mode) limit the search.
+
+    Nodes don't store game states, every iteration re-steps the game from the root. Each thread
+    grows its own tree, the root statistics are merged at the end.
+
+    With `rollout_turns` rollouts stop that many turns after the root and the position is scored by
+    the evaluator instead, so the value of a node mixes final scores and evaluations.
+
+    Like the tree engine, lines that reach a decision without choices are dropped: the iteration
+    counts as an error and (inside the tree) the node is never selected again.
+*/
+
+
+#[derive(Clone)]
+pub struct MctsOptions {
+    pub iterations: Option<usize>,
+    pub seconds: Option<f64>,
+    pub exploration: f64,
+    pub rollout_turns: Option<u8>,
+}
+
+pub struct MctsRow {
+    pub choice: String,
+
+    pub visits: usize,
+    pub victories: usize,
+    pub total_value: f64,
+    // Over the games played out to the end only.
+    pub max_score: i16,
+}
+
+impl MctsRow {
+    pub fn mean_value(&self) -> f64 {
+        self.total_value / self.visits as f64
+    }
+}
+
+pub struct MctsResult {
+    // Sorted by visits, the first is the best decision.
+    pub rows: Vec<MctsRow>,
+
+    pub max_score: i16,
+    pub best_game: Vec<VecDeque<DecisionChoice>>,
+}
+
+
+// A game being walked forward one decision at a time, remembering the decision edges taken (in the
+// form `resimulate_game` and game records use).
+#[derive(Clone)]
+struct Walk {
+    state: GameState,
+    choices: VecDeque<DecisionChoice>,
+    edges: Vec<VecDeque<DecisionChoice>>,
+}
+
+enum Position {
+    // The working state, with the undecided decision on top of its effect stack.
+    Decision(GameState),
+    Over(GameState),
+}
+
+impl Walk {
+    fn run(&mut self) -> Result<Position, Box<dyn Error>> {
+        loop {
+            let mut working_state = self.state.clone();
+            working_state.choices = self.choices.clone();
+
+            match working_state.step() {
+                Ok(_) => {
+                    self.state = working_state;
+                    self.state.advance()?;
+                    self.choices.clear();
+                },
+                Err(StepFailure::DecisionRequired) => {
+                    return Ok(Position::Decision(working_state));
+                },
+                Err(StepFailure::GameOverVictory) | Err(StepFailure::GameOverDefeat) => {
+                    return Ok(Position::Over(working_state));
+                },
+                Err(fail) => {
+                    return Err(Box::<dyn Error>::from(fail));
+                },
+            }
+        }
+    }
+
+    fn choose(&mut self, choice: DecisionChoice) {
+        self.choices.push_back(choice);
+        self.edges.push(self.choices.clone());
+    }
+}
+
+
+struct Node {
+    visits: usize,
+    victories: usize,
+    total_value: f64,
+    max_score: i16,
+
+    expanded: bool,
+    dead: bool,
+    untried: Vec<DecisionChoice>,
+    children: Vec<(DecisionChoice, usize)>,
+}
+
+impl Node {
+    fn new() -> Node {
+        Node {
+            visits: 0,
+            victories: 0,
+            total_value: 0.0,
+            max_score: i16::MIN,
+
+            expanded: false,
+            dead: false,
+            untried: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+    errors: usize,
+    // The range of values seen, to scale mean values into [0, 1] for UCT.
+    min_seen: f64,
+    max_seen: f64,
+
+    best_score: i16,
+    best_game: Vec<VecDeque<DecisionChoice>>,
+}
+
+impl Tree {
+    fn select(&self, node: usize, exploration: f64) -> Option<(DecisionChoice, usize)> {
+        let parent = &self.nodes[node];
+        let range = (self.max_seen - self.min_seen).max(1.0);
+        let log_visits = (parent.visits.max(1) as f64).ln();
+
+        let uct = |child: &Node| -> f64 {
+            // children whose first rollout was dropped haven't been visited yet
+            if child.visits == 0 {
+                return f64::INFINITY;
+            }
+            let exploit = (child.total_value / child.visits as f64 - self.min_seen) / range;
+            exploit + exploration * (log_visits / child.visits as f64).sqrt()
+        };
+
+        parent.children.iter()
+            .filter(|(_, index)| !self.nodes[*index].dead)
+            .max_by(|(_, a), (_, b)| uct(&self.nodes[*a]).partial_cmp(&uct(&self.nodes[*b])).unwrap())
+            .cloned()
+    }
+
+    fn iterate(&mut self, root: &Walk, strategy: &dyn SolveStrategy, evaluator: &dyn Evaluator, options: &MctsOptions, horizon: Option<u8>, rng: &mut dyn RngCore) -> Result<(), Box<dyn Error>> {
+        let mut walk = root.clone();
+        let mut node = 0;
+        let mut path = vec![0];
+
+        // 1. Selection and expansion
+        let mut position = walk.run()?;
+        while let Position::Decision(working_state) = &position {
+            if !self.nodes[node].expanded {
+                let mut untried = strategy.decide(working_state);
+                untried.reverse();
+                self.nodes[node].untried = untried;
+                self.nodes[node].expanded = true;
+            }
+
+            if let Some(choice) = self.nodes[node].untried.pop() {
+                let child = self.nodes.len();
+                self.nodes.push(Node::new());
+                self.nodes[node].children.push((choice.clone(), child));
+                path.push(child);
+
+                walk.choose(choice);
+                position = walk.run()?;
+                break;
+            }
+
+            let (choice, child) = match self.select(node, options.exploration) {
+                Some(selected) => selected,
+                None => {
+                    self.nodes[node].dead = true;
+                    self.errors += 1;
+                    return Ok(());
+                },
+            };
+            node = child;
+            path.push(node);
+
+            walk.choose(choice);
+            position = walk.run()?;
+        }
+
+        // 2. Rollout (up to the horizon)
+        while let Position::Decision(working_state) = &position {
+            if let (Some(horizon), GameStep::Turn(turn, _)) = (horizon, walk.state.step) {
+                if turn >= horizon {
+                    break;
+                }
+            }
+
+            match strategy.decide(working_state).choose(rng) {
+                Some(choice) => walk.choose(choice.clone()),
+                None => {
+                    self.errors += 1;
+                    return Ok(());
+                },
+            }
+            position = walk.run()?;
+        }
+
+        // 3. Backpropagation
+        let (value, score, victory) = match &position {
+            Position::Over(end) => {
+                let score = end.score_game();
+                if score > self.best_score {
+                    self.best_score = score;
+                    self.best_game = walk.edges.clone();
+                }
+                (score as f64, Some(score), end.step == GameStep::Victory)
+            },
+            // cut off at the horizon, mid step: evaluate the position the step started from
+            Position::Decision(_) => (evaluator.evaluate(&walk.state), None, false),
+        };
+
+        self.min_seen = self.min_seen.min(value);
+        self.max_seen = self.max_seen.max(value);
+
+        for index in path {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            node.total_value += value;
+            if let Some(score) = score {
+                node.max_score = node.max_score.max(score);
+            }
+            if victory {
+                node.victories += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+pub struct MctsEngine {
+    init_state: GameState,
+    strategy: Arc<Box<dyn SolveStrategy>>,
+    evaluator: Arc<Box<dyn Evaluator>>,
+    options: MctsOptions,
+    seed: String,
+}
+
+impl MctsEngine {
+    pub fn new(init_state: &GameState, strategy: Box<dyn SolveStrategy>, evaluator: Box<dyn Evaluator>, options: MctsOptions, seed: &str) -> MctsEngine {
+        MctsEngine {
+            init_state: init_state.clone(),
+            strategy: Arc::new(strategy),
+            evaluator: Arc::new(evaluator),
+            options,
+            seed: seed.to_string(),
+        }
+    }
+
+    pub fn main(&self, threads: usize) -> Result<MctsResult, Box<dyn Error>> {
+        if self.options.iterations.is_none() && self.options.seconds.is_none() {
+            bail!("MCTS needs an iteration or time budget.");
+        }
+
+        // Find the root decision first, the tree is pointless without one.
+        let mut root = Walk {
+            state: self.init_state.clone(),
+            choices: VecDeque::new(),
+            edges: Vec::new(),
+        };
+        let decision_step = match root.run()? {
+            Position::Decision(working_state) => working_state.step,
+            Position::Over(end) => bail!("The game ends before the first decision ({}).", end.game_over_reason.as_ref().unwrap()),
+        };
+
+        let horizon = match (self.options.rollout_turns, decision_step) {
+            (Some(turns), GameStep::Turn(turn, _)) => Some(turn.saturating_add(turns)),
+            (Some(turns), _) => Some(turns),
+            (None, _) => None,
+        };
+
+        let threads = threads.max(1);
+        let start = Instant::now();
+        let deadline = self.options.seconds.map(|seconds| start + Duration::from_secs_f64(seconds));
+
+        let mut handles = Vec::new();
+        for id in 0..threads {
+            let root = root.clone();
+            let strategy = Arc::clone(&self.strategy);
+            let evaluator = Arc::clone(&self.evaluator);
+            let options = self.options.clone();
+            // Split the iterations between the threads, the first ones taking the remainder.
+            let iterations = self.options.iterations.map(|total| total / threads + if id < total % threads { 1 } else { 0 });
+            let seed = format!("{}-mcts-{}", self.seed, id);
+
+            handles.push(thread::spawn(move || -> Result<Tree, String> {
+                let mut rng = ChaChaRng::from_seed(base::make_seed(&seed));
+                let mut tree = Tree {
+                    nodes: vec![Node::new()],
+                    errors: 0,
+                    min_seen: f64::INFINITY,
+                    max_seen: f64::NEG_INFINITY,
+
+                    best_score: i16::MIN,
+                    best_game: Vec::new(),
+                };
+
+                let mut done = 0;
+                while !tree.nodes[0].dead && iterations.is_none_or(|i| done < i) && deadline.is_none_or(|d| Instant::now() < d) {
+                    tree.iterate(&root, strategy.as_ref().as_ref(), evaluator.as_ref().as_ref(), &options, horizon, &mut rng)
+                        .map_err(|e| e.to_string())?;
+                    done += 1;
+                }
+
+                Ok(tree)
+            }));
+        }
+
+        let mut rows: HashMap<String, MctsRow> = HashMap::new();
+        let mut iterations = 0;
+        let mut errors = 0;
+        let mut max_score = i16::MIN;
+        let mut best_game = Vec::new();
+        for handle in handles {
+            let tree = handle.join()
+                .map_err(|_| Box::<dyn Error>::from("An MCTS thread panicked."))??;
+
+            iterations += tree.nodes[0].visits + tree.errors;
+            errors += tree.errors;
+            if tree.best_score > max_score {
+                max_score = tree.best_score;
+                best_game = tree.best_game.clone();
+            }
+
+            for (choice, index) in tree.nodes[0].children.iter() {
+                let node = &tree.nodes[*index];
+                let row = rows.entry(choice.to_string()).or_insert_with(|| MctsRow {
+                    choice: choice.to_string(),
+
+                    visits: 0,
+                    victories: 0,
+                    total_value: 0.0,
+                    max_score: i16::MIN,
+                });
+
+                row.visits += node.visits;
+                row.victories += node.victories;
+                row.total_value += node.total_value;
+                row.max_score = row.max_score.max(node.max_score);
+            }
+        }
+
+        let mut rows: Vec<MctsRow> = rows.into_values().filter(|row| row.visits > 0).collect();
+        rows.sort_by(|a, b| b.visits.cmp(&a.visits).then(b.mean_value().partial_cmp(&a.mean_value()).unwrap()));
+
+        println!("Elapsed: {:.2}s", start.elapsed().as_secs_f64());
+        println!("Searched the decision at {} ({} iterations, {} dropped).", decision_step, iterations, errors);
+        println!("  rank  choice                          visits     mean   win%    max");
+        for (rank, row) in rows.iter().enumerate() {
+            let max_score = if row.max_score == i16::MIN { "-".to_string() } else { row.max_score.to_string() };
+            println!("  {:>4}  {:<30} {:>7} {:>8.2} {:>6.1} {:>6}",
+                rank + 1, row.choice, row.visits, row.mean_value(),
+                100.0 * row.victories as f64 / row.visits as f64, max_score);
+        }
+        if max_score != i16::MIN {
+            println!("    max: {}", max_score);
+        } else {
+            println!("    max: - (no game was played to the end)");
+        }
+        println!();
+
+        Ok(MctsResult {
+            rows,
+
+            max_score,
+            best_game,
+        })
+    }
+}
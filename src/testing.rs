@@ -0,0 +1,84 @@
+// Helpers shared by the unit tests.
+
+use std::collections::VecDeque;
+
+use crate::{
+    base::{
+        GameState, GameStep, TurnStep, SpiritStep, StepFailure, ContentPack,
+        SpiritDescription, BoardDescription, FearCardDescription, PowerCardDescription,
+        BlightCardDescription, EventCardDescription,
+        effect::*,
+    },
+    config::RunConfig,
+};
+
+
+// A new game of the spirits on their boards (seed `test`, no adversary), before its setup.
+pub fn new_game(spirits: &[&str], boards: &[&str]) -> GameState {
+    let text = format!("seed: test\ngame:\n  spirits: [{}]\n  boards: [{}]\n", spirits.join(", "), boards.join(", "));
+    RunConfig::parse(&text).unwrap().make_state().unwrap()
+}
+
+// Steps a game through its setup, up to the growth of the first turn.
+pub fn set_up(game: &mut GameState) {
+    while game.step != GameStep::Turn(0, TurnStep::Spirit(SpiritStep::Growth)) {
+        game.step().unwrap();
+        game.advance().unwrap();
+    }
+}
+
+// A game of the spirits on their boards, set up and ready for the first growth.
+pub fn set_up_game(spirits: &[&str], boards: &[&str]) -> GameState {
+    let mut game = new_game(spirits, boards);
+    set_up(&mut game);
+    game
+}
+
+// Plays up to `steps` steps of a game (stopping when it ends), making the first valid choice of
+// every decision.
+pub fn play_steps(game: &mut GameState, steps: usize) {
+    for _ in 0..steps {
+        let mut choices = VecDeque::new();
+        loop {
+            let mut working = game.clone();
+            working.choices = choices.clone();
+            match working.step() {
+                Ok(()) => {
+                    *game = working;
+                    game.advance().unwrap();
+                    break;
+                },
+                Err(StepFailure::DecisionRequired) => {
+                    let decision = working.effect_stack.last().and_then(|e| e.as_decision()).unwrap();
+                    choices.push_back(decision.valid_choices(&working)[0].clone());
+                },
+                Err(StepFailure::GameOverVictory) | Err(StepFailure::GameOverDefeat) => return,
+                Err(failure) => panic!("{:?} at {}", failure, working.step),
+            }
+        }
+    }
+}
+
+
+// The `test-events` content pack, a single event card (to play alongside `core`): 1 fear, and a
+// dahan in land 1 for its dahan event.
+pub struct TestEventContent {}
+
+impl ContentPack for TestEventContent {
+    fn get_spirits(&self) -> Vec<Box<dyn SpiritDescription>> { Vec::new() }
+    fn get_boards(&self) -> Vec<BoardDescription> { Vec::new() }
+    fn get_fear_cards(&self) -> Vec<FearCardDescription> { Vec::new() }
+    fn get_power_cards(&self) -> Vec<PowerCardDescription> { Vec::new() }
+    fn get_blight_cards(&self) -> Vec<BlightCardDescription> { Vec::new() }
+
+    fn get_event_cards(&self) -> Vec<EventCardDescription> {
+        vec![
+            EventCardDescription {
+                name: "Test Event",
+                main: Box::new(GenerateFearEffect{ fear: 1, land_index: None }),
+                token: None,
+                dahan: Some(Box::new(AddDahanEffect{ land_index: 1, count: 1 })),
+            },
+        ]
+    }
+}